@@ -1,13 +1,19 @@
 use crate::alloc::string::ToString;
 use crate::proof::verify_signature;
-use alloc::format;
 use alloc::vec;
 use alloc::vec::Vec;
-use ibc_client_solomachine_types::error::Error;
+#[cfg(feature = "candid")]
+use ibc_client_solomachine_types::candid_types::CandidClientState;
+use ibc_client_solomachine_types::error::{Error, SignatureKind};
+use ibc_client_solomachine_types::proof::sign_bytes_path;
 use ibc_client_solomachine_types::proof::types::sign_bytes::SignBytes;
+use ibc_client_solomachine_types::v2::{
+    migrate_client_state, SOLOMACHINE_V2_CLIENT_STATE_TYPE_URL,
+};
 use ibc_client_solomachine_types::{
     client_type as sm_client_type, ClientState as ClientStateType,
     ConsensusState as ConsensusStateType, Header as SmHeader, Misbehaviour as SmMisbehaviour,
+    TimePolicy, VerificationLimits,
 };
 use ibc_core::client::context::client_state::{
     ClientStateCommon, ClientStateExecution, ClientStateValidation,
@@ -23,8 +29,9 @@ use ibc_core::commitment_types::merkle::apply_prefix;
 use ibc_core::host::types::identifiers::{ClientId, ClientType};
 use ibc_core::host::types::path::Path;
 use ibc_core::host::types::path::{ClientConsensusStatePath, ClientStatePath};
-use ibc_core::host::ExecutionContext;
+use ibc_core::primitives::Timestamp;
 use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::core::commitment::v1::MerklePath;
 use ibc_proto::ibc::lightclients::solomachine::v3::ClientState as RawSmClientState;
 use ibc_proto::Protobuf;
 
@@ -33,7 +40,7 @@ pub mod update_client;
 
 use super::consensus_state::ConsensusState as SmConsensusState;
 use crate::context::{
-    ExecutionContext as SmExecutionContext, ValidationContext as SmValidationContext,
+    CommonContext, ExecutionContext as SmExecutionContext, ValidationContext as SmValidationContext,
 };
 
 pub const SOLOMACHINE_CLIENT_STATE_TYPE_URL: &str = "/ibc.lightclients.solomachine.v3.ClientState";
@@ -45,6 +52,18 @@ pub const SOLOMACHINE_CLIENT_STATE_TYPE_URL: &str = "/ibc.lightclients.solomachi
 /// bypass Rust's orphan rules and implement traits from
 /// `ibc::core::client::context` on the `ClientState` type.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
 #[derive(Clone, Debug, PartialEq)]
 pub struct ClientState(ClientStateType);
 
@@ -52,6 +71,108 @@ impl ClientState {
     pub fn inner(&self) -> &ClientStateType {
         &self.0
     }
+
+    /// Upgrades the stored client state of `client_id` in place: a v2 client
+    /// state is migrated to v3 and stored, while a v3 client state is returned
+    /// unchanged.
+    ///
+    /// No consensus state is stored: the client keeps its consensus state in
+    /// the client state, and ibc-go v7 prunes the solo machine consensus states
+    /// in the same migration. Hosts keeping v2 consensus states prune them.
+    pub fn migrate<E>(ctx: &mut E, client_id: &ClientId, stored: Any) -> Result<Self, ClientError>
+    where
+        E: SmExecutionContext,
+        <E as ClientExecutionContext>::AnyClientState: From<ClientState>,
+    {
+        let is_v2 = stored.type_url == SOLOMACHINE_V2_CLIENT_STATE_TYPE_URL;
+        let client_state = Self(migrate_client_state(stored)?);
+        if is_v2 {
+            ctx.store_client_state(ClientStatePath::new(client_id), client_state.clone().into())?;
+        }
+        Ok(client_state)
+    }
+
+    /// Verifies that `proof` signs `value` at the already prefixed
+    /// `merkle_path`, as 08-wasm hosts pass it. An empty value proves
+    /// non-membership. The proof height carries the sequence the proof is
+    /// signed at, which must be the client sequence. The proof is checked
+    /// against the limits and the time policy of `ctx`.
+    pub fn verify_signed_path<V>(
+        &self,
+        ctx: &V,
+        proof_height: Height,
+        proof: &CommitmentProofBytes,
+        merkle_path: &MerklePath,
+        value: Vec<u8>,
+    ) -> Result<(), ClientError>
+    where
+        V: SmValidationContext,
+    {
+        let time_policy = ctx.time_policy();
+        let time_bounds = if time_policy.is_enabled() {
+            let host_timestamp = ctx.host_timestamp().map_err(|e| ClientError::Other {
+                description: e.to_string(),
+            })?;
+            Some((time_policy, host_timestamp))
+        } else {
+            None
+        };
+        self.verify_signed_path_with(
+            &ctx.verification_limits(),
+            time_bounds,
+            proof_height,
+            proof,
+            merkle_path,
+            value,
+        )
+    }
+
+    /// Verifies a proof like [`Self::verify_signed_path`], against `limits`
+    /// and, if given, a time policy and the host timestamp it is checked
+    /// against, for callers without a host context.
+    //
+    // A solo machine signs every proof at its current sequence, so ibc-go
+    // requires the proof sequence to equal the client sequence. The revision
+    // number of the latest height is always 0, so other revisions are
+    // rejected too.
+    // ref: https://github.com/cosmos/ibc-go/blob/v7.3.0/modules/light-clients/06-solomachine/client_state.go
+    pub fn verify_signed_path_with(
+        &self,
+        limits: &VerificationLimits,
+        time_bounds: Option<(TimePolicy, Timestamp)>,
+        proof_height: Height,
+        proof: &CommitmentProofBytes,
+        merkle_path: &MerklePath,
+        value: Vec<u8>,
+    ) -> Result<(), ClientError> {
+        if self.latest_height() != proof_height {
+            return Err(Error::SequenceMismatch {
+                expected: self.latest_height(),
+                got: proof_height,
+            }
+            .into());
+        }
+        limits.check_data(&value)?;
+        let (public_key, sig_data, timestamp, sequence) =
+            self.0.produce_verification_args(proof, limits)?;
+        if let Some((time_policy, host_timestamp)) = time_bounds {
+            time_policy.check_proof(timestamp, host_timestamp)?;
+        }
+        if merkle_path.key_path.is_empty() {
+            return Err(Error::PathCannotEmpty.into());
+        }
+        limits.check_merkle_path(merkle_path)?;
+        let sign_bytes = SignBytes {
+            sequence,
+            timestamp: timestamp.nanoseconds(),
+            diversifier: self.0.consensus_state.diversifier.clone(),
+            path: sign_bytes_path(merkle_path),
+            data: value,
+        };
+        let sign_bz = sign_bytes.encode_vec();
+        verify_signature(public_key, sign_bz, sig_data, limits)
+            .map_err(|e| e.for_signature(SignatureKind::Proof).into())
+    }
 }
 
 impl From<ClientStateType> for ClientState {
@@ -60,8 +181,25 @@ impl From<ClientStateType> for ClientState {
     }
 }
 
-impl Protobuf<RawSmClientState> for ClientState {}
+#[cfg(feature = "candid")]
+impl From<&ClientState> for CandidClientState {
+    fn from(client_state: &ClientState) -> Self {
+        (&client_state.0).into()
+    }
+}
+
+#[cfg(feature = "candid")]
+impl TryFrom<CandidClientState> for ClientState {
+    type Error = Error;
+
+    fn try_from(client_state: CandidClientState) -> Result<Self, Self::Error> {
+        Ok(Self(client_state.try_into()?))
+    }
+}
 
+// Like those of the wrapped client state, the raw conversions carry only the
+// ibc-go fields and drop the client state extension. Encode the client state
+// as an `Any` to keep it.
 impl TryFrom<RawSmClientState> for ClientState {
     type Error = Error;
 
@@ -113,6 +251,9 @@ impl ClientStateCommon for ClientState {
     }
 
     fn validate_proof_height(&self, proof_height: Height) -> Result<(), ClientError> {
+        // the sequence is encoded in the revision height of the proof height,
+        // which is checked to equal the client sequence when the proof is
+        // verified
         if self.latest_height() < proof_height {
             return Err(ClientError::InvalidProofHeight {
                 latest_height: self.latest_height(),
@@ -141,6 +282,11 @@ impl ClientStateCommon for ClientState {
         Ok(())
     }
 
+    // ibc-core verifies proofs without a host context, so proofs checked
+    // here are held to the default limits and are not bounded by a time
+    // policy. Hosts applying their own limits or time policy to proofs verify
+    // them with `verify_signed_path`. Neither is the proof height passed on,
+    // proofs are checked to be signed at the client sequence instead.
     fn verify_membership(
         &self,
         prefix: &CommitmentPrefix,
@@ -149,25 +295,15 @@ impl ClientStateCommon for ClientState {
         path: Path,
         value: Vec<u8>,
     ) -> Result<(), ClientError> {
-        let (public_key, sig_data, timestamp, sequence) =
-            self.0.produce_verification_args(proof)?;
         let merkle_path = apply_prefix(prefix, vec![path.to_string()]);
-        if merkle_path.key_path.is_empty() {
-            return Err(ClientError::Other {
-                description: "path is empty".to_string(),
-            });
-        }
-        let sign_bytes = SignBytes {
-            sequence,
-            timestamp: timestamp.nanoseconds(),
-            diversifier: self.0.consensus_state.diversifier.clone(),
-            path: merkle_path,
-            data: value,
-        };
-        let sign_bz = sign_bytes.encode_vec();
-        verify_signature(public_key, sign_bz, sig_data).map_err(|e| ClientError::Other {
-            description: e.to_string(),
-        })
+        self.verify_signed_path_with(
+            &VerificationLimits::default(),
+            None,
+            self.latest_height(),
+            proof,
+            &merkle_path,
+            value,
+        )
     }
 
     fn verify_non_membership(
@@ -177,26 +313,15 @@ impl ClientStateCommon for ClientState {
         _root: &CommitmentRoot,
         path: Path,
     ) -> Result<(), ClientError> {
-        let (public_key, sig_data, timestamp, sequence) =
-            self.0.produce_verification_args(proof)?;
         let merkle_path = apply_prefix(prefix, vec![path.to_string()]);
-        if merkle_path.key_path.is_empty() {
-            return Err(ClientError::Other {
-                description: "path is empty".to_string(),
-            });
-        }
-        let sign_bytes = SignBytes {
-            sequence,
-            timestamp: timestamp.nanoseconds(),
-            diversifier: self.0.consensus_state.diversifier.clone(),
-            path: merkle_path,
-            data: vec![],
-        };
-        let sign_bz = sign_bytes.encode_vec();
-
-        verify_signature(public_key, sign_bz, sig_data).map_err(|e| ClientError::Other {
-            description: e.to_string(),
-        })
+        self.verify_signed_path_with(
+            &VerificationLimits::default(),
+            None,
+            self.latest_height(),
+            proof,
+            &merkle_path,
+            vec![],
+        )
     }
 }
 
@@ -213,13 +338,22 @@ where
         client_message: Any,
         update_kind: &UpdateKind,
     ) -> Result<(), ClientError> {
+        if self.0.is_frozen {
+            return Err(Error::ClientStateIsFrozen.into());
+        }
+
+        let limits = ctx.verification_limits();
         match update_kind {
             UpdateKind::UpdateClient => {
-                let header = SmHeader::try_from(client_message)?;
+                let header = SmHeader::decode_with_limits(client_message, &limits)?;
+                header.validate_basic().map_err(Error::into_header_error)?;
                 self.verify_header(ctx, client_id, header)
             }
             UpdateKind::SubmitMisbehaviour => {
-                let misbehaviour = SmMisbehaviour::try_from(client_message)?;
+                let misbehaviour = SmMisbehaviour::decode_with_limits(client_message, &limits)?;
+                misbehaviour
+                    .validate_basic()
+                    .map_err(Error::into_misbehaviour_error)?;
                 self.verify_misbehaviour(ctx, client_id, misbehaviour)
             }
         }
@@ -244,35 +378,21 @@ where
         }
     }
 
-    fn status(&self, _ctx: &V, _client_id: &ClientId) -> Result<Status, ClientError> {
+    fn status(&self, ctx: &V, _client_id: &ClientId) -> Result<Status, ClientError> {
         if self.0.is_frozen {
             return Ok(Status::Frozen);
         }
 
-        // let latest_consensus_state: SmConsensusState = {
-        //     let any_latest_consensus_state = match ctx
-        //         .consensus_state(&ClientConsensusStatePath::new(client_id, &self.sequence))
-        //     {
-        //         Ok(cs) => cs,
-        //         // if the client state does not have an associated consensus state for its latest height
-        //         // then it must be expired
-        //         Err(_) => return Ok(Status::Expired),
-        //     };
-
-        //     any_latest_consensus_state.try_into()?
-        // };
-
-        // Note: if the `duration_since()` is `None`, indicating that the latest
-        // consensus state is in the future, then we don't consider the client
-        // to be expired.
-        // let now = ctx.host_timestamp()?;
-        // if let Some(elapsed_since_latest_consensus_state) =
-        //     now.duration_since(&latest_consensus_state.timestamp())
-        // {
-        //     if elapsed_since_latest_consensus_state > self.consensus_state.timestamp.into() {
-        //         return Ok(Status::Expired);
-        //     }
-        // }
+        // The client state carries the latest consensus state. Note: if the
+        // latest consensus state is in the future the client is not expired.
+        if self.0.inactivity_period.is_some() {
+            let now = CommonContext::host_timestamp(ctx).map_err(|e| ClientError::Other {
+                description: e.to_string(),
+            })?;
+            if self.0.is_expired(now) {
+                return Ok(Status::Expired);
+            }
+        }
 
         Ok(Status::Active)
     }
@@ -280,7 +400,7 @@ where
 
 impl<E> ClientStateExecution<E> for ClientState
 where
-    E: SmExecutionContext + ExecutionContext,
+    E: SmExecutionContext,
     <E as ClientExecutionContext>::AnyClientState: From<ClientState>,
     <E as ClientExecutionContext>::AnyConsensusState: From<SmConsensusState>,
 {
@@ -290,17 +410,29 @@ where
         client_id: &ClientId,
         consensus_state: Any,
     ) -> Result<(), ClientError> {
+        self.0.validate_basic()?;
+
+        // the initial consensus state must be the one the client state carries
         let sm_consensus_state = SmConsensusState::try_from(consensus_state)?;
+        if sm_consensus_state.inner() != &self.0.consensus_state {
+            return Err(Error::ConsensusStateMismatch.into());
+        }
+
+        let latest_height = self.latest_height();
+        let host_timestamp = CommonContext::host_timestamp(ctx)?;
+        let host_height = CommonContext::host_height(ctx)?;
 
         ctx.store_client_state(ClientStatePath::new(client_id), self.clone().into())?;
         ctx.store_consensus_state(
             ClientConsensusStatePath::new(
                 client_id.clone(),
-                self.latest_height().revision_number(),
-                self.latest_height().revision_height(),
+                latest_height.revision_number(),
+                latest_height.revision_height(),
             ),
             sm_consensus_state.into(),
         )?;
+        ctx.store_update_time(client_id.clone(), latest_height, host_timestamp)?;
+        ctx.store_update_height(client_id.clone(), latest_height, host_height)?;
 
         Ok(())
     }
@@ -311,19 +443,34 @@ where
         client_id: &ClientId,
         header: Any,
     ) -> Result<Vec<Height>, ClientError> {
-        let sm_header = SmHeader::try_from(header).map_err(|e| ClientError::Other {
-            description: format!("decode SmHeader Error({})", e),
-        })?;
+        let sm_header = SmHeader::try_from(header)?;
         let consensus_state = SmConsensusState::from(ConsensusStateType::new(
             sm_header.new_public_key,
             sm_header.new_diversifier,
             sm_header.timestamp,
         ));
         let mut new_client_state = self.clone();
-        new_client_state.0.sequence.increment();
+        new_client_state.0.sequence = new_client_state.0.sequence.increment();
         let new_height = new_client_state.0.sequence;
         new_client_state.0.consensus_state = consensus_state.inner().clone();
+
+        let host_timestamp = CommonContext::host_timestamp(ctx)?;
+        let host_height = CommonContext::host_height(ctx)?;
+
+        // the consensus state is stored at the new sequence as well, so that
+        // the host can look it up by height
+        ctx.store_consensus_state(
+            ClientConsensusStatePath::new(
+                client_id.clone(),
+                new_height.revision_number(),
+                new_height.revision_height(),
+            ),
+            consensus_state.into(),
+        )?;
         ctx.store_client_state(ClientStatePath::new(client_id), new_client_state.into())?;
+        ctx.store_update_time(client_id.clone(), new_height, host_timestamp)?;
+        ctx.store_update_height(client_id.clone(), new_height, host_height)?;
+
         Ok(vec![new_height])
     }
 
@@ -334,7 +481,7 @@ where
         _client_message: Any,
         _update_kind: &UpdateKind,
     ) -> Result<(), ClientError> {
-        let frozen_client_state = self.clone().0.with_frozen_height(Height::min(0));
+        let frozen_client_state = self.clone().0.with_frozen();
 
         let wrapped_frozen_client_state = ClientState::from(frozen_client_state);
 
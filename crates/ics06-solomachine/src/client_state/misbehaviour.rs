@@ -1,7 +1,10 @@
 use crate::proof::verify_signature;
+use ibc_client_solomachine_types::error::{Error, SignatureKind};
 use ibc_client_solomachine_types::proof::types::sign_bytes::SignBytes;
 use ibc_client_solomachine_types::proof::types::signature_and_data::SignatureAndData;
-use ibc_client_solomachine_types::{Header as SmHeader, Misbehaviour as SmMisbehaviour};
+use ibc_client_solomachine_types::{
+    Header as SmHeader, Misbehaviour as SmMisbehaviour, VerificationLimits,
+};
 use ibc_core::client::types::error::ClientError;
 use ibc_core::host::types::identifiers::ClientId;
 use ibc_core::primitives::prelude::*;
@@ -16,7 +19,7 @@ impl SmClientState {
     // the same height would have convinced the light client.
     pub fn verify_misbehaviour<ClientValidationContext>(
         &self,
-        _ctx: &ClientValidationContext,
+        ctx: &ClientValidationContext,
         _client_id: &ClientId,
         misbehaviour: SmMisbehaviour,
     ) -> Result<(), ClientError>
@@ -25,28 +28,37 @@ impl SmClientState {
     {
         // NOTE: a check that the misbehaviour message data are not equal is done by
         // misbehaviour.ValidateBasic which is called by the 02-client keeper.
+        let limits = ctx.verification_limits();
         // verify first signature
-        self.verify_signature_and_data(misbehaviour.clone(), misbehaviour.signature_one.clone())
-            .map_err(|_| ClientError::Other {
-                description: "failed to verify signature one".into(),
-            })?;
+        self.verify_signature_and_data(
+            misbehaviour.clone(),
+            misbehaviour.signature_one.clone(),
+            &limits,
+        )
+        .map_err(|e| {
+            e.for_signature(SignatureKind::MisbehaviourOne)
+                .into_misbehaviour_error()
+        })?;
 
         // verify second signature
-        self.verify_signature_and_data(misbehaviour.clone(), misbehaviour.signature_two)
-            .map_err(|_| ClientError::Other {
-                description: "failed to verify signature one".into(),
+        self.verify_signature_and_data(misbehaviour.clone(), misbehaviour.signature_two, &limits)
+            .map_err(|e| {
+                e.for_signature(SignatureKind::MisbehaviourTwo)
+                    .into_misbehaviour_error()
             })
     }
 
     // verifySignatureAndData verifies that the currently registered public key has signed
     // over the provided data and that the data is valid. The data is valid if it can be
-    // unmarshaled into the specified data type.
+    // unmarshaled into the specified data type. The signature is checked
+    // against the host `limits` before it is decoded.
     // ref: https://github.com/cosmos/ibc-go/blob/388283012124fd3cd66c9541000541d9c6767117/modules/light-clients/06-solomachine/misbehaviour_handle.go#L41
     pub fn verify_signature_and_data(
         &self,
         misbehaviour: SmMisbehaviour,
         signature_and_data: SignatureAndData,
-    ) -> Result<(), ClientError> {
+        limits: &VerificationLimits,
+    ) -> Result<(), Error> {
         let sign_bytes = SignBytes {
             sequence: misbehaviour.sequence.revision_height(),
             timestamp: signature_and_data.timestamp.nanoseconds(),
@@ -56,16 +68,9 @@ impl SmClientState {
         };
         let data = sign_bytes.encode_vec();
 
-        let signature_and_data = SignatureAndData::decode_vec(&signature_and_data.signature)
-            .map_err(|_| ClientError::Other {
-                description: "failed to decode SignatureData".into(),
-            })?;
-
         let public_key = self.0.consensus_state.public_key();
 
-        verify_signature(public_key, data, signature_and_data).map_err(|e| ClientError::Other {
-            description: e.to_string(),
-        })
+        verify_signature(public_key, data, signature_and_data.signature, limits)
     }
 
     pub fn verify_misbehaviour_header(
@@ -77,10 +82,12 @@ impl SmClientState {
         Ok(())
     }
 
+    // CheckForMisbehaviour returns true for a misbehaviour message, which
+    // has been verified by the time it is checked.
     pub fn check_for_misbehaviour_misbehavior(
         &self,
         _misbehaviour: &SmMisbehaviour,
     ) -> Result<bool, ClientError> {
-        Ok(false)
+        Ok(true)
     }
 }
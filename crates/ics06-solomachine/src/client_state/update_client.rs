@@ -1,4 +1,6 @@
 use crate::proof::verify_signature;
+use ibc_client_solomachine_types::error::{Error, SignatureKind};
+use ibc_client_solomachine_types::header::SENTINEL_HEADER_PATH;
 use ibc_client_solomachine_types::proof::types::sign_bytes::SignBytes;
 use ibc_client_solomachine_types::Header as SmHeader;
 use ibc_core::client::types::error::ClientError;
@@ -9,13 +11,11 @@ use ibc_proto::Protobuf;
 use super::ClientState;
 use crate::context::ValidationContext as SmValidationContext;
 use ibc_client_solomachine_types::proof::types::header_data::HeaderData;
-use ibc_client_solomachine_types::proof::types::signature_and_data::SignatureAndData;
-use ibc_proto::ibc::core::commitment::v1::MerklePath;
 
 impl ClientState {
     pub fn verify_header<ClientValidationContext>(
         &self,
-        _ctx: &ClientValidationContext,
+        ctx: &ClientValidationContext,
         _client_id: &ClientId,
         header: SmHeader,
     ) -> Result<(), ClientError>
@@ -24,12 +24,22 @@ impl ClientState {
     {
         // assert update timestamp is not less than current consensus state timestamp
         if header.timestamp < self.0.consensus_state.timestamp {
-            return Err(ClientError::Other {
-                description: format!(
-                    "header timestamp is less than to the consensus state timestamp ({} < {})",
-                    header.timestamp, self.0.consensus_state.timestamp,
-                ),
-            });
+            return Err(Error::HeaderTimestampRegression {
+                timestamp: header.timestamp,
+                consensus_timestamp: self.0.consensus_state.timestamp,
+            }
+            .into_header_error());
+        }
+
+        // assert the header is not further ahead of the host time than the time policy allows
+        let time_policy = ctx.time_policy();
+        if time_policy.max_clock_drift.is_some() {
+            let host_timestamp = ctx.host_timestamp().map_err(|e| ClientError::Other {
+                description: e.to_string(),
+            })?;
+            time_policy
+                .check_header(header.timestamp, host_timestamp)
+                .map_err(Error::into_header_error)?;
         }
 
         // assert currently registered public key signed over the new public key with correct sequence
@@ -48,24 +58,26 @@ impl ClientState {
             // SentinelHeaderPath defines a placeholder path value used for headers in solomachine client updates
             // const SentinelHeaderPath = "solomachine:header"
             // ref: https://github.com/cosmos/ibc-go/blob/3765dfc3b89b16c81abcc3e0b1ad5823d7f7eaa0/modules/light-clients/06-solomachine/update.go#L48
-            path: MerklePath {
-                key_path: vec!["solomachine:header".to_string()],
-            },
+            path: SENTINEL_HEADER_PATH.as_bytes().to_vec(),
             data: data_bz,
         };
         let data = sign_bytes.encode_vec();
-        let sig_data =
-            SignatureAndData::decode_vec(&header.signature).map_err(|_| ClientError::Other {
-                description: "failed to decode SignatureData".into(),
-            })?;
 
         let public_key = self.0.consensus_state.public_key();
 
-        verify_signature(public_key, data, sig_data).map_err(|e| ClientError::Other {
-            description: e.to_string(),
-        })
+        verify_signature(
+            public_key,
+            data,
+            header.signature,
+            &ctx.verification_limits(),
+        )
+        .map_err(|e| e.for_signature(SignatureKind::Header).into_header_error())
     }
 
+    /// A header is never misbehaviour: the client only keeps the sequence the
+    /// header is signed at, so there is nothing it could conflict with. As in
+    /// ibc-go, two messages signed at the same sequence are submitted as a
+    /// [`Misbehaviour`](ibc_client_solomachine_types::Misbehaviour).
     pub fn check_for_misbehaviour_update_client<ClientValidationContext>(
         &self,
         _ctx: &ClientValidationContext,
@@ -1,4 +1,6 @@
 use alloc::vec::Vec;
+#[cfg(feature = "candid")]
+use ibc_client_solomachine_types::candid_types::CandidConsensusState;
 use ibc_client_solomachine_types::error::Error;
 use ibc_client_solomachine_types::ConsensusState as ConsensusStateType;
 use ibc_core::client::types::error::ClientError;
@@ -16,6 +18,18 @@ pub const SOLOMACHINE_CONSENSUS_STATE_TYPE_URL: &str =
 /// bypass Rust's orphan rules and implement traits from
 /// `ibc::core::client::context` on the `ConsensusState` type.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
 #[derive(Clone, Debug, PartialEq)]
 pub struct ConsensusState(ConsensusStateType);
 
@@ -31,6 +45,22 @@ impl From<ConsensusStateType> for ConsensusState {
     }
 }
 
+#[cfg(feature = "candid")]
+impl From<&ConsensusState> for CandidConsensusState {
+    fn from(consensus_state: &ConsensusState) -> Self {
+        (&consensus_state.0).into()
+    }
+}
+
+#[cfg(feature = "candid")]
+impl TryFrom<CandidConsensusState> for ConsensusState {
+    type Error = Error;
+
+    fn try_from(consensus_state: CandidConsensusState) -> Result<Self, Self::Error> {
+        Ok(Self(consensus_state.try_into()?))
+    }
+}
+
 impl Protobuf<RawSmConsensusState> for ConsensusState {}
 
 impl TryFrom<RawSmConsensusState> for ConsensusState {
@@ -1,6 +1,7 @@
 use super::consensus_state::ConsensusState as SmConsensusState;
 use alloc::string::ToString;
 
+use ibc_client_solomachine_types::{TimePolicy, VerificationLimits};
 use ibc_core::client::context::ClientExecutionContext;
 use ibc_core::client::types::Height;
 use ibc_core::handler::types::error::ContextError;
@@ -14,6 +15,12 @@ pub trait CommonContext {
     type ConversionError: ToString;
     type AnyConsensusState: TryInto<SmConsensusState, Error = Self::ConversionError>;
 
+    /// Returns the current timestamp of the local chain.
+    fn host_timestamp(&self) -> Result<Timestamp, ContextError>;
+
+    /// Returns the current height of the local chain.
+    fn host_height(&self) -> Result<Height, ContextError>;
+
     /// Retrieve the consensus state for the given client ID at the specified
     /// height.
     ///
@@ -26,8 +33,21 @@ pub trait CommonContext {
 
 /// Client's context required during validation
 pub trait ValidationContext: CommonContext {
-    /// Returns the current timestamp of the local chain.
-    fn host_timestamp(&self) -> Result<Timestamp, ContextError>;
+    /// Returns the size limits headers, misbehaviours and proofs are checked
+    /// against before they are decoded and verified.
+    ///
+    /// The limits are host configuration and are not stored with the client
+    /// state. Hosts override this method to apply their own limits.
+    fn verification_limits(&self) -> VerificationLimits {
+        VerificationLimits::default()
+    }
+
+    /// Returns the bounds header and proof timestamps must keep from
+    /// [`CommonContext::host_timestamp`]. Disabled by default; hosts
+    /// override this method to enable it.
+    fn time_policy(&self) -> TimePolicy {
+        TimePolicy::default()
+    }
 
     /// Search for the lowest consensus state higher than `height`.
     fn next_consensus_state(
@@ -21,6 +21,8 @@ extern crate std;
 pub mod client_state;
 pub mod consensus_state;
 pub mod context;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod mock;
 pub mod proof;
 
 pub use context::*;
@@ -0,0 +1,349 @@
+//! In-memory host for testing solo machine clients without a chain.
+//!
+//! [`MockContext`] stores client states, consensus states and update
+//! times/heights in memory, and lets the test drive the host timestamp.
+
+use crate::client_state::ClientState;
+use crate::consensus_state::ConsensusState as SmConsensusState;
+use crate::context::{CommonContext, ValidationContext};
+use crate::types::{TimePolicy, VerificationLimits};
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::ops::Bound::{Excluded, Unbounded};
+use core::time::Duration;
+use ibc_core::client::context::consensus_state::ConsensusState;
+use ibc_core::client::context::{ClientExecutionContext, ClientValidationContext};
+use ibc_core::client::types::error::ClientError;
+use ibc_core::client::types::Height;
+use ibc_core::commitment_types::commitment::CommitmentRoot;
+use ibc_core::handler::types::error::ContextError;
+use ibc_core::host::types::identifiers::ClientId;
+use ibc_core::host::types::path::{ClientConsensusStatePath, ClientStatePath};
+use ibc_core::primitives::prelude::*;
+use ibc_core::primitives::Timestamp;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::Protobuf;
+
+/// The consensus states a [`MockContext`] stores.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MockConsensusState {
+    Solomachine(SmConsensusState),
+}
+
+impl From<SmConsensusState> for MockConsensusState {
+    fn from(consensus_state: SmConsensusState) -> Self {
+        Self::Solomachine(consensus_state)
+    }
+}
+
+impl TryFrom<MockConsensusState> for SmConsensusState {
+    type Error = ClientError;
+
+    fn try_from(consensus_state: MockConsensusState) -> Result<Self, Self::Error> {
+        match consensus_state {
+            MockConsensusState::Solomachine(consensus_state) => Ok(consensus_state),
+        }
+    }
+}
+
+impl TryFrom<Any> for MockConsensusState {
+    type Error = ClientError;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        SmConsensusState::try_from(raw).map(Self::Solomachine)
+    }
+}
+
+impl From<MockConsensusState> for Any {
+    fn from(consensus_state: MockConsensusState) -> Self {
+        match consensus_state {
+            MockConsensusState::Solomachine(consensus_state) => consensus_state.into(),
+        }
+    }
+}
+
+impl ConsensusState for MockConsensusState {
+    fn root(&self) -> &CommitmentRoot {
+        match self {
+            Self::Solomachine(consensus_state) => consensus_state.root(),
+        }
+    }
+
+    fn timestamp(&self) -> Timestamp {
+        match self {
+            Self::Solomachine(consensus_state) => consensus_state.timestamp(),
+        }
+    }
+
+    fn encode_vec(self) -> Vec<u8> {
+        match self {
+            Self::Solomachine(consensus_state) => {
+                <SmConsensusState as Protobuf<Any>>::encode_vec(consensus_state)
+            }
+        }
+    }
+}
+
+/// An in-memory host chain running solo machine clients.
+#[derive(Clone, Debug)]
+pub struct MockContext {
+    host_timestamp: Timestamp,
+    host_height: Height,
+    verification_limits: VerificationLimits,
+    time_policy: TimePolicy,
+    client_states: BTreeMap<ClientId, ClientState>,
+    consensus_states: BTreeMap<ClientId, BTreeMap<Height, MockConsensusState>>,
+    update_times: BTreeMap<(ClientId, Height), Timestamp>,
+    update_heights: BTreeMap<(ClientId, Height), Height>,
+}
+
+impl MockContext {
+    /// Create an empty host at height 1 and the given timestamp.
+    pub fn new(host_timestamp: Timestamp) -> Self {
+        Self {
+            host_timestamp,
+            host_height: Height::min(0),
+            verification_limits: VerificationLimits::default(),
+            time_policy: TimePolicy::default(),
+            client_states: BTreeMap::new(),
+            consensus_states: BTreeMap::new(),
+            update_times: BTreeMap::new(),
+            update_heights: BTreeMap::new(),
+        }
+    }
+
+    pub fn set_host_timestamp(&mut self, host_timestamp: Timestamp) {
+        self.host_timestamp = host_timestamp;
+    }
+
+    /// Set the size limits the host checks client messages and proofs
+    /// against.
+    pub fn set_verification_limits(&mut self, verification_limits: VerificationLimits) {
+        self.verification_limits = verification_limits;
+    }
+
+    /// Set the bounds header and proof timestamps must keep from the host
+    /// timestamp.
+    pub fn set_time_policy(&mut self, time_policy: TimePolicy) {
+        self.time_policy = time_policy;
+    }
+
+    /// Produce a new host block `duration` after the current one.
+    pub fn advance_host_time(&mut self, duration: Duration) {
+        self.host_timestamp = (self.host_timestamp + duration).unwrap_or(self.host_timestamp);
+        self.host_height = self.host_height.increment();
+    }
+
+    /// The client state stored for `client_id`, if any.
+    pub fn client_state(&self, client_id: &ClientId) -> Option<&ClientState> {
+        self.client_states.get(client_id)
+    }
+
+    /// The consensus state stored for `client_id` at `height`, if any.
+    pub fn stored_consensus_state(
+        &self,
+        client_id: &ClientId,
+        height: &Height,
+    ) -> Option<SmConsensusState> {
+        match self.consensus_states.get(client_id)?.get(height)? {
+            MockConsensusState::Solomachine(consensus_state) => Some(consensus_state.clone()),
+        }
+    }
+}
+
+impl CommonContext for MockContext {
+    type ConversionError = ClientError;
+    type AnyConsensusState = MockConsensusState;
+
+    fn host_timestamp(&self) -> Result<Timestamp, ContextError> {
+        Ok(self.host_timestamp)
+    }
+
+    fn host_height(&self) -> Result<Height, ContextError> {
+        Ok(self.host_height)
+    }
+
+    fn consensus_state(
+        &self,
+        client_cons_state_path: &ClientConsensusStatePath,
+    ) -> Result<Self::AnyConsensusState, ContextError> {
+        let height = Height::new(
+            client_cons_state_path.revision_number,
+            client_cons_state_path.revision_height,
+        )?;
+        self.consensus_states
+            .get(&client_cons_state_path.client_id)
+            .and_then(|consensus_states| consensus_states.get(&height))
+            .cloned()
+            .ok_or_else(|| {
+                ClientError::ConsensusStateNotFound {
+                    client_id: client_cons_state_path.client_id.clone(),
+                    height,
+                }
+                .into()
+            })
+    }
+}
+
+impl ValidationContext for MockContext {
+    fn verification_limits(&self) -> VerificationLimits {
+        self.verification_limits
+    }
+
+    fn time_policy(&self) -> TimePolicy {
+        self.time_policy
+    }
+
+    fn next_consensus_state(
+        &self,
+        client_id: &ClientId,
+        height: &Height,
+    ) -> Result<Option<Self::AnyConsensusState>, ContextError> {
+        Ok(self
+            .consensus_states
+            .get(client_id)
+            .and_then(|consensus_states| {
+                consensus_states
+                    .range((Excluded(height), Unbounded))
+                    .next()
+                    .map(|(_, consensus_state)| consensus_state.clone())
+            }))
+    }
+
+    fn prev_consensus_state(
+        &self,
+        client_id: &ClientId,
+        height: &Height,
+    ) -> Result<Option<Self::AnyConsensusState>, ContextError> {
+        Ok(self
+            .consensus_states
+            .get(client_id)
+            .and_then(|consensus_states| {
+                consensus_states
+                    .range(..height)
+                    .next_back()
+                    .map(|(_, consensus_state)| consensus_state.clone())
+            }))
+    }
+}
+
+impl ClientValidationContext for MockContext {
+    fn client_update_time(
+        &self,
+        client_id: &ClientId,
+        height: &Height,
+    ) -> Result<Timestamp, ContextError> {
+        self.update_times
+            .get(&(client_id.clone(), *height))
+            .copied()
+            .ok_or_else(|| {
+                ClientError::Other {
+                    description: format!("no update time for client {} at {}", client_id, height),
+                }
+                .into()
+            })
+    }
+
+    fn client_update_height(
+        &self,
+        client_id: &ClientId,
+        height: &Height,
+    ) -> Result<Height, ContextError> {
+        self.update_heights
+            .get(&(client_id.clone(), *height))
+            .copied()
+            .ok_or_else(|| {
+                ClientError::Other {
+                    description: format!("no update height for client {} at {}", client_id, height),
+                }
+                .into()
+            })
+    }
+}
+
+impl ClientExecutionContext for MockContext {
+    type V = Self;
+    type AnyClientState = ClientState;
+    type AnyConsensusState = MockConsensusState;
+
+    fn store_client_state(
+        &mut self,
+        client_state_path: ClientStatePath,
+        client_state: Self::AnyClientState,
+    ) -> Result<(), ContextError> {
+        self.client_states.insert(client_state_path.0, client_state);
+        Ok(())
+    }
+
+    fn store_consensus_state(
+        &mut self,
+        consensus_state_path: ClientConsensusStatePath,
+        consensus_state: Self::AnyConsensusState,
+    ) -> Result<(), ContextError> {
+        let height = Height::new(
+            consensus_state_path.revision_number,
+            consensus_state_path.revision_height,
+        )?;
+        self.consensus_states
+            .entry(consensus_state_path.client_id)
+            .or_default()
+            .insert(height, consensus_state);
+        Ok(())
+    }
+
+    fn delete_consensus_state(
+        &mut self,
+        consensus_state_path: ClientConsensusStatePath,
+    ) -> Result<(), ContextError> {
+        let height = Height::new(
+            consensus_state_path.revision_number,
+            consensus_state_path.revision_height,
+        )?;
+        if let Some(consensus_states) = self
+            .consensus_states
+            .get_mut(&consensus_state_path.client_id)
+        {
+            consensus_states.remove(&height);
+        }
+        Ok(())
+    }
+
+    fn store_update_time(
+        &mut self,
+        client_id: ClientId,
+        height: Height,
+        host_timestamp: Timestamp,
+    ) -> Result<(), ContextError> {
+        self.update_times
+            .insert((client_id, height), host_timestamp);
+        Ok(())
+    }
+
+    fn store_update_height(
+        &mut self,
+        client_id: ClientId,
+        height: Height,
+        host_height: Height,
+    ) -> Result<(), ContextError> {
+        self.update_heights.insert((client_id, height), host_height);
+        Ok(())
+    }
+
+    fn delete_update_time(
+        &mut self,
+        client_id: ClientId,
+        height: Height,
+    ) -> Result<(), ContextError> {
+        self.update_times.remove(&(client_id, height));
+        Ok(())
+    }
+
+    fn delete_update_height(
+        &mut self,
+        client_id: ClientId,
+        height: Height,
+    ) -> Result<(), ContextError> {
+        self.update_heights.remove(&(client_id, height));
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+//! Candid encodings of the client and consensus states.
+
+mod common;
+
+use common::*;
+use ibc_client_solomachine::client_state::ClientState;
+use ibc_client_solomachine::consensus_state::ConsensusState as SmConsensusState;
+use ibc_client_solomachine::types::candid_types::{CandidClientState, CandidConsensusState};
+use ibc_client_solomachine::types::ClientState as ClientStateType;
+use ibc_core::client::types::Height;
+
+#[test]
+fn client_and_consensus_states_round_trip_through_candid() {
+    let key = ed25519_key(1);
+    let consensus_state = consensus_state(&key, "one", 100);
+    let client_state = ClientState::from(
+        ClientStateType::new(Height::new(0, 1).unwrap(), false, consensus_state.clone())
+            .with_inactivity_period(core::time::Duration::from_secs(60)),
+    );
+
+    let encoded = candid::encode_one(CandidClientState::from(&client_state)).unwrap();
+    let decoded = candid::decode_one::<CandidClientState>(&encoded).unwrap();
+    assert_eq!(ClientState::try_from(decoded).unwrap(), client_state);
+
+    let consensus_state = SmConsensusState::from(consensus_state);
+    let encoded = candid::encode_one(CandidConsensusState::from(&consensus_state)).unwrap();
+    let decoded = candid::decode_one::<CandidConsensusState>(&encoded).unwrap();
+    assert_eq!(
+        SmConsensusState::try_from(decoded).unwrap(),
+        consensus_state
+    );
+}
@@ -0,0 +1,154 @@
+//! Fixtures shared by the solo machine client integration tests.
+#![allow(dead_code)]
+
+use ibc_client_solomachine::client_state::ClientState;
+use ibc_client_solomachine::types::cosmos::crypto::SigningKey;
+use ibc_client_solomachine::types::header::SENTINEL_HEADER_PATH;
+use ibc_client_solomachine::types::proof::types::header_data::HeaderData;
+use ibc_client_solomachine::types::proof::types::sign_bytes::SignBytes;
+use ibc_client_solomachine::types::proof::types::signature_and_data::SignatureAndData;
+use ibc_client_solomachine::types::proof::types::timestamped_signature_data::TimestampedSignatureData;
+use ibc_client_solomachine::types::{
+    client_type, ClientState as ClientStateType, ConsensusState as ConsensusStateType, Header,
+};
+use ibc_core::client::types::Height;
+use ibc_core::commitment_types::commitment::{
+    CommitmentPrefix, CommitmentProofBytes, CommitmentRoot,
+};
+use ibc_core::host::types::identifiers::ClientId;
+use ibc_core::host::types::path::{ConnectionPath, Path};
+use ibc_core::primitives::Timestamp;
+use ibc_proto::Protobuf;
+
+/// The path every fixture proof is signed over, `connection-0` under the
+/// `ibc` prefix.
+pub const CONNECTION_PATH: &[u8] = b"/ibc/connections/connection-0";
+
+pub fn timestamp(nanoseconds: u64) -> Timestamp {
+    Timestamp::from_nanoseconds(nanoseconds).unwrap()
+}
+
+/// The proof height of a proof signed at `sequence`.
+pub fn height(sequence: u64) -> Height {
+    Height::new(0, sequence).unwrap()
+}
+
+pub fn client_id() -> ClientId {
+    ClientId::new(client_type(), 0).unwrap()
+}
+
+pub fn ed25519_key(seed: u8) -> SigningKey {
+    SigningKey::from_ed25519_bytes(&[seed; 32]).unwrap()
+}
+
+pub fn secp256k1_key(seed: u8) -> SigningKey {
+    SigningKey::from_secp256k1_bytes(&[seed; 32]).unwrap()
+}
+
+pub fn consensus_state(
+    key: &SigningKey,
+    diversifier: &str,
+    nanoseconds: u64,
+) -> ConsensusStateType {
+    ConsensusStateType::new(
+        key.public_key().unwrap(),
+        diversifier.into(),
+        timestamp(nanoseconds),
+    )
+}
+
+/// An active client at `sequence` holding `consensus_state`.
+pub fn client_state(sequence: u64, consensus_state: ConsensusStateType) -> ClientState {
+    ClientState::from(ClientStateType::new(
+        Height::new(0, sequence).unwrap(),
+        false,
+        consensus_state,
+    ))
+}
+
+/// A header rotating to `new_key`/`new_diversifier`, signed by `key` at
+/// `sequence` under `diversifier`.
+pub fn header(
+    key: &SigningKey,
+    sequence: u64,
+    diversifier: &str,
+    nanoseconds: u64,
+    new_key: &SigningKey,
+    new_diversifier: &str,
+) -> Header {
+    let header_data = HeaderData {
+        new_pub_key: new_key.public_key().unwrap(),
+        new_diversifier: new_diversifier.into(),
+    };
+    Header {
+        timestamp: timestamp(nanoseconds),
+        signature: key.sign_signature_data(SignBytes {
+            sequence,
+            timestamp: nanoseconds,
+            diversifier: diversifier.into(),
+            path: SENTINEL_HEADER_PATH.as_bytes().to_vec(),
+            data: header_data.encode_vec(),
+        }),
+        new_public_key: new_key.public_key().unwrap(),
+        new_diversifier: new_diversifier.into(),
+    }
+}
+
+/// `data` signed by `key` over [`CONNECTION_PATH`], as carried in a
+/// misbehaviour.
+pub fn signature_and_data(
+    key: &SigningKey,
+    sequence: u64,
+    diversifier: &str,
+    nanoseconds: u64,
+    data: &[u8],
+) -> SignatureAndData {
+    SignatureAndData {
+        signature: key.sign_signature_data(SignBytes {
+            sequence,
+            timestamp: nanoseconds,
+            diversifier: diversifier.into(),
+            path: CONNECTION_PATH.to_vec(),
+            data: data.to_vec(),
+        }),
+        path: CONNECTION_PATH.to_vec(),
+        data: data.to_vec(),
+        timestamp: timestamp(nanoseconds),
+    }
+}
+
+/// A proof of `data` signed by `key` over [`CONNECTION_PATH`]; an empty
+/// `data` proves non-membership.
+pub fn proof(
+    key: &SigningKey,
+    sequence: u64,
+    diversifier: &str,
+    nanoseconds: u64,
+    data: &[u8],
+) -> CommitmentProofBytes {
+    TimestampedSignatureData {
+        signature_data: key.sign_signature_data(SignBytes {
+            sequence,
+            timestamp: nanoseconds,
+            diversifier: diversifier.into(),
+            path: CONNECTION_PATH.to_vec(),
+            data: data.to_vec(),
+        }),
+        timestamp: timestamp(nanoseconds),
+    }
+    .encode_vec()
+    .try_into()
+    .unwrap()
+}
+
+pub fn prefix() -> CommitmentPrefix {
+    CommitmentPrefix::try_from(b"ibc".to_vec()).unwrap()
+}
+
+pub fn root() -> CommitmentRoot {
+    CommitmentRoot::from(vec![])
+}
+
+pub fn connection_path() -> Path {
+    Path::Connection(ConnectionPath::new(&Default::default()))
+}
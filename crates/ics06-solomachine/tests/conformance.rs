@@ -0,0 +1,191 @@
+//! Conformance with the ibc-go solo machine encodings, see
+//! `tests/fixtures/README.md`.
+//!
+//! Every vector is decoded, re-encoded byte for byte, and its signature is
+//! verified through the light client.
+
+use ibc_client_solomachine::client_state::ClientState;
+use ibc_client_solomachine::mock::MockContext;
+use ibc_client_solomachine::types::header::SENTINEL_HEADER_PATH;
+use ibc_client_solomachine::types::proof::types::header_data::HeaderData;
+use ibc_client_solomachine::types::proof::types::sign_bytes::SignBytes;
+use ibc_client_solomachine::types::proof::types::timestamped_signature_data::TimestampedSignatureData;
+use ibc_client_solomachine::types::{
+    client_type, ConsensusState as ConsensusStateType, Header, Misbehaviour,
+};
+use ibc_core::client::context::client_state::ClientStateCommon;
+use ibc_core::commitment_types::commitment::{
+    CommitmentPrefix, CommitmentProofBytes, CommitmentRoot,
+};
+use ibc_core::host::types::identifiers::ClientId;
+use ibc_core::host::types::path::Path;
+use ibc_core::primitives::Timestamp;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::Protobuf;
+use prost::Message;
+use serde_json_std::Value;
+
+fn load(name: &str) -> Value {
+    let path = format!(
+        "{}/tests/fixtures/conformance/{}.json",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    );
+    serde_json_std::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+fn hex(value: &Value) -> Vec<u8> {
+    subtle_encoding::hex::decode(value.as_str().unwrap()).unwrap()
+}
+
+fn any(value: &Value) -> Any {
+    Any::decode(hex(value).as_slice()).unwrap()
+}
+
+/// Decode `SignBytes`, check they re-encode to the same bytes and return them.
+fn sign_bytes(value: &Value) -> SignBytes {
+    let bytes = hex(value);
+    let sign_bytes = SignBytes::decode_vec(&bytes).unwrap();
+    assert_eq!(sign_bytes.clone().encode_vec(), bytes);
+    sign_bytes
+}
+
+fn prefix(value: &Value) -> CommitmentPrefix {
+    CommitmentPrefix::try_from(value.as_str().unwrap().as_bytes().to_vec()).unwrap()
+}
+
+fn proof(value: &Value) -> CommitmentProofBytes {
+    let bytes = hex(value);
+    let proof = TimestampedSignatureData::decode_vec(&bytes).unwrap();
+    assert_eq!(proof.encode_vec(), bytes);
+    bytes.try_into().unwrap()
+}
+
+fn path(value: &Value) -> Path {
+    value.as_str().unwrap().parse().unwrap()
+}
+
+fn check(name: &str) {
+    let vectors = load(name);
+    let ctx = MockContext::new(Timestamp::from_nanoseconds(100).unwrap());
+    let client_id = ClientId::new(client_type(), 0).unwrap();
+    let root = CommitmentRoot::from(vec![]);
+
+    // client and consensus states
+    let client_state = ClientState::try_from(any(&vectors["client_state"])).unwrap();
+    assert_eq!(
+        Any::from(client_state.clone()).encode_to_vec(),
+        hex(&vectors["client_state"])
+    );
+    let consensus_state = ConsensusStateType::try_from(any(&vectors["consensus_state"])).unwrap();
+    assert_eq!(
+        Any::from(consensus_state.clone()).encode_to_vec(),
+        hex(&vectors["consensus_state"])
+    );
+    assert!(client_state.inner().consensus_state == consensus_state);
+
+    // header
+    let vector = &vectors["header"];
+    let header = Header::try_from(any(&vector["header"])).unwrap();
+    assert_eq!(
+        Any::from(header.clone()).encode_to_vec(),
+        hex(&vector["header"])
+    );
+    let header_data = HeaderData::decode_vec(&hex(&vector["header_data"])).unwrap();
+    assert_eq!(header_data.new_pub_key, header.new_public_key);
+    assert_eq!(header_data.new_diversifier, header.new_diversifier);
+    assert_eq!(header_data.encode_vec(), hex(&vector["header_data"]));
+    let header_sign_bytes = sign_bytes(&vector["sign_bytes"]);
+    assert_eq!(header_sign_bytes.path, SENTINEL_HEADER_PATH.as_bytes());
+    assert_eq!(header_sign_bytes.data, hex(&vector["header_data"]));
+    client_state
+        .verify_header(&ctx, &client_id, header.clone())
+        .unwrap();
+    assert!(client_state
+        .verify_header(
+            &ctx,
+            &client_id,
+            Header {
+                new_diversifier: "tampered".into(),
+                ..header
+            }
+        )
+        .is_err());
+
+    // membership
+    let vector = &vectors["membership"];
+    let membership_sign_bytes = sign_bytes(&vector["sign_bytes"]);
+    assert_eq!(
+        membership_sign_bytes.path,
+        format!(
+            "/{}/{}",
+            vector["prefix"].as_str().unwrap(),
+            path(&vector["path"])
+        )
+        .as_bytes()
+    );
+    assert_eq!(membership_sign_bytes.data, hex(&vector["value"]));
+    client_state
+        .verify_membership(
+            &prefix(&vector["prefix"]),
+            &proof(&vector["proof"]),
+            &root,
+            path(&vector["path"]),
+            hex(&vector["value"]),
+        )
+        .unwrap();
+    assert!(client_state
+        .verify_membership(
+            &prefix(&vector["prefix"]),
+            &proof(&vector["proof"]),
+            &root,
+            path(&vector["path"]),
+            b"tampered".to_vec(),
+        )
+        .is_err());
+
+    // non-membership
+    let vector = &vectors["non_membership"];
+    let non_membership_sign_bytes = sign_bytes(&vector["sign_bytes"]);
+    assert!(non_membership_sign_bytes.data.is_empty());
+    client_state
+        .verify_non_membership(
+            &prefix(&vector["prefix"]),
+            &proof(&vector["proof"]),
+            &root,
+            path(&vector["path"]),
+        )
+        .unwrap();
+
+    // misbehaviour
+    let vector = &vectors["misbehaviour"];
+    let misbehaviour = Misbehaviour::try_from(any(&vector["misbehaviour"])).unwrap();
+    assert_eq!(
+        Any::from(misbehaviour.clone()).encode_to_vec(),
+        hex(&vector["misbehaviour"])
+    );
+    misbehaviour.validate_basic().unwrap();
+    for (signature, sign_bytes_vector) in [
+        (&misbehaviour.signature_one, &vector["sign_bytes_one"]),
+        (&misbehaviour.signature_two, &vector["sign_bytes_two"]),
+    ] {
+        let expected = sign_bytes(sign_bytes_vector);
+        assert_eq!(expected.sequence, misbehaviour.sequence.revision_height());
+        assert_eq!(expected.timestamp, signature.timestamp.nanoseconds());
+        assert_eq!(expected.path, signature.path);
+        assert_eq!(expected.data, signature.data);
+    }
+    client_state
+        .verify_misbehaviour(&ctx, &client_id, misbehaviour)
+        .unwrap();
+}
+
+#[test]
+fn ed25519_vectors() {
+    check("ed25519");
+}
+
+#[test]
+fn secp256k1_vectors() {
+    check("secp256k1");
+}
@@ -0,0 +1,78 @@
+//! Verification failures surface as structured ibc-core client errors.
+
+mod common;
+
+use common::*;
+use ibc_client_solomachine::client_state::ClientState;
+use ibc_client_solomachine::mock::MockContext;
+use ibc_client_solomachine::types::{Header, Misbehaviour};
+use ibc_core::client::context::client_state::{ClientStateCommon, ClientStateValidation};
+use ibc_core::client::types::error::ClientError;
+use ibc_core::client::types::{Height, UpdateKind};
+use ibc_proto::google::protobuf::Any;
+
+#[test]
+fn verification_errors_are_structured() {
+    let key = ed25519_key(1);
+    let client_id = client_id();
+    let ctx = MockContext::new(timestamp(100));
+    let client_state = client_state(1, consensus_state(&key, "one", 10));
+
+    // the proof height must carry the client sequence
+    assert!(matches!(
+        client_state.validate_proof_height(Height::new(0, 2).unwrap()),
+        Err(ClientError::InvalidProofHeight { .. })
+    ));
+    client_state
+        .validate_proof_height(Height::new(0, 1).unwrap())
+        .unwrap();
+
+    // a header older than the consensus state
+    let header = Header {
+        timestamp: timestamp(5),
+        signature: vec![1; 64],
+        new_public_key: key.public_key().unwrap(),
+        new_diversifier: "two".into(),
+    };
+    match client_state.verify_client_message(
+        &ctx,
+        &client_id,
+        Any::from(header),
+        &UpdateKind::UpdateClient,
+    ) {
+        Err(ClientError::HeaderVerificationFailure { reason }) => {
+            assert!(reason.contains("less than the consensus state timestamp"))
+        }
+        other => panic!("unexpected result {:?}", other),
+    }
+
+    // the second misbehaviour signature is made by another key
+    let misbehaviour = Misbehaviour {
+        sequence: Height::new(0, 1).unwrap(),
+        signature_one: signature_and_data(&key, 1, "one", 30, b"one"),
+        signature_two: signature_and_data(&ed25519_key(2), 1, "one", 30, b"two"),
+    };
+    match client_state.verify_client_message(
+        &ctx,
+        &client_id,
+        Any::from(misbehaviour),
+        &UpdateKind::SubmitMisbehaviour,
+    ) {
+        Err(ClientError::MisbehaviourHandlingFailure { reason }) => {
+            assert!(reason.contains("invalid misbehaviour second signature"))
+        }
+        other => panic!("unexpected result {:?}", other),
+    }
+
+    // a frozen client rejects proofs
+    let frozen = ClientState::from(client_state.inner().clone().with_frozen());
+    assert!(matches!(
+        frozen.verify_non_membership(
+            &prefix(),
+            &vec![1u8].try_into().unwrap(),
+            &root(),
+            connection_path(),
+        ),
+        Err(ClientError::ClientFrozen { .. })
+    ));
+}
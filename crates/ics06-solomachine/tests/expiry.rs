@@ -0,0 +1,53 @@
+//! Expiry of clients that stay inactive longer than their inactivity period.
+
+mod common;
+
+use common::*;
+use ibc_client_solomachine::client_state::ClientState;
+use ibc_client_solomachine::mock::MockContext;
+use ibc_client_solomachine::types::ClientState as ClientStateType;
+use ibc_core::client::context::client_state::{ClientStateExecution, ClientStateValidation};
+use ibc_core::client::types::{Height, Status};
+use ibc_proto::google::protobuf::Any;
+
+#[test]
+fn inactive_client_expires() {
+    let key = ed25519_key(1);
+    let client_id = client_id();
+    let mut ctx = MockContext::new(timestamp(100));
+    let consensus_state = consensus_state(&key, "one", 100);
+    let client_state = ClientState::from(
+        ClientStateType::new(Height::new(0, 1).unwrap(), false, consensus_state.clone())
+            .with_inactivity_period(core::time::Duration::from_nanos(50)),
+    );
+    client_state
+        .initialise(&mut ctx, &client_id, Any::from(consensus_state))
+        .unwrap();
+
+    // the stored client state keeps the period
+    let client_state = ctx.client_state(&client_id).unwrap().clone();
+    assert_eq!(
+        client_state.inner().inactivity_period,
+        Some(core::time::Duration::from_nanos(50))
+    );
+    assert_eq!(
+        client_state.status(&ctx, &client_id).unwrap(),
+        Status::Active
+    );
+
+    ctx.advance_host_time(core::time::Duration::from_nanos(50));
+    assert_eq!(
+        client_state.status(&ctx, &client_id).unwrap(),
+        Status::Active
+    );
+
+    ctx.advance_host_time(core::time::Duration::from_nanos(1));
+    assert_eq!(
+        client_state.status(&ctx, &client_id).unwrap(),
+        Status::Expired
+    );
+
+    // frozen takes precedence over expired
+    let frozen = ClientState::from(client_state.inner().clone().with_frozen());
+    assert_eq!(frozen.status(&ctx, &client_id).unwrap(), Status::Frozen);
+}
@@ -0,0 +1,11 @@
+module github.com/octopus-network/ics06-solomachine/conformance
+
+go 1.20
+
+require (
+	github.com/cosmos/cosmos-sdk v0.47.5
+	github.com/cosmos/ibc-go/v7 v7.3.0
+)
+
+// as in ibc-go v7.3.0
+replace github.com/syndtr/goleveldb => github.com/syndtr/goleveldb v1.0.1-0.20210819022825-2ae1ddf74ef7
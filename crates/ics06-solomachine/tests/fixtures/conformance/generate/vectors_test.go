@@ -0,0 +1,244 @@
+// Package conformance writes the solo machine conformance vectors of
+// `tests/conformance.rs` with ibc-go itself.
+//
+// Run from this directory:
+//
+//	go mod tidy && go test -run TestWriteVectors .
+//
+// Keys are derived from fixed seeds and both ed25519 and secp256k1 signing are
+// deterministic, so re-running it leaves the files unchanged.
+package conformance
+
+import (
+	stded25519 "crypto/ed25519"
+	"encoding/hex"
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/cosmos/cosmos-sdk/codec"
+	codectypes "github.com/cosmos/cosmos-sdk/codec/types"
+	cryptocodec "github.com/cosmos/cosmos-sdk/crypto/codec"
+	"github.com/cosmos/cosmos-sdk/crypto/keys/ed25519"
+	"github.com/cosmos/cosmos-sdk/crypto/keys/secp256k1"
+	cryptotypes "github.com/cosmos/cosmos-sdk/crypto/types"
+	"github.com/stretchr/testify/require"
+
+	commitmenttypes "github.com/cosmos/ibc-go/v7/modules/core/23-commitment/types"
+	solomachine "github.com/cosmos/ibc-go/v7/modules/light-clients/06-solomachine"
+	ibctesting "github.com/cosmos/ibc-go/v7/testing"
+)
+
+const (
+	prefix            = "ibc"
+	membershipPath    = "connections/connection-0"
+	nonMembershipPath = "receipts/ports/transfer/channels/channel-0/sequences/1"
+)
+
+type headerVector struct {
+	HeaderData string `json:"header_data"`
+	SignBytes  string `json:"sign_bytes"`
+	Header     string `json:"header"`
+}
+
+type membershipVector struct {
+	Prefix    string `json:"prefix"`
+	Path      string `json:"path"`
+	Value     string `json:"value"`
+	SignBytes string `json:"sign_bytes"`
+	Proof     string `json:"proof"`
+}
+
+type nonMembershipVector struct {
+	Prefix    string `json:"prefix"`
+	Path      string `json:"path"`
+	SignBytes string `json:"sign_bytes"`
+	Proof     string `json:"proof"`
+}
+
+type misbehaviourVector struct {
+	SignBytesOne string `json:"sign_bytes_one"`
+	SignBytesTwo string `json:"sign_bytes_two"`
+	Misbehaviour string `json:"misbehaviour"`
+}
+
+type vectors struct {
+	Description    string              `json:"description"`
+	ClientState    string              `json:"client_state"`
+	ConsensusState string              `json:"consensus_state"`
+	Header         headerVector        `json:"header"`
+	Membership     membershipVector    `json:"membership"`
+	NonMembership  nonMembershipVector `json:"non_membership"`
+	Misbehaviour   misbehaviourVector  `json:"misbehaviour"`
+}
+
+func seed(b byte) []byte {
+	bz := make([]byte, 32)
+	for i := range bz {
+		bz[i] = b
+	}
+	return bz
+}
+
+func ed25519Key(b byte) cryptotypes.PrivKey {
+	return &ed25519.PrivKey{Key: stded25519.NewKeyFromSeed(seed(b))}
+}
+
+func secp256k1Key(b byte) cryptotypes.PrivKey {
+	return &secp256k1.PrivKey{Key: seed(b)}
+}
+
+func makeCodec() codec.Codec {
+	registry := codectypes.NewInterfaceRegistry()
+	cryptocodec.RegisterInterfaces(registry)
+	solomachine.RegisterInterfaces(registry)
+	return codec.NewProtoCodec(registry)
+}
+
+// setKey makes `key` the single signing key of `solo`.
+func setKey(solo *ibctesting.Solomachine, key cryptotypes.PrivKey) {
+	solo.PrivateKeys = []cryptotypes.PrivKey{key}
+	solo.PublicKeys = []cryptotypes.PubKey{key.PubKey()}
+	solo.PublicKey = key.PubKey()
+}
+
+func anyHex(t *testing.T, cdc codec.Codec, msg codec.ProtoMarshaler) string {
+	packed, err := codectypes.NewAnyWithValue(msg)
+	require.NoError(t, err)
+	return hex.EncodeToString(cdc.MustMarshal(packed))
+}
+
+func merklePath(t *testing.T, path string) []byte {
+	merklePath, err := commitmenttypes.ApplyPrefix(
+		commitmenttypes.NewMerklePrefix([]byte(prefix)),
+		commitmenttypes.NewMerklePath(path),
+	)
+	require.NoError(t, err)
+	return []byte(merklePath.String())
+}
+
+// proof signs `signBytes` the way `Solomachine.GenerateProof` does, at the
+// timestamp of the sign bytes.
+func proof(solo *ibctesting.Solomachine, cdc codec.Codec, signBytes *solomachine.SignBytes) (string, string) {
+	bz := cdc.MustMarshal(signBytes)
+	signatureDoc := cdc.MustMarshal(&solomachine.TimestampedSignatureData{
+		SignatureData: solo.GenerateSignature(bz),
+		Timestamp:     signBytes.Timestamp,
+	})
+	return hex.EncodeToString(bz), hex.EncodeToString(signatureDoc)
+}
+
+func generate(t *testing.T, name string, key, newKey cryptotypes.PrivKey) vectors {
+	cdc := makeCodec()
+	// sequence 1, timestamp 10 and diversifier "testing"
+	solo := ibctesting.NewSolomachine(t, cdc, "solomachine", "testing", 1)
+	setKey(solo, key)
+
+	// header rotating to `newKey`, built as `Solomachine.CreateHeader` does
+	// but with a fixed new key
+	newPublicKey, err := codectypes.NewAnyWithValue(newKey.PubKey())
+	require.NoError(t, err)
+	headerData := cdc.MustMarshal(&solomachine.HeaderData{
+		NewPubKey:      newPublicKey,
+		NewDiversifier: "testing-2",
+	})
+	headerSignBytes := cdc.MustMarshal(&solomachine.SignBytes{
+		Sequence:    solo.Sequence,
+		Timestamp:   20,
+		Diversifier: solo.Diversifier,
+		Path:        []byte(solomachine.SentinelHeaderPath),
+		Data:        headerData,
+	})
+	header := &solomachine.Header{
+		Timestamp:      20,
+		Signature:      solo.GenerateSignature(headerSignBytes),
+		NewPublicKey:   newPublicKey,
+		NewDiversifier: "testing-2",
+	}
+
+	// membership of a value at `connections/connection-0`
+	membershipValue := []byte("connection end")
+	membershipSignBytes, membershipProof := proof(solo, cdc, &solomachine.SignBytes{
+		Sequence:    solo.Sequence,
+		Timestamp:   30,
+		Diversifier: solo.Diversifier,
+		Path:        merklePath(t, membershipPath),
+		Data:        membershipValue,
+	})
+
+	// non-membership of a packet receipt
+	nonMembershipSignBytes, nonMembershipProof := proof(solo, cdc, &solomachine.SignBytes{
+		Sequence:    solo.Sequence,
+		Timestamp:   40,
+		Diversifier: solo.Diversifier,
+		Path:        merklePath(t, nonMembershipPath),
+		Data:        nil,
+	})
+
+	// two different values signed at the same sequence
+	var misbehaviourSignBytes [2][]byte
+	var signatures [2]*solomachine.SignatureAndData
+	for i, data := range [][]byte{[]byte("connection end one"), []byte("connection end two")} {
+		misbehaviourSignBytes[i] = cdc.MustMarshal(&solomachine.SignBytes{
+			Sequence:    solo.Sequence,
+			Timestamp:   50,
+			Diversifier: solo.Diversifier,
+			Path:        merklePath(t, membershipPath),
+			Data:        data,
+		})
+		signatures[i] = &solomachine.SignatureAndData{
+			Signature: solo.GenerateSignature(misbehaviourSignBytes[i]),
+			Path:      merklePath(t, membershipPath),
+			Data:      data,
+			Timestamp: 50,
+		}
+	}
+	misbehaviour := &solomachine.Misbehaviour{
+		Sequence:     solo.Sequence,
+		SignatureOne: signatures[0],
+		SignatureTwo: signatures[1],
+	}
+
+	return vectors{
+		Description:    name + " solo machine at sequence 1",
+		ClientState:    anyHex(t, cdc, solo.ClientState()),
+		ConsensusState: anyHex(t, cdc, solo.ConsensusState()),
+		Header: headerVector{
+			HeaderData: hex.EncodeToString(headerData),
+			SignBytes:  hex.EncodeToString(headerSignBytes),
+			Header:     anyHex(t, cdc, header),
+		},
+		Membership: membershipVector{
+			Prefix:    prefix,
+			Path:      membershipPath,
+			Value:     hex.EncodeToString(membershipValue),
+			SignBytes: membershipSignBytes,
+			Proof:     membershipProof,
+		},
+		NonMembership: nonMembershipVector{
+			Prefix:    prefix,
+			Path:      nonMembershipPath,
+			SignBytes: nonMembershipSignBytes,
+			Proof:     nonMembershipProof,
+		},
+		Misbehaviour: misbehaviourVector{
+			SignBytesOne: hex.EncodeToString(misbehaviourSignBytes[0]),
+			SignBytesTwo: hex.EncodeToString(misbehaviourSignBytes[1]),
+			Misbehaviour: anyHex(t, cdc, misbehaviour),
+		},
+	}
+}
+
+func TestWriteVectors(t *testing.T) {
+	cases := map[string]vectors{
+		"ed25519":   generate(t, "ed25519", ed25519Key(1), ed25519Key(2)),
+		"secp256k1": generate(t, "secp256k1", secp256k1Key(1), secp256k1Key(2)),
+	}
+	for name, vectors := range cases {
+		bz, err := json.MarshalIndent(vectors, "", "  ")
+		require.NoError(t, err)
+		err = os.WriteFile(filepath.Join("..", name+".json"), append(bz, '\n'), 0o644)
+		require.NoError(t, err)
+	}
+}
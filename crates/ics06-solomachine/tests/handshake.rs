@@ -0,0 +1,1670 @@
+//! Simulates a solo machine opening connections and channels with a host
+//! chain running this light client, from either side, then relaying ICS-20
+//! packets both ways and closing a channel.
+//!
+//! Every message goes through `ibc_core::entrypoint::dispatch` on a host
+//! whose ICS-03/ICS-04 stores live in [`Host`] and whose solo machine clients
+//! live in a [`MockContext`]; the solo machine side signs the proofs the
+//! handlers ask the client to verify. As in ibc-go, every verified proof
+//! consumes the client sequence it is signed at, so the proofs of a message
+//! are signed at successive sequences starting at its proof height.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use ibc_client_solomachine::client_state::ClientState;
+use ibc_client_solomachine::consensus_state::ConsensusState as SmConsensusState;
+use ibc_client_solomachine::mock::MockContext;
+use ibc_client_solomachine::types::cosmos::crypto::SigningKey;
+use ibc_client_solomachine::types::header::SENTINEL_HEADER_PATH;
+use ibc_client_solomachine::types::proof::sign_bytes_path;
+use ibc_client_solomachine::types::proof::types::header_data::HeaderData;
+use ibc_client_solomachine::types::proof::types::sign_bytes::SignBytes;
+use ibc_client_solomachine::types::proof::types::timestamped_signature_data::TimestampedSignatureData;
+use ibc_client_solomachine::types::{
+    client_type, ClientState as ClientStateType, ConsensusState as ConsensusStateType, Header,
+};
+use ibc_client_solomachine::CommonContext;
+use ibc_core::channel::handler::send_packet;
+use ibc_core::channel::types::acknowledgement::Acknowledgement;
+use ibc_core::channel::types::channel::{
+    ChannelEnd, Counterparty as ChannelCounterparty, Order, State as ChannelState,
+};
+use ibc_core::channel::types::commitment::{AcknowledgementCommitment, PacketCommitment};
+use ibc_core::channel::types::error::{ChannelError, PacketError};
+use ibc_core::channel::types::msgs::{
+    ChannelMsg, MsgAcknowledgement, MsgChannelCloseConfirm, MsgChannelOpenAck,
+    MsgChannelOpenConfirm, MsgChannelOpenInit, MsgChannelOpenTry, MsgRecvPacket, MsgTimeout,
+    PacketMsg,
+};
+use ibc_core::channel::types::packet::{Packet, Receipt};
+use ibc_core::channel::types::timeout::TimeoutHeight;
+use ibc_core::channel::types::Version as ChannelVersion;
+use ibc_core::client::context::client_state::{
+    ClientStateCommon, ClientStateExecution, ClientStateValidation,
+};
+use ibc_core::client::context::consensus_state::ConsensusState;
+use ibc_core::client::context::ClientExecutionContext;
+use ibc_core::client::types::error::ClientError;
+use ibc_core::client::types::msgs::{ClientMsg, MsgCreateClient, MsgUpdateClient};
+use ibc_core::client::types::{Height, Status, UpdateKind};
+use ibc_core::commitment_types::commitment::{
+    CommitmentPrefix, CommitmentProofBytes, CommitmentRoot,
+};
+use ibc_core::connection::types::error::ConnectionError;
+use ibc_core::connection::types::msgs::{
+    ConnectionMsg, MsgConnectionOpenAck, MsgConnectionOpenConfirm, MsgConnectionOpenInit,
+    MsgConnectionOpenTry,
+};
+use ibc_core::connection::types::version::Version as ConnectionVersion;
+use ibc_core::connection::types::{
+    ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
+};
+use ibc_core::entrypoint::dispatch;
+use ibc_core::handler::types::error::ContextError;
+use ibc_core::handler::types::events::IbcEvent;
+use ibc_core::handler::types::msgs::MsgEnvelope;
+use ibc_core::host::types::identifiers::{
+    ChannelId, ClientId, ClientType, ConnectionId, PortId, Sequence,
+};
+use ibc_core::host::types::path::{
+    AckPath, ChannelEndPath, ClientConnectionPath, ClientConsensusStatePath, ClientStatePath,
+    CommitmentPath, ConnectionPath, Path, ReceiptPath, SeqAckPath, SeqRecvPath, SeqSendPath,
+};
+use ibc_core::host::{ExecutionContext, ValidationContext};
+use ibc_core::primitives::{Signer, Timestamp};
+use ibc_core::router::module::Module;
+use ibc_core::router::router::Router;
+use ibc_core::router::types::module::{ModuleExtras, ModuleId};
+use ibc_proto::google::protobuf::{Any, Timestamp as RawTimestamp};
+use ibc_proto::ibc::core::commitment::v1::{MerklePath, MerkleRoot};
+use ibc_proto::ibc::lightclients::tendermint::v1::{
+    ClientState as RawTmClientState, ConsensusState as RawTmConsensusState,
+};
+use ibc_proto::Protobuf;
+use prost::Message;
+use sha2::{Digest, Sha256};
+
+const PREFIX: &[u8] = b"ibc";
+const HOST_CHAIN_ID: &str = "host";
+const TM_CLIENT_STATE_TYPE_URL: &str = "/ibc.lightclients.tendermint.v1.ClientState";
+const TM_CONSENSUS_STATE_TYPE_URL: &str = "/ibc.lightclients.tendermint.v1.ConsensusState";
+const SUCCESS_ACK: &[u8] = br#"{"result":"AQ=="}"#;
+
+/// The off-chain side: a key, a diversifier and the sequence the host client
+/// expects the next signature at.
+#[derive(Clone)]
+struct SoloMachine {
+    key: SigningKey,
+    diversifier: String,
+    sequence: u64,
+    timestamp: u64,
+}
+
+impl SoloMachine {
+    fn consensus_state(&self) -> ConsensusStateType {
+        ConsensusStateType::new(
+            self.key.public_key().unwrap(),
+            self.diversifier.clone(),
+            Timestamp::from_nanoseconds(self.timestamp).unwrap(),
+        )
+    }
+
+    fn client_state(&self) -> ClientState {
+        ClientState::from(ClientStateType::new(
+            Height::new(0, self.sequence).unwrap(),
+            false,
+            self.consensus_state(),
+        ))
+    }
+
+    /// The sequence the next proof is signed at, which is the proof height of
+    /// a message whose proofs start with it.
+    fn height(&self) -> Height {
+        Height::new(0, self.sequence).unwrap()
+    }
+
+    /// Sign `value` at `path` under the `ibc` prefix at the current sequence,
+    /// then move to the next one; an empty value proves absence.
+    fn prove(&mut self, path: impl Into<Path>, value: Vec<u8>) -> CommitmentProofBytes {
+        self.timestamp += 1;
+        let timestamp = Timestamp::from_nanoseconds(self.timestamp).unwrap();
+        let merkle_path = MerklePath {
+            key_path: vec![
+                String::from_utf8(PREFIX.to_vec()).unwrap(),
+                path.into().to_string(),
+            ],
+        };
+        let signature_data = self.key.sign_signature_data(SignBytes {
+            sequence: self.sequence,
+            timestamp: self.timestamp,
+            diversifier: self.diversifier.clone(),
+            path: sign_bytes_path(&merkle_path),
+            data: value,
+        });
+        self.sequence += 1;
+
+        TimestampedSignatureData {
+            signature_data,
+            timestamp,
+        }
+        .encode_vec()
+        .try_into()
+        .unwrap()
+    }
+
+    /// Rotate to `new_key`, returning the header the host updates its client with.
+    fn rotate(&mut self, new_key: SigningKey, new_diversifier: &str) -> Header {
+        self.timestamp += 1;
+        let header_data = HeaderData {
+            new_pub_key: new_key.public_key().unwrap(),
+            new_diversifier: new_diversifier.into(),
+        };
+        let signature = self.key.sign_signature_data(SignBytes {
+            sequence: self.sequence,
+            timestamp: self.timestamp,
+            diversifier: self.diversifier.clone(),
+            path: SENTINEL_HEADER_PATH.as_bytes().to_vec(),
+            data: header_data.encode_vec(),
+        });
+        let header = Header {
+            timestamp: Timestamp::from_nanoseconds(self.timestamp).unwrap(),
+            signature,
+            new_public_key: new_key.public_key().unwrap(),
+            new_diversifier: new_diversifier.into(),
+        };
+
+        self.key = new_key;
+        self.diversifier = new_diversifier.into();
+        self.sequence += 1;
+        header
+    }
+}
+
+/// The consensus states the host hands to the ICS-03/ICS-04 handlers: those
+/// of its solo machine clients, and its own tendermint ones the solo machine
+/// proves it tracks.
+#[derive(Clone, Debug)]
+enum HostConsensusState {
+    Solomachine(SmConsensusState),
+    Tendermint {
+        root: CommitmentRoot,
+        timestamp: Timestamp,
+        next_validators_hash: Vec<u8>,
+    },
+}
+
+impl ConsensusState for HostConsensusState {
+    fn root(&self) -> &CommitmentRoot {
+        match self {
+            Self::Solomachine(consensus_state) => consensus_state.root(),
+            Self::Tendermint { root, .. } => root,
+        }
+    }
+
+    fn timestamp(&self) -> Timestamp {
+        match self {
+            Self::Solomachine(consensus_state) => consensus_state.timestamp(),
+            Self::Tendermint { timestamp, .. } => *timestamp,
+        }
+    }
+
+    fn encode_vec(self) -> Vec<u8> {
+        match self {
+            Self::Solomachine(consensus_state) => {
+                <SmConsensusState as Protobuf<Any>>::encode_vec(consensus_state)
+            }
+            Self::Tendermint {
+                root,
+                timestamp,
+                next_validators_hash,
+            } => {
+                let nanoseconds = timestamp.nanoseconds();
+                Any {
+                    type_url: TM_CONSENSUS_STATE_TYPE_URL.into(),
+                    value: RawTmConsensusState {
+                        timestamp: Some(RawTimestamp {
+                            seconds: (nanoseconds / 1_000_000_000) as i64,
+                            nanos: (nanoseconds % 1_000_000_000) as i32,
+                        }),
+                        root: Some(MerkleRoot {
+                            hash: root.into_vec(),
+                        }),
+                        next_validators_hash,
+                    }
+                    .encode_to_vec(),
+                }
+                .encode_to_vec()
+            }
+        }
+    }
+}
+
+/// Client states advanced by the proofs of the message being handled, by
+/// client.
+type ConsumedClientStates = Arc<Mutex<BTreeMap<ClientId, ClientState>>>;
+
+/// The client state the host hands to the handlers: a stored solo machine
+/// client state which, like ibc-go, moves to the next sequence and to the
+/// proof timestamp with every proof it verifies. `ibc-core` verifies proofs
+/// through `&self`, so the advanced state is kept in `consumed` and stored by
+/// [`Chain::deliver`] once the message is handled.
+#[derive(Clone, Debug)]
+struct HostClientState {
+    /// `None` for a client state decoded from a `MsgCreateClient`.
+    client_id: Option<ClientId>,
+    client_state: ClientState,
+    consumed: ConsumedClientStates,
+}
+
+impl HostClientState {
+    /// Verify `proof` with `verify` against the client state the previous
+    /// proofs of the message left, then consume its sequence.
+    fn consume(
+        &self,
+        proof: &CommitmentProofBytes,
+        verify: impl FnOnce(&ClientState) -> Result<(), ClientError>,
+    ) -> Result<(), ClientError> {
+        let client_id = self.client_id.as_ref().ok_or_else(|| ClientError::Other {
+            description: "no client to verify the proof with".into(),
+        })?;
+        let mut consumed = self.consumed.lock().unwrap();
+        let client_state = consumed.get(client_id).unwrap_or(&self.client_state);
+        verify(client_state)?;
+
+        let proof =
+            TimestampedSignatureData::decode_vec(&Vec::<u8>::from(proof.clone())).map_err(|e| {
+                ClientError::Other {
+                    description: e.to_string(),
+                }
+            })?;
+        let mut advanced = client_state.inner().clone();
+        advanced.sequence = advanced.sequence.increment();
+        advanced.consensus_state.timestamp = proof.timestamp;
+        consumed.insert(client_id.clone(), ClientState::from(advanced));
+        Ok(())
+    }
+}
+
+impl ClientStateCommon for HostClientState {
+    fn verify_consensus_state(&self, consensus_state: Any) -> Result<(), ClientError> {
+        self.client_state.verify_consensus_state(consensus_state)
+    }
+
+    fn client_type(&self) -> ClientType {
+        self.client_state.client_type()
+    }
+
+    fn latest_height(&self) -> Height {
+        self.client_state.latest_height()
+    }
+
+    fn validate_proof_height(&self, proof_height: Height) -> Result<(), ClientError> {
+        self.client_state.validate_proof_height(proof_height)
+    }
+
+    fn verify_upgrade_client(
+        &self,
+        upgraded_client_state: Any,
+        upgraded_consensus_state: Any,
+        proof_upgrade_client: CommitmentProofBytes,
+        proof_upgrade_consensus_state: CommitmentProofBytes,
+        root: &CommitmentRoot,
+    ) -> Result<(), ClientError> {
+        self.client_state.verify_upgrade_client(
+            upgraded_client_state,
+            upgraded_consensus_state,
+            proof_upgrade_client,
+            proof_upgrade_consensus_state,
+            root,
+        )
+    }
+
+    fn verify_membership(
+        &self,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        path: Path,
+        value: Vec<u8>,
+    ) -> Result<(), ClientError> {
+        self.consume(proof, |client_state| {
+            client_state.verify_membership(prefix, proof, root, path, value)
+        })
+    }
+
+    fn verify_non_membership(
+        &self,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        path: Path,
+    ) -> Result<(), ClientError> {
+        self.consume(proof, |client_state| {
+            client_state.verify_non_membership(prefix, proof, root, path)
+        })
+    }
+}
+
+impl ClientStateValidation<MockContext> for HostClientState {
+    fn verify_client_message(
+        &self,
+        ctx: &MockContext,
+        client_id: &ClientId,
+        client_message: Any,
+        update_kind: &UpdateKind,
+    ) -> Result<(), ClientError> {
+        self.client_state
+            .verify_client_message(ctx, client_id, client_message, update_kind)
+    }
+
+    fn check_for_misbehaviour(
+        &self,
+        ctx: &MockContext,
+        client_id: &ClientId,
+        client_message: Any,
+        update_kind: &UpdateKind,
+    ) -> Result<bool, ClientError> {
+        self.client_state
+            .check_for_misbehaviour(ctx, client_id, client_message, update_kind)
+    }
+
+    fn status(&self, ctx: &MockContext, client_id: &ClientId) -> Result<Status, ClientError> {
+        self.client_state.status(ctx, client_id)
+    }
+}
+
+impl ClientStateExecution<MockContext> for HostClientState {
+    fn initialise(
+        &self,
+        ctx: &mut MockContext,
+        client_id: &ClientId,
+        consensus_state: Any,
+    ) -> Result<(), ClientError> {
+        self.client_state
+            .initialise(ctx, client_id, consensus_state)
+    }
+
+    fn update_state(
+        &self,
+        ctx: &mut MockContext,
+        client_id: &ClientId,
+        header: Any,
+    ) -> Result<Vec<Height>, ClientError> {
+        self.client_state.update_state(ctx, client_id, header)
+    }
+
+    fn update_state_on_misbehaviour(
+        &self,
+        ctx: &mut MockContext,
+        client_id: &ClientId,
+        client_message: Any,
+        update_kind: &UpdateKind,
+    ) -> Result<(), ClientError> {
+        self.client_state
+            .update_state_on_misbehaviour(ctx, client_id, client_message, update_kind)
+    }
+
+    fn update_state_on_upgrade(
+        &self,
+        ctx: &mut MockContext,
+        client_id: &ClientId,
+        upgraded_client_state: Any,
+        upgraded_consensus_state: Any,
+    ) -> Result<Height, ClientError> {
+        self.client_state.update_state_on_upgrade(
+            ctx,
+            client_id,
+            upgraded_client_state,
+            upgraded_consensus_state,
+        )
+    }
+}
+
+/// The host chain: its solo machine clients, connections, channels and
+/// packet stores.
+#[derive(Debug)]
+struct Host {
+    client: MockContext,
+    consumed: ConsumedClientStates,
+    host_consensus_states: BTreeMap<Height, HostConsensusState>,
+    client_counter: u64,
+    connections: BTreeMap<ConnectionId, ConnectionEnd>,
+    client_connections: BTreeMap<ClientId, ConnectionId>,
+    connection_counter: u64,
+    channels: BTreeMap<ChannelEndPath, ChannelEnd>,
+    channel_counter: u64,
+    next_sequence_send: BTreeMap<(PortId, ChannelId), Sequence>,
+    next_sequence_recv: BTreeMap<(PortId, ChannelId), Sequence>,
+    next_sequence_ack: BTreeMap<(PortId, ChannelId), Sequence>,
+    packet_commitments: BTreeMap<CommitmentPath, PacketCommitment>,
+    packet_receipts: BTreeMap<ReceiptPath, Receipt>,
+    packet_acknowledgements: BTreeMap<AckPath, AcknowledgementCommitment>,
+    events: Vec<IbcEvent>,
+}
+
+impl Host {
+    fn new() -> Self {
+        let mut host = Self {
+            client: MockContext::new(Timestamp::from_nanoseconds(10_000).unwrap()),
+            consumed: ConsumedClientStates::default(),
+            host_consensus_states: BTreeMap::new(),
+            client_counter: 0,
+            connections: BTreeMap::new(),
+            client_connections: BTreeMap::new(),
+            connection_counter: 0,
+            channels: BTreeMap::new(),
+            channel_counter: 0,
+            next_sequence_send: BTreeMap::new(),
+            next_sequence_recv: BTreeMap::new(),
+            next_sequence_ack: BTreeMap::new(),
+            packet_commitments: BTreeMap::new(),
+            packet_receipts: BTreeMap::new(),
+            packet_acknowledgements: BTreeMap::new(),
+            events: Vec::new(),
+        };
+        host.commit_block();
+        host
+    }
+
+    /// Produce the next block five seconds later.
+    fn advance_block(&mut self) {
+        self.client.advance_host_time(Duration::from_secs(5));
+        self.commit_block();
+    }
+
+    /// Record the host's own consensus state at the current height.
+    fn commit_block(&mut self) {
+        let height = CommonContext::host_height(&self.client).unwrap();
+        let timestamp = CommonContext::host_timestamp(&self.client).unwrap();
+        self.host_consensus_states.insert(
+            height,
+            HostConsensusState::Tendermint {
+                root: CommitmentRoot::from(Sha256::digest(height.to_string()).to_vec()),
+                timestamp,
+                next_validators_hash: Sha256::digest(b"validators").to_vec(),
+            },
+        );
+    }
+
+    /// The tendermint client state of this host at `latest_height`, as a
+    /// counterparty tracks it.
+    fn self_client_state(&self, chain_id: &str, latest_height: Height) -> Any {
+        Any {
+            type_url: TM_CLIENT_STATE_TYPE_URL.into(),
+            value: RawTmClientState {
+                chain_id: chain_id.into(),
+                latest_height: Some(latest_height.into()),
+                ..Default::default()
+            }
+            .encode_to_vec(),
+        }
+    }
+}
+
+impl ValidationContext for Host {
+    type V = MockContext;
+    type E = MockContext;
+    type AnyConsensusState = HostConsensusState;
+    type AnyClientState = HostClientState;
+
+    fn get_client_validation_context(&self) -> &Self::V {
+        &self.client
+    }
+
+    fn client_state(&self, client_id: &ClientId) -> Result<Self::AnyClientState, ContextError> {
+        let client_state = self
+            .client
+            .client_state(client_id)
+            .cloned()
+            .ok_or_else(|| ClientError::ClientStateNotFound {
+                client_id: client_id.clone(),
+            })?;
+        Ok(HostClientState {
+            client_id: Some(client_id.clone()),
+            client_state,
+            consumed: self.consumed.clone(),
+        })
+    }
+
+    fn decode_client_state(&self, client_state: Any) -> Result<Self::AnyClientState, ContextError> {
+        Ok(HostClientState {
+            client_id: None,
+            client_state: ClientState::try_from(client_state)?,
+            consumed: self.consumed.clone(),
+        })
+    }
+
+    /// Like ibc-go v7, a solo machine client keeps its consensus state in its
+    /// client state, so that one is returned at every height up to the client
+    /// sequence; the handlers only read its root.
+    fn consensus_state(
+        &self,
+        client_cons_state_path: &ClientConsensusStatePath,
+    ) -> Result<Self::AnyConsensusState, ContextError> {
+        let client_id = &client_cons_state_path.client_id;
+        let height = Height::new(
+            client_cons_state_path.revision_number,
+            client_cons_state_path.revision_height,
+        )?;
+        let client_state = self
+            .client
+            .client_state(client_id)
+            .filter(|client_state| height <= client_state.latest_height())
+            .ok_or_else(|| ClientError::ConsensusStateNotFound {
+                client_id: client_id.clone(),
+                height,
+            })?;
+        Ok(HostConsensusState::Solomachine(SmConsensusState::from(
+            client_state.inner().consensus_state.clone(),
+        )))
+    }
+
+    fn host_height(&self) -> Result<Height, ContextError> {
+        CommonContext::host_height(&self.client)
+    }
+
+    fn host_timestamp(&self) -> Result<Timestamp, ContextError> {
+        CommonContext::host_timestamp(&self.client)
+    }
+
+    fn host_consensus_state(
+        &self,
+        height: &Height,
+    ) -> Result<Self::AnyConsensusState, ContextError> {
+        self.host_consensus_states
+            .get(height)
+            .cloned()
+            .ok_or_else(|| ClientError::MissingLocalConsensusState { height: *height }.into())
+    }
+
+    fn client_counter(&self) -> Result<u64, ContextError> {
+        Ok(self.client_counter)
+    }
+
+    fn connection_end(&self, conn_id: &ConnectionId) -> Result<ConnectionEnd, ContextError> {
+        self.connections.get(conn_id).cloned().ok_or_else(|| {
+            ConnectionError::ConnectionNotFound {
+                connection_id: conn_id.clone(),
+            }
+            .into()
+        })
+    }
+
+    fn validate_self_client(
+        &self,
+        client_state_of_host_on_counterparty: Any,
+    ) -> Result<(), ContextError> {
+        let invalid = |description: String| ContextError::from(ClientError::Other { description });
+        if client_state_of_host_on_counterparty.type_url != TM_CLIENT_STATE_TYPE_URL {
+            return Err(invalid(format!(
+                "unexpected client type {}",
+                client_state_of_host_on_counterparty.type_url
+            )));
+        }
+        let client_state =
+            RawTmClientState::decode(client_state_of_host_on_counterparty.value.as_slice())
+                .map_err(|e| invalid(e.to_string()))?;
+        if client_state.chain_id != HOST_CHAIN_ID {
+            return Err(invalid(format!(
+                "client tracks chain {}, not {}",
+                client_state.chain_id, HOST_CHAIN_ID
+            )));
+        }
+        if client_state
+            .frozen_height
+            .is_some_and(|height| height.revision_height != 0)
+        {
+            return Err(invalid("client is frozen".into()));
+        }
+        let latest_height = Height::try_from(client_state.latest_height.unwrap_or_default())?;
+        let host_height = self.host_height()?;
+        if latest_height > host_height {
+            return Err(invalid(format!(
+                "client latest height {} is above the host height {}",
+                latest_height, host_height
+            )));
+        }
+        Ok(())
+    }
+
+    fn commitment_prefix(&self) -> CommitmentPrefix {
+        prefix()
+    }
+
+    fn connection_counter(&self) -> Result<u64, ContextError> {
+        Ok(self.connection_counter)
+    }
+
+    fn channel_end(&self, channel_end_path: &ChannelEndPath) -> Result<ChannelEnd, ContextError> {
+        self.channels.get(channel_end_path).cloned().ok_or_else(|| {
+            ChannelError::ChannelNotFound {
+                port_id: channel_end_path.0.clone(),
+                channel_id: channel_end_path.1.clone(),
+            }
+            .into()
+        })
+    }
+
+    fn get_next_sequence_send(
+        &self,
+        seq_send_path: &SeqSendPath,
+    ) -> Result<Sequence, ContextError> {
+        let SeqSendPath(port_id, channel_id) = seq_send_path.clone();
+        self.next_sequence_send
+            .get(&(port_id.clone(), channel_id.clone()))
+            .copied()
+            .ok_or_else(|| {
+                PacketError::MissingNextSendSeq {
+                    port_id,
+                    channel_id,
+                }
+                .into()
+            })
+    }
+
+    fn get_next_sequence_recv(
+        &self,
+        seq_recv_path: &SeqRecvPath,
+    ) -> Result<Sequence, ContextError> {
+        let SeqRecvPath(port_id, channel_id) = seq_recv_path.clone();
+        self.next_sequence_recv
+            .get(&(port_id.clone(), channel_id.clone()))
+            .copied()
+            .ok_or_else(|| {
+                PacketError::MissingNextRecvSeq {
+                    port_id,
+                    channel_id,
+                }
+                .into()
+            })
+    }
+
+    fn get_next_sequence_ack(&self, seq_ack_path: &SeqAckPath) -> Result<Sequence, ContextError> {
+        let SeqAckPath(port_id, channel_id) = seq_ack_path.clone();
+        self.next_sequence_ack
+            .get(&(port_id.clone(), channel_id.clone()))
+            .copied()
+            .ok_or_else(|| {
+                PacketError::MissingNextAckSeq {
+                    port_id,
+                    channel_id,
+                }
+                .into()
+            })
+    }
+
+    fn get_packet_commitment(
+        &self,
+        commitment_path: &CommitmentPath,
+    ) -> Result<PacketCommitment, ContextError> {
+        self.packet_commitments
+            .get(commitment_path)
+            .cloned()
+            .ok_or_else(|| {
+                PacketError::PacketCommitmentNotFound {
+                    sequence: commitment_path.sequence,
+                }
+                .into()
+            })
+    }
+
+    fn get_packet_receipt(&self, receipt_path: &ReceiptPath) -> Result<Receipt, ContextError> {
+        self.packet_receipts
+            .get(receipt_path)
+            .cloned()
+            .ok_or_else(|| {
+                PacketError::PacketReceiptNotFound {
+                    sequence: receipt_path.sequence,
+                }
+                .into()
+            })
+    }
+
+    fn get_packet_acknowledgement(
+        &self,
+        ack_path: &AckPath,
+    ) -> Result<AcknowledgementCommitment, ContextError> {
+        self.packet_acknowledgements
+            .get(ack_path)
+            .cloned()
+            .ok_or_else(|| {
+                PacketError::PacketAcknowledgementNotFound {
+                    sequence: ack_path.sequence,
+                }
+                .into()
+            })
+    }
+
+    fn channel_counter(&self) -> Result<u64, ContextError> {
+        Ok(self.channel_counter)
+    }
+
+    fn max_expected_time_per_block(&self) -> Duration {
+        Duration::from_secs(8)
+    }
+
+    fn validate_message_signer(&self, _signer: &Signer) -> Result<(), ContextError> {
+        Ok(())
+    }
+}
+
+impl ExecutionContext for Host {
+    fn get_client_execution_context(&mut self) -> &mut Self::E {
+        &mut self.client
+    }
+
+    fn increase_client_counter(&mut self) -> Result<(), ContextError> {
+        self.client_counter += 1;
+        Ok(())
+    }
+
+    fn store_connection(
+        &mut self,
+        connection_path: &ConnectionPath,
+        connection_end: ConnectionEnd,
+    ) -> Result<(), ContextError> {
+        self.connections
+            .insert(connection_path.0.clone(), connection_end);
+        Ok(())
+    }
+
+    fn store_connection_to_client(
+        &mut self,
+        client_connection_path: &ClientConnectionPath,
+        conn_id: ConnectionId,
+    ) -> Result<(), ContextError> {
+        self.client_connections
+            .insert(client_connection_path.0.clone(), conn_id);
+        Ok(())
+    }
+
+    fn increase_connection_counter(&mut self) -> Result<(), ContextError> {
+        self.connection_counter += 1;
+        Ok(())
+    }
+
+    fn store_packet_commitment(
+        &mut self,
+        commitment_path: &CommitmentPath,
+        commitment: PacketCommitment,
+    ) -> Result<(), ContextError> {
+        self.packet_commitments
+            .insert(commitment_path.clone(), commitment);
+        Ok(())
+    }
+
+    fn delete_packet_commitment(
+        &mut self,
+        commitment_path: &CommitmentPath,
+    ) -> Result<(), ContextError> {
+        self.packet_commitments.remove(commitment_path);
+        Ok(())
+    }
+
+    fn store_packet_receipt(
+        &mut self,
+        receipt_path: &ReceiptPath,
+        receipt: Receipt,
+    ) -> Result<(), ContextError> {
+        self.packet_receipts.insert(receipt_path.clone(), receipt);
+        Ok(())
+    }
+
+    fn store_packet_acknowledgement(
+        &mut self,
+        ack_path: &AckPath,
+        ack_commitment: AcknowledgementCommitment,
+    ) -> Result<(), ContextError> {
+        self.packet_acknowledgements
+            .insert(ack_path.clone(), ack_commitment);
+        Ok(())
+    }
+
+    fn delete_packet_acknowledgement(&mut self, ack_path: &AckPath) -> Result<(), ContextError> {
+        self.packet_acknowledgements.remove(ack_path);
+        Ok(())
+    }
+
+    fn store_channel(
+        &mut self,
+        channel_end_path: &ChannelEndPath,
+        channel_end: ChannelEnd,
+    ) -> Result<(), ContextError> {
+        self.channels.insert(channel_end_path.clone(), channel_end);
+        Ok(())
+    }
+
+    fn store_next_sequence_send(
+        &mut self,
+        seq_send_path: &SeqSendPath,
+        seq: Sequence,
+    ) -> Result<(), ContextError> {
+        let SeqSendPath(port_id, channel_id) = seq_send_path.clone();
+        self.next_sequence_send.insert((port_id, channel_id), seq);
+        Ok(())
+    }
+
+    fn store_next_sequence_recv(
+        &mut self,
+        seq_recv_path: &SeqRecvPath,
+        seq: Sequence,
+    ) -> Result<(), ContextError> {
+        let SeqRecvPath(port_id, channel_id) = seq_recv_path.clone();
+        self.next_sequence_recv.insert((port_id, channel_id), seq);
+        Ok(())
+    }
+
+    fn store_next_sequence_ack(
+        &mut self,
+        seq_ack_path: &SeqAckPath,
+        seq: Sequence,
+    ) -> Result<(), ContextError> {
+        let SeqAckPath(port_id, channel_id) = seq_ack_path.clone();
+        self.next_sequence_ack.insert((port_id, channel_id), seq);
+        Ok(())
+    }
+
+    fn increase_channel_counter(&mut self) -> Result<(), ContextError> {
+        self.channel_counter += 1;
+        Ok(())
+    }
+
+    fn emit_ibc_event(&mut self, event: IbcEvent) -> Result<(), ContextError> {
+        self.events.push(event);
+        Ok(())
+    }
+
+    fn log_message(&mut self, _message: String) -> Result<(), ContextError> {
+        Ok(())
+    }
+}
+
+/// A transfer application that accepts every packet and records what the
+/// handlers deliver to it.
+#[derive(Debug, Default)]
+struct TransferModule {
+    received: Vec<Packet>,
+    acknowledged: Vec<(Packet, Acknowledgement)>,
+    timed_out: Vec<Packet>,
+}
+
+impl Module for TransferModule {
+    fn on_chan_open_init_validate(
+        &self,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty: &ChannelCounterparty,
+        version: &ChannelVersion,
+    ) -> Result<ChannelVersion, ChannelError> {
+        Ok(version.clone())
+    }
+
+    fn on_chan_open_init_execute(
+        &mut self,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty: &ChannelCounterparty,
+        version: &ChannelVersion,
+    ) -> Result<(ModuleExtras, ChannelVersion), ChannelError> {
+        Ok((ModuleExtras::empty(), version.clone()))
+    }
+
+    fn on_chan_open_try_validate(
+        &self,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty: &ChannelCounterparty,
+        counterparty_version: &ChannelVersion,
+    ) -> Result<ChannelVersion, ChannelError> {
+        Ok(counterparty_version.clone())
+    }
+
+    fn on_chan_open_try_execute(
+        &mut self,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty: &ChannelCounterparty,
+        counterparty_version: &ChannelVersion,
+    ) -> Result<(ModuleExtras, ChannelVersion), ChannelError> {
+        Ok((ModuleExtras::empty(), counterparty_version.clone()))
+    }
+
+    fn on_recv_packet_execute(
+        &mut self,
+        packet: &Packet,
+        _relayer: &Signer,
+    ) -> (ModuleExtras, Acknowledgement) {
+        self.received.push(packet.clone());
+        (
+            ModuleExtras::empty(),
+            Acknowledgement::try_from(SUCCESS_ACK.to_vec()).unwrap(),
+        )
+    }
+
+    fn on_acknowledgement_packet_validate(
+        &self,
+        _packet: &Packet,
+        _acknowledgement: &Acknowledgement,
+        _relayer: &Signer,
+    ) -> Result<(), PacketError> {
+        Ok(())
+    }
+
+    fn on_acknowledgement_packet_execute(
+        &mut self,
+        packet: &Packet,
+        acknowledgement: &Acknowledgement,
+        _relayer: &Signer,
+    ) -> (ModuleExtras, Result<(), PacketError>) {
+        self.acknowledged
+            .push((packet.clone(), acknowledgement.clone()));
+        (ModuleExtras::empty(), Ok(()))
+    }
+
+    fn on_timeout_packet_validate(
+        &self,
+        _packet: &Packet,
+        _relayer: &Signer,
+    ) -> Result<(), PacketError> {
+        Ok(())
+    }
+
+    fn on_timeout_packet_execute(
+        &mut self,
+        packet: &Packet,
+        _relayer: &Signer,
+    ) -> (ModuleExtras, Result<(), PacketError>) {
+        self.timed_out.push(packet.clone());
+        (ModuleExtras::empty(), Ok(()))
+    }
+}
+
+/// Routes the transfer port to the [`TransferModule`].
+#[derive(Debug, Default)]
+struct HostRouter {
+    transfer: TransferModule,
+}
+
+impl HostRouter {
+    fn transfer_module_id() -> ModuleId {
+        ModuleId::new("transfer".into())
+    }
+}
+
+impl Router for HostRouter {
+    fn get_route(&self, module_id: &ModuleId) -> Option<&dyn Module> {
+        (*module_id == Self::transfer_module_id()).then_some(&self.transfer as &dyn Module)
+    }
+
+    fn get_route_mut(&mut self, module_id: &ModuleId) -> Option<&mut dyn Module> {
+        (*module_id == Self::transfer_module_id()).then_some(&mut self.transfer as &mut dyn Module)
+    }
+
+    fn lookup_module(&self, port_id: &PortId) -> Option<ModuleId> {
+        (*port_id == PortId::transfer()).then(Self::transfer_module_id)
+    }
+}
+
+/// The host and its router, delivering relayed messages to the handlers.
+struct Chain {
+    host: Host,
+    router: HostRouter,
+}
+
+impl Chain {
+    /// Dispatch `msg`, then store the client states its proofs advanced if
+    /// it was handled, processed at the current host time and height so the
+    /// packet handlers find them when checking the connection delay.
+    fn deliver(&mut self, msg: impl Into<MsgEnvelope>) -> Result<(), ContextError> {
+        let result = dispatch(&mut self.host, &mut self.router, msg.into());
+        let consumed = std::mem::take(&mut *self.host.consumed.lock().unwrap());
+        if result.is_ok() {
+            let host_timestamp = self.host.host_timestamp()?;
+            let host_height = self.host.host_height()?;
+            let client = &mut self.host.client;
+            for (client_id, client_state) in consumed {
+                let height = client_state.latest_height();
+                client.store_client_state(ClientStatePath::new(&client_id), client_state)?;
+                client.store_update_time(client_id.clone(), height, host_timestamp)?;
+                client.store_update_height(client_id, height, host_height)?;
+            }
+        }
+        result
+    }
+
+    fn update_client(&mut self, client_id: &ClientId, header: Header) {
+        self.host.advance_block();
+        self.deliver(ClientMsg::UpdateClient(MsgUpdateClient {
+            client_id: client_id.clone(),
+            client_message: Any::from(header),
+            signer: relayer(),
+        }))
+        .unwrap();
+    }
+}
+
+fn relayer() -> Signer {
+    Signer::from("cosmos1relayer".to_string())
+}
+
+fn prefix() -> CommitmentPrefix {
+    CommitmentPrefix::try_from(PREFIX.to_vec()).unwrap()
+}
+
+fn ics20_packet_data(sender: &str, receiver: &str) -> Vec<u8> {
+    format!(
+        r#"{{"amount":"100","denom":"uatom","receiver":"{}","sender":"{}"}}"#,
+        receiver, sender
+    )
+    .into_bytes()
+}
+
+/// ICS-04 packet commitment:
+/// `sha256(timeout_timestamp || revision_number || revision_height || sha256(data))`.
+fn packet_commitment(data: &[u8], timeout_height: Height, timeout_timestamp: u64) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(timeout_timestamp.to_be_bytes());
+    hasher.update(timeout_height.revision_number().to_be_bytes());
+    hasher.update(timeout_height.revision_height().to_be_bytes());
+    hasher.update(Sha256::digest(data));
+    hasher.finalize().to_vec()
+}
+
+/// ICS-04 acknowledgement commitment: `sha256(ack)`.
+fn ack_commitment(ack: &[u8]) -> Vec<u8> {
+    Sha256::digest(ack).to_vec()
+}
+
+/// A host with a client of a solo machine at sequence 1.
+fn setup() -> (SoloMachine, Chain, ClientId) {
+    let solo_machine = SoloMachine {
+        key: SigningKey::from_secp256k1_bytes(&[1u8; 32]).unwrap(),
+        diversifier: "solo".into(),
+        sequence: 1,
+        timestamp: 1_000,
+    };
+    let mut chain = Chain {
+        host: Host::new(),
+        router: HostRouter::default(),
+    };
+    chain
+        .deliver(ClientMsg::CreateClient(MsgCreateClient::new(
+            solo_machine.client_state().into(),
+            solo_machine.consensus_state().into(),
+            relayer(),
+        )))
+        .unwrap();
+    (
+        solo_machine,
+        chain,
+        ClientId::new(client_type(), 0).unwrap(),
+    )
+}
+
+#[test]
+fn connection_and_channel_handshake_then_packets() {
+    let (mut solo_machine, mut chain, client_id) = setup();
+
+    // identifiers on the solo machine side
+    let counterparty_client_id: ClientId = "07-tendermint-0".parse().unwrap();
+    let counterparty_connection_id = ConnectionId::new(7);
+    let counterparty_channel_id = ChannelId::new(7);
+    let port_id = PortId::transfer();
+
+    chain
+        .deliver(ConnectionMsg::OpenInit(MsgConnectionOpenInit {
+            client_id_on_a: client_id.clone(),
+            counterparty: ConnectionCounterparty::new(
+                counterparty_client_id.clone(),
+                None,
+                prefix(),
+            ),
+            version: Some(ConnectionVersion::default()),
+            delay_period: Duration::ZERO,
+            signer: relayer(),
+        }))
+        .unwrap();
+    let connection_id = ConnectionId::new(0);
+    chain.host.advance_block();
+
+    // ConnOpenTry ran on the solo machine: the host's ConnOpenAck verifies
+    // its connection end in TRYOPEN, and the host client and consensus state
+    // it claims to track at host height 1.
+    let consensus_height = Height::new(0, 1).unwrap();
+    let host_client_state = chain
+        .host
+        .self_client_state(HOST_CHAIN_ID, consensus_height);
+    let host_consensus_state = chain.host.host_consensus_state(&consensus_height).unwrap();
+    let connection_end = |state| {
+        ConnectionEnd::new(
+            state,
+            counterparty_client_id.clone(),
+            ConnectionCounterparty::new(client_id.clone(), Some(connection_id.clone()), prefix()),
+            vec![ConnectionVersion::default()],
+            Duration::ZERO,
+        )
+        .unwrap()
+    };
+    let conn_open_ack = |solo_machine: &mut SoloMachine, connection_end: ConnectionEnd| {
+        let proofs_height_on_b = solo_machine.height();
+        MsgConnectionOpenAck {
+            conn_id_on_a: connection_id.clone(),
+            conn_id_on_b: counterparty_connection_id.clone(),
+            client_state_of_a_on_b: host_client_state.clone(),
+            proof_conn_end_on_b: solo_machine.prove(
+                ConnectionPath::new(&counterparty_connection_id),
+                connection_end.encode_vec(),
+            ),
+            proof_client_state_of_a_on_b: solo_machine.prove(
+                ClientStatePath::new(&counterparty_client_id),
+                host_client_state.encode_to_vec(),
+            ),
+            proof_consensus_state_of_a_on_b: solo_machine.prove(
+                ClientConsensusStatePath::new(
+                    counterparty_client_id.clone(),
+                    consensus_height.revision_number(),
+                    consensus_height.revision_height(),
+                ),
+                host_consensus_state.clone().encode_vec(),
+            ),
+            proofs_height_on_b,
+            consensus_height_of_a_on_b: consensus_height,
+            version: ConnectionVersion::default(),
+            signer: relayer(),
+            proof_consensus_state_of_a: None,
+        }
+    };
+
+    // proofs claimed at a sequence the client is not at; a rejected message
+    // consumes no sequence, so its proofs are signed on a copy
+    let mut msg = conn_open_ack(
+        &mut solo_machine.clone(),
+        connection_end(ConnectionState::TryOpen),
+    );
+    msg.proofs_height_on_b = solo_machine.height().increment();
+    assert!(matches!(
+        chain.deliver(ConnectionMsg::OpenAck(msg)),
+        Err(ContextError::ClientError(
+            ClientError::InvalidProofHeight { .. }
+        ))
+    ));
+
+    // a proof over a connection end still in INIT
+    let msg = conn_open_ack(
+        &mut solo_machine.clone(),
+        connection_end(ConnectionState::Init),
+    );
+    assert!(matches!(
+        chain.deliver(ConnectionMsg::OpenAck(msg)),
+        Err(ContextError::ConnectionError(
+            ConnectionError::VerifyConnectionState(_)
+        ))
+    ));
+    assert_eq!(
+        chain.host.client_state(&client_id).unwrap().latest_height(),
+        solo_machine.height()
+    );
+
+    // each of the three proofs consumes a sequence
+    let msg = conn_open_ack(&mut solo_machine, connection_end(ConnectionState::TryOpen));
+    chain.deliver(ConnectionMsg::OpenAck(msg)).unwrap();
+    assert_eq!(
+        chain.host.connection_end(&connection_id).unwrap().state(),
+        &ConnectionState::Open
+    );
+    assert_eq!(
+        chain.host.client_state(&client_id).unwrap().latest_height(),
+        Height::new(0, 4).unwrap()
+    );
+
+    // the solo machine rotates its key before opening the channel
+    let header = solo_machine.rotate(
+        SigningKey::from_ed25519_bytes(&[2u8; 32]).unwrap(),
+        "solo-2",
+    );
+    chain.update_client(&client_id, header);
+    assert_eq!(
+        chain.host.client_state(&client_id).unwrap().latest_height(),
+        solo_machine.height()
+    );
+
+    chain
+        .deliver(ChannelMsg::OpenInit(MsgChannelOpenInit {
+            port_id_on_a: port_id.clone(),
+            connection_hops_on_a: vec![connection_id.clone()],
+            port_id_on_b: port_id.clone(),
+            ordering: Order::Unordered,
+            signer: relayer(),
+            version_proposal: ChannelVersion::new("ics20-1".into()),
+        }))
+        .unwrap();
+    let channel_id = ChannelId::new(0);
+
+    // ChanOpenTry ran on the solo machine: the host's ChanOpenAck verifies
+    // its channel end in TRYOPEN.
+    let try_open_channel = ChannelEnd::new(
+        ChannelState::TryOpen,
+        Order::Unordered,
+        ChannelCounterparty::new(port_id.clone(), Some(channel_id.clone())),
+        vec![counterparty_connection_id.clone()],
+        ChannelVersion::new("ics20-1".into()),
+    )
+    .unwrap();
+    let proof_height = solo_machine.height();
+    let proof = solo_machine.prove(
+        ChannelEndPath::new(&port_id, &counterparty_channel_id),
+        try_open_channel.encode_vec(),
+    );
+    chain
+        .deliver(ChannelMsg::OpenAck(MsgChannelOpenAck {
+            port_id_on_a: port_id.clone(),
+            chan_id_on_a: channel_id.clone(),
+            chan_id_on_b: counterparty_channel_id.clone(),
+            version_on_b: ChannelVersion::new("ics20-1".into()),
+            proof_chan_end_on_b: proof,
+            proof_height_on_b: proof_height,
+            signer: relayer(),
+        }))
+        .unwrap();
+    let channel_end_path = ChannelEndPath::new(&port_id, &channel_id);
+    assert!(chain.host.channel_end(&channel_end_path).unwrap().is_open());
+
+    // ICS-20 transfer from the solo machine: the host's RecvPacket verifies
+    // the solo machine's packet commitment.
+    let timeout_height = Height::new(0, 1_000).unwrap();
+    let packet = Packet {
+        seq_on_a: Sequence::from(1),
+        port_id_on_a: port_id.clone(),
+        chan_id_on_a: counterparty_channel_id.clone(),
+        port_id_on_b: port_id.clone(),
+        chan_id_on_b: channel_id.clone(),
+        data: ics20_packet_data("solo", "cosmos1receiver"),
+        timeout_height_on_b: TimeoutHeight::At(timeout_height),
+        timeout_timestamp_on_b: Timestamp::none(),
+    };
+    let recv_packet = |solo_machine: &mut SoloMachine| MsgRecvPacket {
+        packet: packet.clone(),
+        proof_height_on_a: solo_machine.height(),
+        proof_commitment_on_a: solo_machine.prove(
+            CommitmentPath::new(&port_id, &counterparty_channel_id, packet.seq_on_a),
+            packet_commitment(&packet.data, timeout_height, 0),
+        ),
+        signer: relayer(),
+    };
+
+    // a proof height above the sequence the solo machine signs at
+    let mut msg = recv_packet(&mut solo_machine.clone());
+    msg.proof_height_on_a = solo_machine.height().increment();
+    assert!(matches!(
+        chain.deliver(PacketMsg::Recv(msg)),
+        Err(ContextError::ClientError(
+            ClientError::InvalidProofHeight { .. }
+        ))
+    ));
+
+    let msg = recv_packet(&mut solo_machine);
+    chain.deliver(PacketMsg::Recv(msg)).unwrap();
+    assert_eq!(chain.router.transfer.received, vec![packet.clone()]);
+    assert_eq!(
+        chain
+            .host
+            .get_packet_acknowledgement(&AckPath::new(&port_id, &channel_id, packet.seq_on_a))
+            .unwrap()
+            .into_vec(),
+        ack_commitment(SUCCESS_ACK)
+    );
+
+    // the same packet cannot be received twice
+    let msg = recv_packet(&mut solo_machine.clone());
+    assert!(matches!(
+        chain.deliver(PacketMsg::Recv(msg)),
+        Err(ContextError::PacketError(
+            PacketError::AcknowledgementExists { .. }
+        ))
+    ));
+
+    // ICS-20 transfer from the host: the solo machine writes a successful
+    // acknowledgement which the host's AcknowledgePacket verifies.
+    let packet = Packet {
+        seq_on_a: Sequence::from(1),
+        port_id_on_a: port_id.clone(),
+        chan_id_on_a: channel_id.clone(),
+        port_id_on_b: port_id.clone(),
+        chan_id_on_b: counterparty_channel_id.clone(),
+        data: ics20_packet_data("cosmos1sender", "solo"),
+        timeout_height_on_b: TimeoutHeight::At(timeout_height),
+        timeout_timestamp_on_b: Timestamp::none(),
+    };
+    send_packet(&mut chain.host, packet.clone()).unwrap();
+    let commitment_path = CommitmentPath::new(&port_id, &channel_id, packet.seq_on_a);
+    assert_eq!(
+        chain
+            .host
+            .get_packet_commitment(&commitment_path)
+            .unwrap()
+            .into_vec(),
+        packet_commitment(&packet.data, timeout_height, 0)
+    );
+
+    let acknowledge_packet =
+        |solo_machine: &mut SoloMachine, acknowledgement: &[u8]| MsgAcknowledgement {
+            packet: packet.clone(),
+            acknowledgement: Acknowledgement::try_from(acknowledgement.to_vec()).unwrap(),
+            proof_height_on_b: solo_machine.height(),
+            proof_acked_on_b: solo_machine.prove(
+                AckPath::new(&port_id, &counterparty_channel_id, packet.seq_on_a),
+                ack_commitment(SUCCESS_ACK),
+            ),
+            signer: relayer(),
+        };
+
+    // the relayer claims an acknowledgement the solo machine did not write
+    let msg = acknowledge_packet(&mut solo_machine.clone(), br#"{"error":"failed"}"#);
+    assert!(matches!(
+        chain.deliver(PacketMsg::Ack(msg)),
+        Err(ContextError::PacketError(PacketError::Channel(
+            ChannelError::PacketVerificationFailed { .. }
+        )))
+    ));
+
+    let msg = acknowledge_packet(&mut solo_machine, SUCCESS_ACK);
+    chain.deliver(PacketMsg::Ack(msg)).unwrap();
+    assert_eq!(chain.router.transfer.acknowledged.len(), 1);
+    assert!(chain.host.get_packet_commitment(&commitment_path).is_err());
+
+    // a second host packet is never received before its timeout height: once
+    // the solo machine moved past it, the host's TimeoutPacket verifies the
+    // absence of a receipt.
+    let timeout_height = solo_machine.height();
+    let packet = Packet {
+        seq_on_a: Sequence::from(2),
+        port_id_on_a: port_id.clone(),
+        chan_id_on_a: channel_id.clone(),
+        port_id_on_b: port_id.clone(),
+        chan_id_on_b: counterparty_channel_id.clone(),
+        data: ics20_packet_data("cosmos1sender", "solo"),
+        timeout_height_on_b: TimeoutHeight::At(timeout_height),
+        timeout_timestamp_on_b: Timestamp::none(),
+    };
+    send_packet(&mut chain.host, packet.clone()).unwrap();
+
+    let header = solo_machine.rotate(
+        SigningKey::from_secp256k1_bytes(&[3u8; 32]).unwrap(),
+        "solo-3",
+    );
+    chain.update_client(&client_id, header);
+
+    let timeout_packet = |solo_machine: &mut SoloMachine, receipt: Vec<u8>| MsgTimeout {
+        packet: packet.clone(),
+        next_seq_recv_on_b: Sequence::from(1),
+        proof_height_on_b: solo_machine.height(),
+        proof_unreceived_on_b: solo_machine.prove(
+            ReceiptPath::new(&port_id, &counterparty_channel_id, packet.seq_on_a),
+            receipt,
+        ),
+        signer: relayer(),
+    };
+
+    // a receipt the solo machine did write cannot be proven absent
+    let msg = timeout_packet(&mut solo_machine.clone(), vec![1]);
+    assert!(matches!(
+        chain.deliver(PacketMsg::Timeout(msg)),
+        Err(ContextError::PacketError(PacketError::Channel(
+            ChannelError::PacketVerificationFailed { .. }
+        )))
+    ));
+
+    let msg = timeout_packet(&mut solo_machine, vec![]);
+    chain.deliver(PacketMsg::Timeout(msg)).unwrap();
+    assert_eq!(chain.router.transfer.timed_out, vec![packet.clone()]);
+    assert!(chain
+        .host
+        .get_packet_commitment(&CommitmentPath::new(&port_id, &channel_id, packet.seq_on_a))
+        .is_err());
+
+    assert!(chain
+        .host
+        .events
+        .iter()
+        .any(|event| matches!(event, IbcEvent::OpenAckChannel(_))));
+    assert!(chain
+        .host
+        .events
+        .iter()
+        .any(|event| matches!(event, IbcEvent::TimeoutPacket(_))));
+}
+
+#[test]
+fn counterparty_initiated_handshake_then_close() {
+    let (mut solo_machine, mut chain, client_id) = setup();
+    chain.host.advance_block();
+
+    // identifiers on the solo machine side
+    let counterparty_client_id: ClientId = "07-tendermint-0".parse().unwrap();
+    let counterparty_connection_id = ConnectionId::new(7);
+    let counterparty_channel_id = ChannelId::new(7);
+    let port_id = PortId::transfer();
+    let version = ChannelVersion::new("ics20-1".into());
+
+    // ConnOpenInit ran on the solo machine: the host's ConnOpenTry verifies
+    // its connection end in INIT, and the host client and consensus state it
+    // claims to track at host height 1.
+    let consensus_height = Height::new(0, 1).unwrap();
+    let host_client_state = chain
+        .host
+        .self_client_state(HOST_CHAIN_ID, consensus_height);
+    let host_consensus_state = chain.host.host_consensus_state(&consensus_height).unwrap();
+    let connection_end = |state, connection_id| {
+        ConnectionEnd::new(
+            state,
+            counterparty_client_id.clone(),
+            ConnectionCounterparty::new(client_id.clone(), connection_id, prefix()),
+            vec![ConnectionVersion::default()],
+            Duration::ZERO,
+        )
+        .unwrap()
+    };
+    let client_state_path = ClientStatePath::new(&counterparty_client_id);
+    #[allow(deprecated)]
+    let conn_open_try = |solo_machine: &mut SoloMachine| {
+        let proofs_height_on_a = solo_machine.height();
+        MsgConnectionOpenTry {
+            client_id_on_b: client_id.clone(),
+            client_state_of_b_on_a: host_client_state.clone(),
+            counterparty: ConnectionCounterparty::new(
+                counterparty_client_id.clone(),
+                Some(counterparty_connection_id.clone()),
+                prefix(),
+            ),
+            versions_on_a: vec![ConnectionVersion::default()],
+            proof_conn_end_on_a: solo_machine.prove(
+                ConnectionPath::new(&counterparty_connection_id),
+                connection_end(ConnectionState::Init, None).encode_vec(),
+            ),
+            proof_client_state_of_b_on_a: solo_machine
+                .prove(client_state_path.clone(), host_client_state.encode_to_vec()),
+            proof_consensus_state_of_b_on_a: solo_machine.prove(
+                ClientConsensusStatePath::new(
+                    counterparty_client_id.clone(),
+                    consensus_height.revision_number(),
+                    consensus_height.revision_height(),
+                ),
+                host_consensus_state.clone().encode_vec(),
+            ),
+            proofs_height_on_a,
+            consensus_height_of_b_on_a: consensus_height,
+            delay_period: Duration::ZERO,
+            signer: relayer(),
+            proof_consensus_state_of_b: None,
+            previous_connection_id: String::new(),
+        }
+    };
+
+    // the client state proof signed at the sequence the connection end proof
+    // already consumed
+    let mut msg = conn_open_try(&mut solo_machine.clone());
+    msg.proof_client_state_of_b_on_a = solo_machine
+        .clone()
+        .prove(client_state_path.clone(), host_client_state.encode_to_vec());
+    assert!(matches!(
+        chain.deliver(ConnectionMsg::OpenTry(msg)),
+        Err(ContextError::ConnectionError(
+            ConnectionError::ClientStateVerificationFailure { .. }
+        ))
+    ));
+
+    let msg = conn_open_try(&mut solo_machine);
+    chain.deliver(ConnectionMsg::OpenTry(msg)).unwrap();
+    let connection_id = ConnectionId::new(0);
+    assert_eq!(
+        chain.host.connection_end(&connection_id).unwrap().state(),
+        &ConnectionState::TryOpen
+    );
+    assert_eq!(
+        chain.host.client_state(&client_id).unwrap().latest_height(),
+        Height::new(0, 4).unwrap()
+    );
+
+    // ConnOpenAck ran on the solo machine: the host's ConnOpenConfirm
+    // verifies its connection end in OPEN.
+    let conn_open_confirm = |solo_machine: &mut SoloMachine, state| MsgConnectionOpenConfirm {
+        conn_id_on_b: connection_id.clone(),
+        proof_height_on_a: solo_machine.height(),
+        proof_conn_end_on_a: solo_machine.prove(
+            ConnectionPath::new(&counterparty_connection_id),
+            connection_end(state, Some(connection_id.clone())).encode_vec(),
+        ),
+        signer: relayer(),
+    };
+
+    let msg = conn_open_confirm(&mut solo_machine.clone(), ConnectionState::TryOpen);
+    assert!(matches!(
+        chain.deliver(ConnectionMsg::OpenConfirm(msg)),
+        Err(ContextError::ConnectionError(
+            ConnectionError::VerifyConnectionState(_)
+        ))
+    ));
+
+    let msg = conn_open_confirm(&mut solo_machine, ConnectionState::Open);
+    chain.deliver(ConnectionMsg::OpenConfirm(msg)).unwrap();
+    assert_eq!(
+        chain.host.connection_end(&connection_id).unwrap().state(),
+        &ConnectionState::Open
+    );
+
+    // ChanOpenInit ran on the solo machine: the host's ChanOpenTry verifies
+    // its channel end in INIT.
+    let init_channel = ChannelEnd::new(
+        ChannelState::Init,
+        Order::Unordered,
+        ChannelCounterparty::new(port_id.clone(), None),
+        vec![counterparty_connection_id.clone()],
+        version.clone(),
+    )
+    .unwrap();
+    let proof_height = solo_machine.height();
+    let proof = solo_machine.prove(
+        ChannelEndPath::new(&port_id, &counterparty_channel_id),
+        init_channel.encode_vec(),
+    );
+    #[allow(deprecated)]
+    let msg = MsgChannelOpenTry {
+        port_id_on_b: port_id.clone(),
+        connection_hops_on_b: vec![connection_id.clone()],
+        port_id_on_a: port_id.clone(),
+        chan_id_on_a: counterparty_channel_id.clone(),
+        version_supported_on_a: version.clone(),
+        proof_chan_end_on_a: proof,
+        proof_height_on_a: proof_height,
+        ordering: Order::Unordered,
+        signer: relayer(),
+        version_proposal: version.clone(),
+    };
+    chain.deliver(ChannelMsg::OpenTry(msg)).unwrap();
+    let channel_id = ChannelId::new(0);
+    let channel_end_path = ChannelEndPath::new(&port_id, &channel_id);
+    assert_eq!(
+        chain.host.channel_end(&channel_end_path).unwrap().state(),
+        &ChannelState::TryOpen
+    );
+
+    // ChanOpenAck and ChanCloseInit ran on the solo machine: the host's
+    // ChanOpenConfirm and ChanCloseConfirm verify its channel end in OPEN and
+    // then in CLOSED.
+    let channel_end = |state| {
+        ChannelEnd::new(
+            state,
+            Order::Unordered,
+            ChannelCounterparty::new(port_id.clone(), Some(channel_id.clone())),
+            vec![counterparty_connection_id.clone()],
+            version.clone(),
+        )
+        .unwrap()
+    };
+    let proof_height = solo_machine.height();
+    let proof = solo_machine.prove(
+        ChannelEndPath::new(&port_id, &counterparty_channel_id),
+        channel_end(ChannelState::Open).encode_vec(),
+    );
+    chain
+        .deliver(ChannelMsg::OpenConfirm(MsgChannelOpenConfirm {
+            port_id_on_b: port_id.clone(),
+            chan_id_on_b: channel_id.clone(),
+            proof_chan_end_on_a: proof,
+            proof_height_on_a: proof_height,
+            signer: relayer(),
+        }))
+        .unwrap();
+    assert!(chain.host.channel_end(&channel_end_path).unwrap().is_open());
+
+    let chan_close_confirm = |solo_machine: &mut SoloMachine, state| MsgChannelCloseConfirm {
+        port_id_on_b: port_id.clone(),
+        chan_id_on_b: channel_id.clone(),
+        proof_height_on_a: solo_machine.height(),
+        proof_chan_end_on_a: solo_machine.prove(
+            ChannelEndPath::new(&port_id, &counterparty_channel_id),
+            channel_end(state).encode_vec(),
+        ),
+        signer: relayer(),
+    };
+
+    // a proof over the channel end the solo machine has not closed
+    let msg = chan_close_confirm(&mut solo_machine.clone(), ChannelState::Open);
+    assert!(matches!(
+        chain.deliver(ChannelMsg::CloseConfirm(msg)),
+        Err(ContextError::ChannelError(
+            ChannelError::VerifyChannelFailed(_)
+        ))
+    ));
+
+    let msg = chan_close_confirm(&mut solo_machine, ChannelState::Closed);
+    chain.deliver(ChannelMsg::CloseConfirm(msg)).unwrap();
+    assert_eq!(
+        chain.host.channel_end(&channel_end_path).unwrap().state(),
+        &ChannelState::Closed
+    );
+    assert_eq!(
+        chain.host.client_state(&client_id).unwrap().latest_height(),
+        solo_machine.height()
+    );
+
+    for expected in [
+        |event: &IbcEvent| matches!(event, IbcEvent::OpenTryConnection(_)),
+        |event: &IbcEvent| matches!(event, IbcEvent::OpenConfirmConnection(_)),
+        |event: &IbcEvent| matches!(event, IbcEvent::OpenTryChannel(_)),
+        |event: &IbcEvent| matches!(event, IbcEvent::OpenConfirmChannel(_)),
+        |event: &IbcEvent| matches!(event, IbcEvent::CloseConfirmChannel(_)),
+    ] {
+        assert!(chain.host.events.iter().any(expected));
+    }
+}
@@ -0,0 +1,119 @@
+//! Creating, updating and freezing a client through the ibc-core client
+//! traits.
+
+mod common;
+
+use common::*;
+use core::time::Duration;
+use ibc_client_solomachine::mock::MockContext;
+use ibc_client_solomachine::types::Misbehaviour;
+use ibc_core::client::context::client_state::{
+    ClientStateCommon, ClientStateExecution, ClientStateValidation,
+};
+use ibc_core::client::context::ClientValidationContext;
+use ibc_core::client::types::{Height, Status, UpdateKind};
+use ibc_proto::google::protobuf::Any;
+
+#[test]
+fn create_update_and_freeze_client() {
+    let key_one = ed25519_key(1);
+    let key_two = secp256k1_key(2);
+    let client_id = client_id();
+    let mut ctx = MockContext::new(timestamp(100));
+
+    let consensus_state = consensus_state(&key_one, "one", 10);
+    client_state(1, consensus_state.clone())
+        .initialise(&mut ctx, &client_id, Any::from(consensus_state))
+        .unwrap();
+    let height = Height::new(0, 1).unwrap();
+    assert!(ctx.stored_consensus_state(&client_id, &height).is_some());
+    assert_eq!(
+        ctx.client_update_time(&client_id, &height).unwrap(),
+        timestamp(100)
+    );
+    assert_eq!(
+        ctx.client_update_height(&client_id, &height).unwrap(),
+        Height::min(0)
+    );
+
+    // rotate to the second key with a header signed by the first one
+    let header = header(&key_one, 1, "one", 20, &key_two, "two");
+    let client_state = ctx.client_state(&client_id).unwrap().clone();
+    client_state
+        .verify_client_message(
+            &ctx,
+            &client_id,
+            Any::from(header.clone()),
+            &UpdateKind::UpdateClient,
+        )
+        .unwrap();
+    ctx.advance_host_time(Duration::from_nanos(50));
+    let heights = client_state
+        .update_state(&mut ctx, &client_id, Any::from(header))
+        .unwrap();
+    let height = Height::new(0, 2).unwrap();
+    assert_eq!(heights, vec![height]);
+
+    // the new consensus state is stored at the new sequence with the host
+    // time and height it was stored at
+    assert_eq!(
+        ctx.stored_consensus_state(&client_id, &height)
+            .unwrap()
+            .inner()
+            .public_key,
+        key_two.public_key().unwrap()
+    );
+    assert_eq!(
+        ctx.client_update_time(&client_id, &height).unwrap(),
+        timestamp(150)
+    );
+    assert_eq!(
+        ctx.client_update_height(&client_id, &height).unwrap(),
+        Height::new(0, 2).unwrap()
+    );
+
+    let client_state = ctx.client_state(&client_id).unwrap().clone();
+    assert_eq!(client_state.latest_height(), Height::new(0, 2).unwrap());
+    assert_eq!(
+        client_state.inner().consensus_state.public_key,
+        key_two.public_key().unwrap()
+    );
+
+    // two different messages signed at the same sequence freeze the client
+    let misbehaviour = Misbehaviour {
+        sequence: Height::new(0, 2).unwrap(),
+        signature_one: signature_and_data(&key_two, 2, "two", 30, b"one"),
+        signature_two: signature_and_data(&key_two, 2, "two", 30, b"two"),
+    };
+    client_state
+        .verify_client_message(
+            &ctx,
+            &client_id,
+            Any::from(misbehaviour.clone()),
+            &UpdateKind::SubmitMisbehaviour,
+        )
+        .unwrap();
+    assert!(client_state
+        .check_for_misbehaviour(
+            &ctx,
+            &client_id,
+            Any::from(misbehaviour.clone()),
+            &UpdateKind::SubmitMisbehaviour,
+        )
+        .unwrap());
+    client_state
+        .update_state_on_misbehaviour(
+            &mut ctx,
+            &client_id,
+            Any::from(misbehaviour),
+            &UpdateKind::SubmitMisbehaviour,
+        )
+        .unwrap();
+
+    let client_state = ctx.client_state(&client_id).unwrap().clone();
+    assert!(client_state.inner().is_frozen);
+    assert_eq!(
+        client_state.status(&ctx, &client_id).unwrap(),
+        Status::Frozen
+    );
+}
@@ -0,0 +1,100 @@
+//! Size limits on the inputs of a verification, taken from the host context.
+
+mod common;
+
+use common::*;
+use ibc_client_solomachine::mock::MockContext;
+use ibc_client_solomachine::types::{Header, VerificationLimits};
+use ibc_core::client::context::client_state::{ClientStateCommon, ClientStateValidation};
+use ibc_core::client::types::error::ClientError;
+use ibc_core::client::types::UpdateKind;
+use ibc_core::commitment_types::merkle::apply_prefix;
+use ibc_proto::google::protobuf::Any;
+
+fn assert_too_large(result: Result<(), ClientError>, field: &str) {
+    let description = result.unwrap_err().to_string();
+    assert!(
+        description.contains(&format!("{} size", field)),
+        "{}",
+        description
+    );
+}
+
+#[test]
+fn oversized_inputs_are_rejected() {
+    let key = ed25519_key(1);
+    let client_id = client_id();
+    let mut ctx = MockContext::new(timestamp(100));
+    ctx.set_verification_limits(VerificationLimits {
+        max_data_size: 16,
+        max_signature_size: 128,
+        ..VerificationLimits::default()
+    });
+    let client_state = client_state(1, consensus_state(&key, "one", 10));
+    let merkle_path = apply_prefix(&prefix(), vec![connection_path().to_string()]);
+
+    // the value is rejected before the proof is decoded
+    assert_too_large(
+        client_state.verify_signed_path(
+            &ctx,
+            height(1),
+            &vec![1u8].try_into().unwrap(),
+            &merkle_path,
+            vec![0; 17],
+        ),
+        "data",
+    );
+    client_state
+        .verify_signed_path(
+            &ctx,
+            height(1),
+            &proof(&key, 1, "one", 20, &[0; 16]),
+            &merkle_path,
+            vec![0; 16],
+        )
+        .unwrap();
+
+    let header = Header {
+        timestamp: timestamp(20),
+        signature: vec![1; 129],
+        new_public_key: key.public_key().unwrap(),
+        new_diversifier: "two".into(),
+    };
+    assert_too_large(
+        client_state.verify_client_message(
+            &ctx,
+            &client_id,
+            Any::from(header),
+            &UpdateKind::UpdateClient,
+        ),
+        "signature",
+    );
+}
+
+#[test]
+fn proofs_verified_without_a_host_use_the_default_limits() {
+    let key = ed25519_key(1);
+    let client_state = client_state(1, consensus_state(&key, "one", 10));
+    let max_data_size = VerificationLimits::default().max_data_size;
+    let value = vec![0; max_data_size];
+
+    client_state
+        .verify_membership(
+            &prefix(),
+            &proof(&key, 1, "one", 20, &value),
+            &root(),
+            connection_path(),
+            value.clone(),
+        )
+        .unwrap();
+    assert_too_large(
+        client_state.verify_membership(
+            &prefix(),
+            &vec![1u8].try_into().unwrap(),
+            &root(),
+            connection_path(),
+            vec![0; max_data_size + 1],
+        ),
+        "data",
+    );
+}
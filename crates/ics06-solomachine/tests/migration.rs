@@ -0,0 +1,50 @@
+//! Migrating clients stored in the ibc-go v2 solo machine encoding.
+
+mod common;
+
+use common::*;
+use ibc_client_solomachine::client_state::ClientState;
+use ibc_client_solomachine::context::CommonContext;
+use ibc_client_solomachine::mock::MockContext;
+use ibc_client_solomachine::types::v2;
+use ibc_core::client::context::client_state::ClientStateValidation;
+use ibc_core::client::types::{Height, Status};
+use ibc_core::host::types::path::ClientConsensusStatePath;
+use ibc_proto::google::protobuf::Any;
+
+#[test]
+fn v2_client_is_migrated_in_place() {
+    let key = ed25519_key(1);
+    let client_id = client_id();
+    let mut ctx = MockContext::new(timestamp(100));
+    let stored = v2::ClientState {
+        sequence: Height::new(0, 3).unwrap(),
+        is_frozen: false,
+        consensus_state: v2::ConsensusState {
+            public_key: key.public_key().unwrap(),
+            diversifier: "one".into(),
+            timestamp: timestamp(50),
+        },
+        allow_update_after_proposal: true,
+    };
+
+    let client_state =
+        ClientState::migrate(&mut ctx, &client_id, Any::from(stored.clone())).unwrap();
+    assert_eq!(ctx.client_state(&client_id), Some(&client_state));
+    assert!(client_state.inner().allow_update_after_proposal);
+    assert_eq!(v2::ClientState::from(client_state.inner().clone()), stored);
+    assert_eq!(
+        client_state.status(&ctx, &client_id).unwrap(),
+        Status::Active
+    );
+
+    // as in ibc-go, the consensus state lives in the client state only
+    assert!(ctx
+        .consensus_state(&ClientConsensusStatePath::new(client_id.clone(), 0, 3))
+        .is_err());
+
+    // an already migrated client is returned as it is
+    let migrated =
+        ClientState::migrate(&mut ctx, &client_id, Any::from(client_state.clone())).unwrap();
+    assert_eq!(migrated, client_state);
+}
@@ -0,0 +1,78 @@
+//! Proof heights carry the sequence the solo machine signed the proof at.
+
+mod common;
+
+use common::*;
+use ibc_client_solomachine::mock::MockContext;
+use ibc_core::client::context::client_state::ClientStateCommon;
+use ibc_core::client::types::error::ClientError;
+use ibc_core::client::types::Height;
+use ibc_core::commitment_types::merkle::apply_prefix;
+
+fn assert_invalid_proof_height(result: Result<(), ClientError>, proof_height: Height) {
+    match result {
+        Err(ClientError::InvalidProofHeight {
+            latest_height,
+            proof_height: got,
+        }) => {
+            assert_eq!(latest_height, height(5));
+            assert_eq!(got, proof_height);
+        }
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn proof_height_may_not_be_above_the_client_sequence() {
+    let key = ed25519_key(1);
+    let client_state = client_state(5, consensus_state(&key, "one", 10));
+
+    // as for chain clients, heights up to the latest height are valid
+    for proof_height in [height(4), height(5)] {
+        client_state.validate_proof_height(proof_height).unwrap();
+    }
+    for proof_height in [height(6), Height::new(1, 5).unwrap()] {
+        assert_invalid_proof_height(
+            client_state.validate_proof_height(proof_height),
+            proof_height,
+        );
+    }
+}
+
+#[test]
+fn proofs_must_be_signed_at_the_client_sequence() {
+    let key = ed25519_key(1);
+    let ctx = MockContext::new(timestamp(100));
+    let client_state = client_state(5, consensus_state(&key, "one", 10));
+    let merkle_path = apply_prefix(&prefix(), vec![connection_path().to_string()]);
+    let verify = |proof_height: Height, sequence: u64| {
+        client_state.verify_signed_path(
+            &ctx,
+            proof_height,
+            &proof(&key, sequence, "one", 20, b"connection"),
+            &merkle_path,
+            b"connection".to_vec(),
+        )
+    };
+
+    verify(height(5), 5).unwrap();
+
+    // a proof one sequence behind the client passes `validate_proof_height`
+    // but the solo machine no longer signs at that sequence
+    client_state.validate_proof_height(height(4)).unwrap();
+    assert_invalid_proof_height(verify(height(4), 4), height(4));
+    assert!(verify(height(5), 4).is_err());
+    assert!(client_state
+        .verify_membership(
+            &prefix(),
+            &proof(&key, 4, "one", 20, b"connection"),
+            &root(),
+            connection_path(),
+            b"connection".to_vec(),
+        )
+        .is_err());
+
+    for proof_height in [height(6), Height::new(1, 5).unwrap()] {
+        assert_invalid_proof_height(verify(proof_height, 5), proof_height);
+    }
+}
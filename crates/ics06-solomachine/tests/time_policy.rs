@@ -0,0 +1,107 @@
+//! Bounding header and proof timestamps against the host clock.
+
+mod common;
+
+use common::*;
+use ibc_client_solomachine::mock::MockContext;
+use ibc_client_solomachine::types::TimePolicy;
+use ibc_core::client::context::client_state::{
+    ClientStateCommon, ClientStateExecution, ClientStateValidation,
+};
+use ibc_core::client::types::error::ClientError;
+use ibc_core::client::types::UpdateKind;
+use ibc_core::commitment_types::merkle::apply_prefix;
+use ibc_proto::google::protobuf::Any;
+
+fn context() -> MockContext {
+    let mut ctx = MockContext::new(timestamp(100));
+    ctx.set_time_policy(TimePolicy {
+        max_clock_drift: Some(core::time::Duration::from_nanos(10)),
+        max_proof_age: Some(core::time::Duration::from_nanos(60)),
+    });
+    ctx
+}
+
+#[test]
+fn time_policy_bounds_headers() {
+    let key = ed25519_key(1);
+    let client_id = client_id();
+    let ctx = context();
+    let client_state = client_state(1, consensus_state(&key, "one", 10));
+
+    // headers may be at most 10ns ahead of the host
+    let verify_header = |nanoseconds: u64| {
+        client_state.verify_client_message(
+            &ctx,
+            &client_id,
+            Any::from(header(&key, 1, "one", nanoseconds, &key, "two")),
+            &UpdateKind::UpdateClient,
+        )
+    };
+    verify_header(110).unwrap();
+    match verify_header(111) {
+        Err(ClientError::HeaderVerificationFailure { reason }) => {
+            assert!(
+                reason.contains("header timestamp") && reason.contains("ahead of the host"),
+                "{}",
+                reason
+            )
+        }
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn time_policy_bounds_proofs_of_stored_clients() {
+    let key = ed25519_key(1);
+    let client_id = client_id();
+    let mut ctx = context();
+    let consensus_state = consensus_state(&key, "one", 10);
+    client_state(1, consensus_state.clone())
+        .initialise(&mut ctx, &client_id, Any::from(consensus_state))
+        .unwrap();
+
+    // the policy is taken from the host, so it applies to the client state as
+    // loaded from the store
+    let client_state = ctx.client_state(&client_id).unwrap().clone();
+    let merkle_path = apply_prefix(&prefix(), vec![connection_path().to_string()]);
+    let verify = |ctx: &MockContext, nanoseconds: u64| {
+        client_state.verify_signed_path(
+            ctx,
+            height(1),
+            &proof(&key, 1, "one", nanoseconds, &[]),
+            &merkle_path,
+            vec![],
+        )
+    };
+
+    // proofs may be at most 60ns behind the host
+    verify(&ctx, 50).unwrap();
+    verify(&ctx, 110).unwrap();
+    assert!(verify(&ctx, 39)
+        .unwrap_err()
+        .to_string()
+        .contains("behind the host timestamp"));
+    assert!(verify(&ctx, 111)
+        .unwrap_err()
+        .to_string()
+        .contains("ahead of the host timestamp"));
+
+    // the host time is read when the proof is verified
+    ctx.advance_host_time(core::time::Duration::from_nanos(20));
+    verify(&ctx, 111).unwrap();
+    assert!(verify(&ctx, 50)
+        .unwrap_err()
+        .to_string()
+        .contains("behind the host timestamp"));
+
+    // ibc-core verifies proofs without the host, so they are not bounded
+    client_state
+        .verify_non_membership(
+            &prefix(),
+            &proof(&key, 1, "one", 50, &[]),
+            &root(),
+            connection_path(),
+        )
+        .unwrap();
+}
@@ -0,0 +1,142 @@
+//! Basic validation of client states, consensus states and client messages
+//! before they reach the signature checks.
+
+mod common;
+
+use common::*;
+use ibc_client_solomachine::client_state::ClientState;
+use ibc_client_solomachine::mock::MockContext;
+use ibc_client_solomachine::types::proof::types::signature_and_data::SignatureAndData;
+use ibc_client_solomachine::types::{
+    ClientState as ClientStateType, ConsensusState as ConsensusStateType, Header, Misbehaviour,
+};
+use ibc_core::client::context::client_state::{ClientStateExecution, ClientStateValidation};
+use ibc_core::client::types::error::ClientError;
+use ibc_core::client::types::{Height, UpdateKind};
+use ibc_core::primitives::Timestamp;
+use ibc_proto::google::protobuf::Any;
+
+#[test]
+fn initialise_validates_client_and_consensus_state() {
+    let key = ed25519_key(1);
+    let client_id = client_id();
+    let initialise = |client: ConsensusStateType, consensus: ConsensusStateType| {
+        let mut ctx = MockContext::new(timestamp(100));
+        let client_state = ClientState::from(ClientStateType::new(
+            Height::new(0, 1).unwrap(),
+            false,
+            client,
+        ));
+        let result = client_state.initialise(&mut ctx, &client_id, Any::from(consensus));
+        (result, ctx.client_state(&client_id).is_some())
+    };
+
+    let (result, stored) = initialise(
+        consensus_state(&key, "one", 10),
+        consensus_state(&key, "one", 10),
+    );
+    result.unwrap();
+    assert!(stored);
+
+    // zero timestamp
+    let zero_timestamp = ConsensusStateType {
+        timestamp: Timestamp::none(),
+        ..consensus_state(&key, "one", 10)
+    };
+    let (result, stored) = initialise(zero_timestamp.clone(), zero_timestamp);
+    assert!(result.is_err());
+    assert!(!stored);
+
+    // diversifier of only whitespace
+    let (result, stored) = initialise(
+        consensus_state(&key, "  ", 10),
+        consensus_state(&key, "  ", 10),
+    );
+    assert!(result.is_err());
+    assert!(!stored);
+
+    // consensus state differing from the client state one
+    let (result, stored) = initialise(
+        consensus_state(&key, "one", 10),
+        consensus_state(&key, "two", 10),
+    );
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("consensus state does not match"));
+    assert!(!stored);
+}
+
+#[test]
+fn client_messages_are_validated() {
+    let key = ed25519_key(1);
+    let client_id = client_id();
+    let ctx = MockContext::new(timestamp(100));
+    let client_state = client_state(1, consensus_state(&key, "one", 10));
+    let header = Header {
+        timestamp: timestamp(20),
+        signature: vec![1; 64],
+        new_public_key: key.public_key().unwrap(),
+        new_diversifier: "two".into(),
+    };
+    let assert_invalid_header = |header: Header, expected: &str| match client_state
+        .verify_client_message(
+            &ctx,
+            &client_id,
+            Any::from(header),
+            &UpdateKind::UpdateClient,
+        ) {
+        Err(ClientError::HeaderVerificationFailure { reason }) => {
+            assert!(reason.contains(expected), "{}", reason)
+        }
+        other => panic!("unexpected result {:?}", other),
+    };
+
+    assert_invalid_header(
+        Header {
+            timestamp: Timestamp::none(),
+            ..header.clone()
+        },
+        "timestamp cannot be 0",
+    );
+    assert_invalid_header(
+        Header {
+            signature: vec![],
+            ..header.clone()
+        },
+        "signature cannot be empty",
+    );
+    assert_invalid_header(
+        Header {
+            new_diversifier: " ".into(),
+            ..header
+        },
+        "diversifier cannot contain only spaces",
+    );
+
+    let signature_and_data = SignatureAndData {
+        signature: vec![1; 64],
+        path: CONNECTION_PATH.to_vec(),
+        data: b"one".to_vec(),
+        timestamp: timestamp(30),
+    };
+    let misbehaviour = Misbehaviour {
+        sequence: Height::new(0, 1).unwrap(),
+        signature_one: signature_and_data.clone(),
+        signature_two: SignatureAndData {
+            signature: vec![2; 64],
+            ..signature_and_data
+        },
+    };
+    match client_state.verify_client_message(
+        &ctx,
+        &client_id,
+        Any::from(misbehaviour),
+        &UpdateKind::SubmitMisbehaviour,
+    ) {
+        Err(ClientError::MisbehaviourHandlingFailure { reason }) => {
+            assert!(reason.contains("different messages"), "{}", reason)
+        }
+        other => panic!("unexpected result {:?}", other),
+    }
+}
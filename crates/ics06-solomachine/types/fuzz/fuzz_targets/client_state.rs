@@ -0,0 +1,22 @@
+#![no_main]
+
+use ibc_client_solomachine_types::{decode_client_state, encode_client_state, ClientState};
+use ibc_proto::google::protobuf::Any;
+use libfuzzer_sys::fuzz_target;
+use prost::Message;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(client_state) = decode_client_state(data) {
+        let _ = client_state.validate_basic();
+        let encoded = encode_client_state(client_state.clone());
+        let decoded = decode_client_state(&encoded).unwrap();
+        assert!(decoded == client_state);
+    }
+
+    if let Ok(any) = Any::decode(data) {
+        if let Ok(client_state) = ClientState::try_from(any) {
+            let decoded = ClientState::try_from(Any::from(client_state.clone())).unwrap();
+            assert!(decoded == client_state);
+        }
+    }
+});
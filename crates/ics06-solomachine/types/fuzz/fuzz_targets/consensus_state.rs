@@ -0,0 +1,26 @@
+#![no_main]
+
+use ibc_client_solomachine_types::ConsensusState;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::lightclients::solomachine::v3::ConsensusState as RawSmConsensusState;
+use ibc_proto::Protobuf;
+use libfuzzer_sys::fuzz_target;
+use prost::Message;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(consensus_state) = <ConsensusState as Protobuf<RawSmConsensusState>>::decode_vec(data)
+    {
+        let _ = consensus_state.validate_basic();
+        let encoded = Protobuf::<RawSmConsensusState>::encode_vec(consensus_state.clone());
+        let decoded =
+            <ConsensusState as Protobuf<RawSmConsensusState>>::decode_vec(&encoded).unwrap();
+        assert!(decoded == consensus_state);
+    }
+
+    if let Ok(any) = Any::decode(data) {
+        if let Ok(consensus_state) = ConsensusState::try_from(any) {
+            let decoded = ConsensusState::try_from(Any::from(consensus_state.clone())).unwrap();
+            assert!(decoded == consensus_state);
+        }
+    }
+});
@@ -0,0 +1,38 @@
+#![no_main]
+
+use ibc_client_solomachine_types::proof::types::header_data::HeaderData;
+use ibc_client_solomachine_types::Header;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::lightclients::solomachine::v3::Header as RawSmHeader;
+use ibc_proto::Protobuf;
+use libfuzzer_sys::fuzz_target;
+use prost::Message;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(header) = <Header as Protobuf<RawSmHeader>>::decode_vec(data) {
+        let _ = header.validate_basic();
+        let _ = header.to_string();
+        let encoded = Protobuf::<RawSmHeader>::encode_vec(header.clone());
+        let decoded = <Header as Protobuf<RawSmHeader>>::decode_vec(&encoded).unwrap();
+        assert!(decoded == header);
+
+        let header_data = HeaderData {
+            new_pub_key: header.new_public_key,
+            new_diversifier: header.new_diversifier,
+        };
+        let decoded = HeaderData::decode_vec(&header_data.clone().encode_vec()).unwrap();
+        assert!(decoded == header_data);
+    }
+
+    if let Ok(header_data) = HeaderData::decode_vec(data) {
+        let decoded = HeaderData::decode_vec(&header_data.clone().encode_vec()).unwrap();
+        assert!(decoded == header_data);
+    }
+
+    if let Ok(any) = Any::decode(data) {
+        if let Ok(header) = Header::try_from(any) {
+            let decoded = Header::try_from(Any::from(header.clone())).unwrap();
+            assert!(decoded == header);
+        }
+    }
+});
@@ -0,0 +1,25 @@
+#![no_main]
+
+use ibc_client_solomachine_types::Misbehaviour;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::lightclients::solomachine::v3::Misbehaviour as RawSmMisbehaviour;
+use ibc_proto::Protobuf;
+use libfuzzer_sys::fuzz_target;
+use prost::Message;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(misbehaviour) = <Misbehaviour as Protobuf<RawSmMisbehaviour>>::decode_vec(data) {
+        let _ = misbehaviour.validate_basic();
+        let _ = misbehaviour.to_string();
+        let encoded = Protobuf::<RawSmMisbehaviour>::encode_vec(misbehaviour.clone());
+        let decoded = <Misbehaviour as Protobuf<RawSmMisbehaviour>>::decode_vec(&encoded).unwrap();
+        assert!(decoded == misbehaviour);
+    }
+
+    if let Ok(any) = Any::decode(data) {
+        if let Ok(misbehaviour) = Misbehaviour::try_from(any) {
+            let decoded = Misbehaviour::try_from(Any::from(misbehaviour.clone())).unwrap();
+            assert!(decoded == misbehaviour);
+        }
+    }
+});
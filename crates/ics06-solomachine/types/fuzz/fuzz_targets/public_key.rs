@@ -0,0 +1,51 @@
+#![no_main]
+
+use core::str::FromStr;
+use ibc_client_solomachine_types::cosmos::crypto::{Algorithm, PublicKey};
+use ibc_proto::google::protobuf::Any;
+use libfuzzer_sys::fuzz_target;
+use prost::Message;
+
+fn round_trip(public_key: PublicKey) {
+    assert_eq!(
+        PublicKey::try_from(public_key.to_any()).unwrap(),
+        public_key
+    );
+    assert_eq!(
+        PublicKey::from_json(&public_key.try_to_json().unwrap()).unwrap(),
+        public_key
+    );
+    assert_eq!(
+        PublicKey::from_bech32(&public_key.to_bech32("cosmospub").unwrap()).unwrap(),
+        public_key
+    );
+    let _ = public_key.account_id("cosmos");
+}
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(any) = Any::decode(data) {
+        if let Ok(public_key) = PublicKey::try_from(&any) {
+            round_trip(public_key);
+        }
+    }
+
+    for algorithm in [Algorithm::Ed25519, Algorithm::Secp256k1] {
+        if let Ok(public_key) = PublicKey::from_raw(algorithm, data) {
+            round_trip(public_key);
+        }
+    }
+
+    if let Ok(s) = core::str::from_utf8(data) {
+        if let Ok(public_key) = PublicKey::from_str(s) {
+            round_trip(public_key);
+        }
+        for algorithm in [Algorithm::Ed25519, Algorithm::Secp256k1] {
+            if let Ok(public_key) = PublicKey::from_hex(algorithm, s) {
+                round_trip(public_key);
+            }
+            if let Ok(public_key) = PublicKey::from_base64(algorithm, s) {
+                round_trip(public_key);
+            }
+        }
+    }
+});
@@ -0,0 +1,12 @@
+#![no_main]
+
+use ibc_client_solomachine_types::proof::types::sign_bytes::SignBytes;
+use ibc_proto::Protobuf;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(sign_bytes) = SignBytes::decode_vec(data) {
+        let decoded = SignBytes::decode_vec(&sign_bytes.clone().encode_vec()).unwrap();
+        assert!(decoded == sign_bytes);
+    }
+});
@@ -0,0 +1,17 @@
+#![no_main]
+
+use ibc_client_solomachine_types::proof::decode_signature_data;
+use ibc_client_solomachine_types::proof::types::signature_and_data::SignatureAndData;
+use ibc_proto::Protobuf;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(signature_and_data) = SignatureAndData::decode_vec(data) {
+        let _ = signature_and_data.validate_basic();
+        let _ = signature_and_data.to_string();
+        let _ = decode_signature_data(&signature_and_data.signature);
+        let decoded =
+            SignatureAndData::decode_vec(&signature_and_data.clone().encode_vec()).unwrap();
+        assert!(decoded == signature_and_data);
+    }
+});
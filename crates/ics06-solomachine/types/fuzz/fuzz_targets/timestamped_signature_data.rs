@@ -0,0 +1,14 @@
+#![no_main]
+
+use ibc_client_solomachine_types::proof::decode_signature_data;
+use ibc_client_solomachine_types::proof::types::timestamped_signature_data::TimestampedSignatureData;
+use ibc_proto::Protobuf;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(proof) = TimestampedSignatureData::decode_vec(data) {
+        let _ = decode_signature_data(&proof.signature_data);
+        let decoded = TimestampedSignatureData::decode_vec(&proof.clone().encode_vec()).unwrap();
+        assert!(decoded == proof);
+    }
+});
@@ -0,0 +1,324 @@
+//! Candid representations of the solo machine domain types, for hosts running
+//! inside Internet Computer canisters.
+//!
+//! The domain types keep their serde derives for JSON, and their ibc-core
+//! fields cannot implement `CandidType` here, so each type has a Candid record
+//! shaped after its protobuf message, with lossless conversions both ways.
+
+use crate::client_state::ClientState;
+use crate::consensus_state::ConsensusState;
+use crate::cosmos::crypto::public_key::{ED25519_TYPE_URL, SECP256K1_TYPE_URL};
+use crate::cosmos::crypto::{Algorithm, PublicKey};
+use crate::cosmos::error::Error as CosmosError;
+use crate::error::Error;
+use crate::header::Header;
+use crate::misbehaviour::Misbehaviour;
+use crate::proof::types::signature_and_data::SignatureAndData;
+use candid::CandidType;
+use core::time::Duration;
+use ibc_core::client::types::Height;
+use ibc_core::primitives::prelude::*;
+use ibc_core::primitives::Timestamp;
+use serde::Deserialize;
+
+/// Candid record of a [`PublicKey`], its Cosmos JSON fields with the key as
+/// raw bytes.
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CandidPublicKey {
+    pub type_url: String,
+    pub key: Vec<u8>,
+}
+
+/// Candid record of a [`Duration`].
+#[derive(CandidType, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CandidDuration {
+    pub secs: u64,
+    pub nanos: u32,
+}
+
+/// Candid record of a [`ConsensusState`]. The commitment root is derived from
+/// the public key and is not carried.
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CandidConsensusState {
+    pub public_key: CandidPublicKey,
+    pub diversifier: String,
+    /// nanoseconds since the Unix epoch
+    pub timestamp: u64,
+}
+
+/// Candid record of a [`ClientState`]. Runtime configuration is not carried.
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CandidClientState {
+    pub sequence: u64,
+    pub is_frozen: bool,
+    pub consensus_state: CandidConsensusState,
+    pub inactivity_period: Option<CandidDuration>,
+    pub allow_update_after_proposal: bool,
+}
+
+/// Candid record of a [`Header`].
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CandidHeader {
+    /// nanoseconds since the Unix epoch
+    pub timestamp: u64,
+    pub signature: Vec<u8>,
+    pub new_public_key: CandidPublicKey,
+    pub new_diversifier: String,
+}
+
+/// Candid record of a [`SignatureAndData`].
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CandidSignatureAndData {
+    pub signature: Vec<u8>,
+    pub path: Vec<u8>,
+    pub data: Vec<u8>,
+    /// nanoseconds since the Unix epoch
+    pub timestamp: u64,
+}
+
+/// Candid record of a [`Misbehaviour`].
+#[derive(CandidType, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CandidMisbehaviour {
+    pub sequence: u64,
+    pub signature_one: CandidSignatureAndData,
+    pub signature_two: CandidSignatureAndData,
+}
+
+fn timestamp(nanoseconds: u64) -> Result<Timestamp, Error> {
+    Timestamp::from_nanoseconds(nanoseconds).map_err(Error::ParseTimeError)
+}
+
+fn sequence(sequence: u64) -> Result<Height, Error> {
+    Height::new(0, sequence).map_err(Error::InvalidHeight)
+}
+
+impl From<&PublicKey> for CandidPublicKey {
+    fn from(value: &PublicKey) -> Self {
+        Self {
+            type_url: value.type_url().to_string(),
+            key: value.to_bytes(),
+        }
+    }
+}
+
+impl TryFrom<CandidPublicKey> for PublicKey {
+    type Error = Error;
+
+    fn try_from(value: CandidPublicKey) -> Result<Self, Self::Error> {
+        let algorithm = match value.type_url.as_str() {
+            ED25519_TYPE_URL => Algorithm::Ed25519,
+            SECP256K1_TYPE_URL => Algorithm::Secp256k1,
+            _ => {
+                return Err(Error::PublicKeyParseFailed(
+                    CosmosError::UnsupportedPublicKey {
+                        key_type: value.type_url,
+                    },
+                ))
+            }
+        };
+        PublicKey::from_raw(algorithm, &value.key).map_err(Error::PublicKeyParseFailed)
+    }
+}
+
+impl From<Duration> for CandidDuration {
+    fn from(value: Duration) -> Self {
+        Self {
+            secs: value.as_secs(),
+            nanos: value.subsec_nanos(),
+        }
+    }
+}
+
+impl TryFrom<CandidDuration> for Duration {
+    type Error = Error;
+
+    fn try_from(value: CandidDuration) -> Result<Self, Self::Error> {
+        if value.nanos >= 1_000_000_000 {
+            return Err(Error::Other(format!(
+                "invalid duration nanoseconds: {}",
+                value.nanos
+            )));
+        }
+        Ok(Duration::new(value.secs, value.nanos))
+    }
+}
+
+impl From<&ConsensusState> for CandidConsensusState {
+    fn from(value: &ConsensusState) -> Self {
+        Self {
+            public_key: (&value.public_key).into(),
+            diversifier: value.diversifier.clone(),
+            timestamp: value.timestamp.nanoseconds(),
+        }
+    }
+}
+
+impl TryFrom<CandidConsensusState> for ConsensusState {
+    type Error = Error;
+
+    fn try_from(value: CandidConsensusState) -> Result<Self, Self::Error> {
+        Ok(ConsensusState::new(
+            value.public_key.try_into()?,
+            value.diversifier,
+            timestamp(value.timestamp)?,
+        ))
+    }
+}
+
+impl From<&ClientState> for CandidClientState {
+    fn from(value: &ClientState) -> Self {
+        Self {
+            sequence: value.sequence.revision_height(),
+            is_frozen: value.is_frozen,
+            consensus_state: (&value.consensus_state).into(),
+            inactivity_period: value.inactivity_period.map(Into::into),
+            allow_update_after_proposal: value.allow_update_after_proposal,
+        }
+    }
+}
+
+impl TryFrom<CandidClientState> for ClientState {
+    type Error = Error;
+
+    fn try_from(value: CandidClientState) -> Result<Self, Self::Error> {
+        Ok(ClientState {
+            inactivity_period: value.inactivity_period.map(TryInto::try_into).transpose()?,
+            allow_update_after_proposal: value.allow_update_after_proposal,
+            ..ClientState::new(
+                sequence(value.sequence)?,
+                value.is_frozen,
+                value.consensus_state.try_into()?,
+            )
+        })
+    }
+}
+
+impl From<&Header> for CandidHeader {
+    fn from(value: &Header) -> Self {
+        Self {
+            timestamp: value.timestamp.nanoseconds(),
+            signature: value.signature.clone(),
+            new_public_key: (&value.new_public_key).into(),
+            new_diversifier: value.new_diversifier.clone(),
+        }
+    }
+}
+
+impl TryFrom<CandidHeader> for Header {
+    type Error = Error;
+
+    fn try_from(value: CandidHeader) -> Result<Self, Self::Error> {
+        Ok(Self {
+            timestamp: timestamp(value.timestamp)?,
+            signature: value.signature,
+            new_public_key: value.new_public_key.try_into()?,
+            new_diversifier: value.new_diversifier,
+        })
+    }
+}
+
+impl From<&SignatureAndData> for CandidSignatureAndData {
+    fn from(value: &SignatureAndData) -> Self {
+        Self {
+            signature: value.signature.clone(),
+            path: value.path.clone(),
+            data: value.data.clone(),
+            timestamp: value.timestamp.nanoseconds(),
+        }
+    }
+}
+
+impl TryFrom<CandidSignatureAndData> for SignatureAndData {
+    type Error = Error;
+
+    fn try_from(value: CandidSignatureAndData) -> Result<Self, Self::Error> {
+        Ok(Self {
+            signature: value.signature,
+            path: value.path,
+            data: value.data,
+            timestamp: timestamp(value.timestamp)?,
+        })
+    }
+}
+
+impl From<&Misbehaviour> for CandidMisbehaviour {
+    fn from(value: &Misbehaviour) -> Self {
+        Self {
+            sequence: value.sequence.revision_height(),
+            signature_one: (&value.signature_one).into(),
+            signature_two: (&value.signature_two).into(),
+        }
+    }
+}
+
+impl TryFrom<CandidMisbehaviour> for Misbehaviour {
+    type Error = Error;
+
+    fn try_from(value: CandidMisbehaviour) -> Result<Self, Self::Error> {
+        Ok(Self {
+            sequence: sequence(value.sequence)?,
+            signature_one: value.signature_one.try_into()?,
+            signature_two: value.signature_two.try_into()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn round_trip<C, T>(value: &T) -> T
+    where
+        C: for<'a> From<&'a T> + CandidType + for<'de> Deserialize<'de>,
+        T: TryFrom<C, Error = Error> + PartialEq,
+    {
+        let encoded = candid::encode_one(C::from(value)).unwrap();
+        let decoded: T = candid::decode_one::<C>(&encoded)
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert!(decoded == *value);
+        decoded
+    }
+
+    #[test]
+    fn test_candid_round_trip() {
+        const EXAMPLE_JSON: &str = "{\"@type\":\"/cosmos.crypto.ed25519.PubKey\",\"key\":\"sEEsVGkXvyewKLWMJbHVDRkBoerW0IIwmj1rHkabtHU=\"}";
+        let public_key = EXAMPLE_JSON.parse::<PublicKey>().unwrap();
+        let timestamp = Timestamp::from_nanoseconds(10).unwrap();
+        let sequence = Height::new(0, 3).unwrap();
+        let consensus_state = ConsensusState::new(public_key, "testing".into(), timestamp);
+        let signature_and_data = |data: u8| SignatureAndData {
+            signature: vec![1, data],
+            path: b"path".to_vec(),
+            data: vec![data],
+            timestamp,
+        };
+
+        round_trip::<CandidPublicKey, _>(&public_key);
+        round_trip::<CandidConsensusState, _>(&consensus_state);
+        round_trip::<CandidSignatureAndData, _>(&signature_and_data(1));
+        round_trip::<CandidHeader, _>(&Header {
+            timestamp,
+            signature: vec![1, 2, 3],
+            new_public_key: public_key,
+            new_diversifier: "testing".into(),
+        });
+        round_trip::<CandidMisbehaviour, _>(&Misbehaviour {
+            sequence,
+            signature_one: signature_and_data(1),
+            signature_two: signature_and_data(2),
+        });
+        round_trip::<CandidClientState, _>(
+            &ClientState::new(sequence, true, consensus_state)
+                .with_inactivity_period(Duration::from_millis(1500)),
+        );
+
+        let unknown = CandidPublicKey {
+            type_url: "/unknown.PubKey".into(),
+            key: public_key.to_bytes(),
+        };
+        assert!(PublicKey::try_from(unknown).is_err());
+    }
+}
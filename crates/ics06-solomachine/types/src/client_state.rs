@@ -3,9 +3,10 @@
 use crate::consensus_state::ConsensusState as SmConsensusState;
 use crate::cosmos::crypto::PublicKey;
 use crate::error::Error;
-use crate::proof::types::signature_and_data::SignatureAndData;
+use crate::limits::VerificationLimits;
 use crate::proof::types::timestamped_signature_data::TimestampedSignatureData;
 use core::convert::{TryFrom, TryInto};
+use core::time::Duration;
 use ibc_core::client::types::error::ClientError;
 use ibc_core::client::types::Height;
 use ibc_core::commitment_types::commitment::CommitmentProofBytes;
@@ -18,9 +19,36 @@ use prost::Message;
 
 pub const SOLOMACHINE_CLIENT_STATE_TYPE_URL: &str = "/ibc.lightclients.solomachine.v3.ClientState";
 
+/// ClientStateExtension holds the client state fields that are not part of the
+/// ibc-go schema. They are encoded next to the `ClientState` fields under field
+/// numbers ibc-go does not use, so ibc-go decoders skip them and an unset
+/// extension encodes to nothing. The field numbers are 1024 and above, which
+/// ADR-020 marks non-critical, so Cosmos SDK hosts rejecting unknown critical
+/// fields accept them. The inactivity period is an explicit optional field so
+/// that a zero period is told apart from no period.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RawSmClientStateExtension {
+    /// inactivity period in nanoseconds, unset if the client never expires
+    #[prost(uint64, optional, tag = "1024")]
+    pub inactivity_period: ::core::option::Option<u64>,
+    /// `allow_update_after_proposal` of a client migrated from v2
+    #[prost(bool, tag = "1025")]
+    pub allow_update_after_proposal: bool,
+}
+
 /// ClientState defines a solo machine client that tracks the current consensus
 /// state and if the client is frozen.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
 #[derive(Clone, PartialEq, Debug)]
 pub struct ClientState {
     /// latest sequence of the client state
@@ -29,6 +57,15 @@ pub struct ClientState {
     pub is_frozen: bool,
     /// consensus state of the solo machine
     pub consensus_state: SmConsensusState,
+    /// period after the latest consensus state timestamp at which the client
+    /// expires, encoded in the client state extension
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub inactivity_period: Option<Duration>,
+    /// `allow_update_after_proposal` of a client migrated from v2, kept in the
+    /// client state extension so the migration is lossless. v3 clients do not
+    /// use it.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub allow_update_after_proposal: bool,
 }
 impl ClientState {
     /// Create a new ClientState Instance.
@@ -37,6 +74,16 @@ impl ClientState {
             sequence,
             is_frozen,
             consensus_state,
+            inactivity_period: None,
+            allow_update_after_proposal: false,
+        }
+    }
+
+    /// Sets the period of inactivity after which the client expires.
+    pub fn with_inactivity_period(self, inactivity_period: Duration) -> Self {
+        Self {
+            inactivity_period: Some(inactivity_period),
+            ..self
         }
     }
 
@@ -60,56 +107,139 @@ impl ClientState {
         self.sequence
     }
 
+    /// Returns true if the latest consensus state is older than the
+    /// inactivity period at `host_timestamp`.
+    pub fn is_expired(&self, host_timestamp: Timestamp) -> bool {
+        match (
+            self.inactivity_period,
+            host_timestamp.duration_since(&self.consensus_state.timestamp),
+        ) {
+            (Some(inactivity_period), Some(elapsed)) => elapsed > inactivity_period,
+            _ => false,
+        }
+    }
+
     // GetTimestampAtHeight returns the timestamp in nanoseconds of the consensus state at the given height.
     pub fn time_stamp(&self) -> Timestamp {
         self.consensus_state.timestamp
     }
 
-    // Validate performs basic validation of the client state fields.
-    pub fn valida_basic(&self) -> Result<(), Error> {
+    // Validate performs basic validation of the client state fields. The
+    // inactivity period must fit the nanoseconds of the extension encoding.
+    pub fn validate_basic(&self) -> Result<(), Error> {
         if self.sequence.revision_height() == 0 {
             return Err(Error::SequenceCannotZero);
         }
-        self.consensus_state.valida_basic()
+        if let Some(inactivity_period) = self.inactivity_period {
+            if u64::try_from(inactivity_period.as_nanos()).is_err() {
+                return Err(Error::InactivityPeriodTooLong(inactivity_period));
+            }
+        }
+        self.consensus_state.validate_basic()
+    }
+
+    #[deprecated(since = "0.1.4", note = "use `validate_basic` instead")]
+    pub fn valida_basic(&self) -> Result<(), Error> {
+        self.validate_basic()
     }
 
     // produceVerificationArgs perfoms the basic checks on the arguments that are
     // shared between the verification functions and returns the public key of the
     // consensus state, the unmarshalled proof representing the signature and timestamp.
+    // The proof and its signature are checked against the host `limits` before
+    // they are decoded.
     pub fn produce_verification_args(
         &self,
         proof: &CommitmentProofBytes,
-    ) -> Result<(PublicKey, SignatureAndData, Timestamp, u64), Error> {
+        limits: &VerificationLimits,
+    ) -> Result<(PublicKey, Vec<u8>, Timestamp, u64), Error> {
+        if self.is_frozen {
+            return Err(Error::ClientStateIsFrozen);
+        }
+
         let proof = Vec::<u8>::from(proof.clone());
         if proof.is_empty() {
-            return Err(Error::Other("proof cannot be empty".into()));
+            return Err(Error::ProofCannotEmpty);
         }
+        limits.check_message(proof.len())?;
 
-        let timestamped_sig_data = TimestampedSignatureData::decode_vec(&proof).map_err(|e| {
-            Error::Other(format!(
-                "failed to decode proof into type TimestampedSignatureData: {}",
-                e
-            ))
-        })?;
+        let timestamped_sig_data =
+            TimestampedSignatureData::decode_vec(&proof).map_err(|e| Error::UndecodableProof {
+                reason: e.to_string(),
+            })?;
 
         let timestamp = timestamped_sig_data.timestamp;
-        let signature_and_data = timestamped_sig_data.signature_data;
+        let signature_data = timestamped_sig_data.signature_data;
+        limits.check_signature(&signature_data)?;
 
         if self.consensus_state.timestamp > timestamp {
-            return Err(Error::Other(format!(
-                "the consensus state timestamp is greater than the signature timestamp ({} >= {})",
-                self.consensus_state.timestamp, timestamp
-            )));
+            return Err(Error::TimestampRegression {
+                timestamp,
+                consensus_timestamp: self.consensus_state.timestamp,
+            });
         }
 
         let latest_sequence = self.sequence.revision_height();
         let public_key = self.consensus_state.public_key();
-        Ok((public_key, signature_and_data, timestamp, latest_sequence))
+        Ok((public_key, signature_data, timestamp, latest_sequence))
+    }
+}
+
+// The raw conversions carry the ibc-go fields only, the `Any` encoding also
+// carries the extension.
+// Borsh encodes the persisted fields in declaration order, the inactivity
+// period as its seconds and subsecond nanoseconds.
+#[cfg(feature = "borsh")]
+impl borsh::BorshSerialize for ClientState {
+    fn serialize<W: borsh::maybestd::io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> borsh::maybestd::io::Result<()> {
+        self.sequence.serialize(writer)?;
+        self.is_frozen.serialize(writer)?;
+        self.consensus_state.serialize(writer)?;
+        self.inactivity_period
+            .map(|period| (period.as_secs(), period.subsec_nanos()))
+            .serialize(writer)?;
+        self.allow_update_after_proposal.serialize(writer)
     }
 }
 
-impl Protobuf<RawSmClientState> for ClientState {}
+#[cfg(feature = "borsh")]
+impl borsh::BorshDeserialize for ClientState {
+    fn deserialize_reader<R: borsh::maybestd::io::Read>(
+        reader: &mut R,
+    ) -> borsh::maybestd::io::Result<Self> {
+        use borsh::maybestd::io::{Error as IoError, ErrorKind};
+
+        let sequence = Height::deserialize_reader(reader)?;
+        let is_frozen = bool::deserialize_reader(reader)?;
+        let consensus_state = SmConsensusState::deserialize_reader(reader)?;
+        let inactivity_period = match Option::<(u64, u32)>::deserialize_reader(reader)? {
+            Some((_, nanos)) if nanos >= 1_000_000_000 => {
+                return Err(IoError::new(
+                    ErrorKind::InvalidData,
+                    "invalid inactivity period",
+                ))
+            }
+            period => period.map(|(secs, nanos)| Duration::new(secs, nanos)),
+        };
+        let allow_update_after_proposal = bool::deserialize_reader(reader)?;
+
+        Ok(Self {
+            inactivity_period,
+            allow_update_after_proposal,
+            ..Self::new(sequence, is_frozen, consensus_state)
+        })
+    }
+}
 
+// The raw conversions carry only the fields of the ibc-go schema: a client
+// state converted from `RawSmClientState` has no inactivity period and does
+// not allow updates after proposals, and both are dropped on the way back.
+// There is therefore no `Protobuf<RawSmClientState>` implementation; client
+// states are encoded with `encode_client_state` and `decode_client_state`, or
+// as an `Any`, which carry the extension as well.
 impl TryFrom<RawSmClientState> for ClientState {
     type Error = Error;
 
@@ -124,6 +254,8 @@ impl TryFrom<RawSmClientState> for ClientState {
             sequence,
             is_frozen: raw.is_frozen,
             consensus_state,
+            inactivity_period: None,
+            allow_update_after_proposal: false,
         })
     }
 }
@@ -138,24 +270,49 @@ impl From<ClientState> for RawSmClientState {
     }
 }
 
+impl From<&ClientState> for RawSmClientStateExtension {
+    fn from(value: &ClientState) -> Self {
+        Self {
+            // periods too long for the encoding fail `validate_basic`
+            inactivity_period: value
+                .inactivity_period
+                .map(|period| u64::try_from(period.as_nanos()).unwrap_or(u64::MAX)),
+            allow_update_after_proposal: value.allow_update_after_proposal,
+        }
+    }
+}
+
+/// Decodes a client state together with its extension fields.
+pub fn decode_client_state(buf: &[u8]) -> Result<ClientState, Error> {
+    let extension = RawSmClientStateExtension::decode(buf).map_err(Error::Decode)?;
+    let client_state: ClientState = RawSmClientState::decode(buf)
+        .map_err(Error::Decode)?
+        .try_into()?;
+
+    Ok(ClientState {
+        inactivity_period: extension.inactivity_period.map(Duration::from_nanos),
+        allow_update_after_proposal: extension.allow_update_after_proposal,
+        ..client_state
+    })
+}
+
+/// Encodes a client state together with its extension fields.
+pub fn encode_client_state(client_state: ClientState) -> Vec<u8> {
+    let mut buf = RawSmClientStateExtension::from(&client_state).encode_to_vec();
+    let mut raw = RawSmClientState::from(client_state).encode_to_vec();
+    raw.append(&mut buf);
+    raw
+}
+
 impl Protobuf<Any> for ClientState {}
 
 impl TryFrom<Any> for ClientState {
     type Error = ClientError;
 
     fn try_from(raw: Any) -> Result<Self, Self::Error> {
-        use bytes::Buf;
-        use core::ops::Deref;
-
-        fn decode_client_state<B: Buf>(buf: B) -> Result<ClientState, Error> {
-            RawSmClientState::decode(buf)
-                .map_err(Error::Decode)?
-                .try_into()
-        }
-
         match raw.type_url.as_str() {
             SOLOMACHINE_CLIENT_STATE_TYPE_URL => {
-                decode_client_state(raw.value.deref()).map_err(Into::into)
+                decode_client_state(raw.value.as_slice()).map_err(Into::into)
             }
             _ => Err(ClientError::UnknownClientStateType {
                 client_state_type: raw.type_url,
@@ -168,7 +325,110 @@ impl From<ClientState> for Any {
     fn from(client_state: ClientState) -> Self {
         Any {
             type_url: SOLOMACHINE_CLIENT_STATE_TYPE_URL.to_string(),
-            value: Protobuf::<RawSmClientState>::encode_vec(client_state),
+            value: encode_client_state(client_state),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client_state() -> ClientState {
+        const EXAMPLE_JSON: &str = "{\"@type\":\"/cosmos.crypto.ed25519.PubKey\",\"key\":\"sEEsVGkXvyewKLWMJbHVDRkBoerW0IIwmj1rHkabtHU=\"}";
+        ClientState::new(
+            Height::new(0, 1).unwrap(),
+            false,
+            SmConsensusState::new(
+                EXAMPLE_JSON.parse::<PublicKey>().unwrap(),
+                "testing".into(),
+                Timestamp::from_nanoseconds(10).unwrap(),
+            ),
+        )
+    }
+
+    #[test]
+    fn test_inactivity_period_extension() {
+        // without an inactivity period the encoding is the ibc-go one
+        let plain = client_state();
+        let any = Any::from(plain.clone());
+        assert_eq!(
+            any.value,
+            RawSmClientState::from(plain.clone()).encode_to_vec()
+        );
+        assert_eq!(ClientState::try_from(any).unwrap(), plain);
+
+        // the extension round-trips and is skipped by ibc-go decoders
+        let extended = client_state().with_inactivity_period(Duration::from_secs(3600));
+        let any = Any::from(extended.clone());
+        assert_eq!(ClientState::try_from(any.clone()).unwrap(), extended);
+        assert_eq!(
+            RawSmClientState::decode(any.value.as_slice()).unwrap(),
+            RawSmClientState::from(plain)
+        );
+    }
+
+    // The unknown field check of Cosmos SDK hosts, from ADR-020: unknown fields
+    // without the non-critical bit (1024) are rejected.
+    fn reject_unknown_critical_fields(mut buf: &[u8], known: &[u32]) -> Result<(), u32> {
+        while !buf.is_empty() {
+            let (tag, wire_type) = prost::encoding::decode_key(&mut buf).unwrap();
+            if tag & 1024 == 0 && !known.contains(&tag) {
+                return Err(tag);
+            }
+            prost::encoding::skip_field(wire_type, tag, &mut buf, Default::default()).unwrap();
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_extension_fields_are_non_critical() {
+        let extended = ClientState {
+            allow_update_after_proposal: true,
+            ..client_state().with_inactivity_period(Duration::ZERO)
+        };
+        let encoded = encode_client_state(extended);
+        assert!(encoded.len() > RawSmClientState::from(client_state()).encoded_len());
+        assert_eq!(reject_unknown_critical_fields(&encoded, &[1, 2, 3]), Ok(()));
+        assert_eq!(reject_unknown_critical_fields(&encoded, &[1, 2]), Err(3));
+    }
+
+    #[test]
+    fn test_zero_inactivity_period_round_trips() {
+        let zero = client_state().with_inactivity_period(Duration::ZERO);
+        let encoded = encode_client_state(zero.clone());
+        assert_ne!(encoded, encode_client_state(client_state()));
+        assert_eq!(decode_client_state(&encoded).unwrap(), zero);
+        assert_eq!(
+            ClientState::try_from(Any::from(zero.clone())).unwrap(),
+            zero
+        );
+    }
+
+    #[test]
+    fn test_inactivity_period_must_fit_the_encoding() {
+        let longest = client_state().with_inactivity_period(Duration::from_nanos(u64::MAX));
+        longest.validate_basic().unwrap();
+        assert_eq!(
+            decode_client_state(&encode_client_state(longest.clone())).unwrap(),
+            longest
+        );
+
+        let too_long = client_state().with_inactivity_period(Duration::from_secs(u64::MAX));
+        assert!(matches!(
+            too_long.validate_basic(),
+            Err(Error::InactivityPeriodTooLong(_))
+        ));
+    }
+
+    #[test]
+    fn test_is_expired() {
+        let client_state = client_state();
+        assert!(!client_state.is_expired(Timestamp::from_nanoseconds(u64::MAX).unwrap()));
+
+        let client_state = client_state.with_inactivity_period(Duration::from_nanos(100));
+        assert!(!client_state.is_expired(Timestamp::from_nanoseconds(5).unwrap()));
+        assert!(!client_state.is_expired(Timestamp::from_nanoseconds(110).unwrap()));
+        assert!(client_state.is_expired(Timestamp::from_nanoseconds(111).unwrap()));
+    }
+}
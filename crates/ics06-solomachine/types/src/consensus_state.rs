@@ -18,6 +18,19 @@ pub const SOLOMACHINE_CONSENSUS_STATE_TYPE_URL: &str =
 /// consensus state is contained in the "height" key used in storing the
 /// consensus state.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
 #[derive(Clone, PartialEq, Debug)]
 pub struct ConsensusState {
     /// public key of the solo machine
@@ -43,7 +56,7 @@ impl ConsensusState {
     }
 
     // ValidateBasic defines basic validation for the solo machine consensus state.
-    pub fn valida_basic(&self) -> Result<(), Error> {
+    pub fn validate_basic(&self) -> Result<(), Error> {
         if self.timestamp.into_tm_time().is_none() {
             return Err(Error::TimeStampIsEmpty);
         }
@@ -52,11 +65,14 @@ impl ConsensusState {
             return Err(Error::DriversifierContainOnlySpaces);
         }
 
-        let _ = self.public_key();
-
         Ok(())
     }
 
+    #[deprecated(since = "0.1.4", note = "use `validate_basic` instead")]
+    pub fn valida_basic(&self) -> Result<(), Error> {
+        self.validate_basic()
+    }
+
     // GetPubKey unmarshals the public key into a cryptotypes.PubKey type.
     // An error is returned if the public key is nil or the cached value
     // is not a PubKey.
@@ -80,17 +96,17 @@ impl TryFrom<RawSmConsensusState> for ConsensusState {
             public_key,
             diversifier: raw.diversifier,
             timestamp,
-            root: CommitmentRoot::from(vec![]),
+            root: CommitmentRoot::from(public_key.to_bytes()),
         })
     }
 }
 
 impl From<ConsensusState> for RawSmConsensusState {
     fn from(value: ConsensusState) -> Self {
-        let public_key = value.public_key.to_any();
+        let public_key = Some(value.public_key.to_any());
         let timestamp = value.timestamp.nanoseconds();
         Self {
-            public_key: Some(public_key),
+            public_key,
             diversifier: value.diversifier,
             timestamp,
         }
@@ -0,0 +1,97 @@
+//! Account addresses
+
+use crate::cosmos::error::Error;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+use subtle_encoding::bech32;
+
+/// Maximum allowed length (in bytes) for an address.
+pub const MAX_ADDRESS_LENGTH: usize = 255;
+
+/// Account identifiers: Bech32-encoded account addresses such as
+/// `cosmos1...`.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct AccountId {
+    /// Account ID encoded as Bech32
+    bech32: String,
+    /// Length of the human-readable prefix of the address
+    hrp_length: usize,
+    /// Raw address bytes
+    bytes: Vec<u8>,
+}
+
+impl AccountId {
+    /// Create an [`AccountId`] with the given human-readable prefix and
+    /// address bytes.
+    pub fn new(prefix: &str, bytes: &[u8]) -> Result<Self, Error> {
+        let id = bech32::encode(prefix, bytes);
+
+        let valid_prefix =
+            !prefix.is_empty() && prefix.chars().all(|c| matches!(c, 'a'..='z' | '0'..='9'));
+        if !valid_prefix || bytes.is_empty() || bytes.len() > MAX_ADDRESS_LENGTH {
+            return Err(Error::AccountId { id });
+        }
+
+        Ok(Self {
+            bech32: id,
+            hrp_length: prefix.len(),
+            bytes: bytes.to_vec(),
+        })
+    }
+
+    /// Get the human-readable prefix of this account.
+    pub fn prefix(&self) -> &str {
+        &self.bech32[..self.hrp_length]
+    }
+
+    /// Decode an account ID from Bech32 to an inner byte value.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.bytes.clone()
+    }
+}
+
+impl AsRef<str> for AccountId {
+    fn as_ref(&self) -> &str {
+        &self.bech32
+    }
+}
+
+impl fmt::Display for AccountId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.bech32)
+    }
+}
+
+impl FromStr for AccountId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (hrp, bytes) = bech32::decode(s).map_err(|_| Error::AccountId { id: s.to_string() })?;
+        AccountId::new(&hrp, &bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AccountId;
+    use alloc::string::ToString;
+
+    #[test]
+    fn bech32_round_trip() {
+        let address = "cosmos1k28nej8nuudz60h3v3h6pactyx3e26au7wwqnc";
+        let account_id = address.parse::<AccountId>().unwrap();
+        assert_eq!(account_id.prefix(), "cosmos");
+        assert_eq!(account_id.to_string(), address);
+        assert_eq!(
+            AccountId::new("cosmos", &account_id.to_bytes()).unwrap(),
+            account_id
+        );
+
+        assert!("cosmos1k28nej8nuudz60h3v3h6pactyx3e26au7wwqnd"
+            .parse::<AccountId>()
+            .is_err());
+        assert!(AccountId::new("Cosmos", &account_id.to_bytes()).is_err());
+    }
+}
@@ -1,3 +1,7 @@
 pub mod public_key;
+#[cfg(feature = "signer")]
+pub mod signing_key;
 
-pub use self::public_key::PublicKey;
+pub use self::public_key::{Algorithm, PublicKey};
+#[cfg(feature = "signer")]
+pub use self::signing_key::SigningKey;
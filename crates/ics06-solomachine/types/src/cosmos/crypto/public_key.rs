@@ -1,5 +1,6 @@
 //! Public keys
 
+use crate::cosmos::account_id::AccountId;
 use crate::cosmos::error::Error;
 use crate::cosmos::raw_proto::ed25519::PubKey as Ed25519PubKey;
 use crate::cosmos::raw_proto::secp256k1::PubKey as Secp256k1PubKey;
@@ -9,10 +10,11 @@ use alloc::str::FromStr;
 use alloc::string::String;
 use alloc::string::ToString;
 use alloc::vec::Vec;
+use core::fmt;
 use ibc_proto::google::protobuf::Any;
 use prost::Message;
-use serde::{Deserialize, Serialize};
-use subtle_encoding::base64;
+use serde::{Deserialize, Serialize, Serializer};
+use subtle_encoding::{base64, bech32, hex};
 
 /// Protobuf [`Any`] type URL for Ed25519 public keys
 pub const ED25519_TYPE_URL: &str = "/cosmos.crypto.ed25519.PubKey";
@@ -20,10 +22,54 @@ pub const ED25519_TYPE_URL: &str = "/cosmos.crypto.ed25519.PubKey";
 /// Protobuf [`Any`] type URL for secp256k1 public keys
 pub const SECP256K1_TYPE_URL: &str = "/cosmos.crypto.secp256k1.PubKey";
 
+/// Amino prefix of `tendermint/PubKeyEd25519`
+const AMINO_ED25519_PUB_KEY_PREFIX: [u8; 4] = [0x16, 0x24, 0xde, 0x64];
+
+/// Amino prefix of `tendermint/PubKeySecp256k1`
+const AMINO_SECP256K1_PUB_KEY_PREFIX: [u8; 4] = [0xeb, 0x5a, 0xe9, 0x87];
+
+/// Public key algorithms
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Algorithm {
+    /// Ed25519
+    Ed25519,
+    /// secp256k1
+    Secp256k1,
+}
+
+impl FromStr for Algorithm {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "ed25519" => Ok(Algorithm::Ed25519),
+            "secp256k1" => Ok(Algorithm::Secp256k1),
+            _ => Err(Error::Other {
+                description: format!("unknown public key algorithm: {}", s),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Algorithm::Ed25519 => f.write_str("ed25519"),
+            Algorithm::Secp256k1 => f.write_str("secp256k1"),
+        }
+    }
+}
+
 /// Public keys
-#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
-#[serde(try_from = "PublicKeyJson", into = "PublicKeyJson")]
-pub struct PublicKey(tendermint::PublicKey);
+///
+/// Only ed25519 and secp256k1 keys can be built, so a [`PublicKey`] always
+/// encodes to a supported type URL.
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(try_from = "PublicKeyJson")]
+pub struct PublicKey {
+    key: tendermint::PublicKey,
+    algorithm: Algorithm,
+}
 
 impl PublicKey {
     /// Parse public key from Cosmos JSON format.
@@ -33,33 +79,126 @@ impl PublicKey {
         })
     }
 
+    /// Parse a raw public key of the given algorithm.
+    pub fn from_raw(algorithm: Algorithm, bytes: &[u8]) -> Result<Self, Error> {
+        let tm_key = match algorithm {
+            Algorithm::Ed25519 => tendermint::PublicKey::from_raw_ed25519(bytes),
+            Algorithm::Secp256k1 => tendermint::PublicKey::from_raw_secp256k1(bytes),
+        };
+
+        tm_key
+            .map(|key| Self { key, algorithm })
+            .ok_or(Error::Crypto)
+    }
+
+    /// Parse a hex-encoded raw public key of the given algorithm.
+    pub fn from_hex(algorithm: Algorithm, s: &str) -> Result<Self, Error> {
+        let bytes = hex::decode(s.trim().to_ascii_lowercase()).map_err(|e| Error::Other {
+            description: format!("{}", e),
+        })?;
+        Self::from_raw(algorithm, &bytes)
+    }
+
+    /// Parse a standard Base64 encoded raw public key of the given algorithm.
+    pub fn from_base64(algorithm: Algorithm, s: &str) -> Result<Self, Error> {
+        let bytes = base64::decode(s.trim()).map_err(|e| Error::Other {
+            description: format!("{}", e),
+        })?;
+        Self::from_raw(algorithm, &bytes)
+    }
+
+    /// Parse a legacy amino Bech32 public key, e.g. `cosmospub1...`.
+    pub fn from_bech32(s: &str) -> Result<Self, Error> {
+        let (_hrp, bytes) = bech32::decode(s.trim()).map_err(|e| Error::Other {
+            description: format!("{}", e),
+        })?;
+        if bytes.len() < 5 || usize::from(bytes[4]) != bytes.len() - 5 {
+            return Err(Error::Crypto);
+        }
+
+        let (prefix, key) = (&bytes[..4], &bytes[5..]);
+        if prefix == AMINO_ED25519_PUB_KEY_PREFIX {
+            Self::from_raw(Algorithm::Ed25519, key)
+        } else if prefix == AMINO_SECP256K1_PUB_KEY_PREFIX {
+            Self::from_raw(Algorithm::Secp256k1, key)
+        } else {
+            Err(Error::Crypto)
+        }
+    }
+
+    /// Serialize public key as a legacy amino Bech32 string with the given
+    /// human-readable prefix, e.g. `cosmospub`.
+    pub fn to_bech32(&self, prefix: &str) -> Result<String, Error> {
+        let amino_prefix = match self.algorithm {
+            Algorithm::Ed25519 => AMINO_ED25519_PUB_KEY_PREFIX,
+            Algorithm::Secp256k1 => AMINO_SECP256K1_PUB_KEY_PREFIX,
+        };
+        let key = self.to_bytes();
+
+        let mut bytes = amino_prefix.to_vec();
+        bytes.push(key.len() as u8);
+        bytes.extend_from_slice(&key);
+        Ok(bech32::encode(prefix, bytes))
+    }
+
+    /// Serialize the raw public key as lower case hex.
+    pub fn to_hex(&self) -> String {
+        String::from_utf8(hex::encode(self.to_bytes())).unwrap_or_default()
+    }
+
+    /// Serialize the raw public key as standard Base64.
+    pub fn to_base64(&self) -> String {
+        String::from_utf8(base64::encode(self.to_bytes())).unwrap_or_default()
+    }
+
+    /// Get the account address of this public key with the given Bech32
+    /// prefix, e.g. `cosmos`.
+    pub fn account_id(&self, prefix: &str) -> Result<AccountId, Error> {
+        let id = tendermint::account::Id::from(self.key);
+        AccountId::new(prefix, id.as_bytes())
+    }
+
     /// Serialize public key as Cosmos JSON.
+    pub fn try_to_json(&self) -> Result<String, Error> {
+        serde_json::to_string(&self).map_err(|e| Error::Json {
+            description: format!("{}", e),
+        })
+    }
+
+    /// Serialize public key as Cosmos JSON, or an empty string if that fails
+    /// like [`PublicKey::try_to_json`] does.
+    #[deprecated(since = "0.1.4", note = "use `try_to_json` instead")]
     pub fn to_json(&self) -> String {
-        serde_json::to_string(&self).expect("JSON serialization error")
+        self.try_to_json().unwrap_or_default()
+    }
+
+    /// Get the algorithm of this [`PublicKey`].
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
     }
 
     /// Get the type URL for this [`PublicKey`].
     pub fn type_url(&self) -> &'static str {
-        match &self.0 {
-            tendermint::PublicKey::Ed25519(_) => ED25519_TYPE_URL,
-            tendermint::PublicKey::Secp256k1(_) => SECP256K1_TYPE_URL,
-            // `tendermint::PublicKey` is `non_exhaustive`
-            _ => unreachable!("unknown pubic key type"),
+        match self.algorithm {
+            Algorithm::Ed25519 => ED25519_TYPE_URL,
+            Algorithm::Secp256k1 => SECP256K1_TYPE_URL,
         }
     }
 
+    /// Get the type URL for this [`PublicKey`].
+    ///
+    /// The error is unreachable: every [`PublicKey`] holds a supported key.
+    #[deprecated(since = "0.1.4", note = "use `type_url` instead, which cannot fail")]
+    pub fn try_type_url(&self) -> Result<&'static str, Error> {
+        Ok(self.type_url())
+    }
+
     /// Convert this [`PublicKey`] to a Protobuf [`Any`] type.
     pub fn to_any(&self) -> Any {
-        let value = match self.0 {
-            tendermint::PublicKey::Ed25519(_) => Ed25519PubKey {
-                key: self.to_bytes(),
-            }
-            .encode_to_vec(),
-            tendermint::PublicKey::Secp256k1(_) => Secp256k1PubKey {
-                key: self.to_bytes(),
-            }
-            .encode_to_vec(),
-            n => panic!("{}", format!("unknown {:?}", n)),
+        let key = self.to_bytes();
+        let value = match self.algorithm {
+            Algorithm::Ed25519 => Ed25519PubKey { key }.encode_to_vec(),
+            Algorithm::Secp256k1 => Secp256k1PubKey { key }.encode_to_vec(),
         };
 
         Any {
@@ -68,15 +207,26 @@ impl PublicKey {
         }
     }
 
+    /// Convert this [`PublicKey`] to a Protobuf [`Any`] type.
+    ///
+    /// The error is unreachable: every [`PublicKey`] holds a supported key.
+    #[deprecated(since = "0.1.4", note = "use `to_any` instead, which cannot fail")]
+    pub fn try_to_any(&self) -> Result<Any, Error> {
+        Ok(self.to_any())
+    }
+
     /// Serialize this [`PublicKey`] as a byte vector.
     pub fn to_bytes(&self) -> Vec<u8> {
-        self.0.to_bytes()
+        self.key.to_bytes()
     }
 }
 
 impl From<k256::ecdsa::VerifyingKey> for PublicKey {
     fn from(vk: k256::ecdsa::VerifyingKey) -> PublicKey {
-        PublicKey(vk.into())
+        PublicKey {
+            key: vk.into(),
+            algorithm: Algorithm::Secp256k1,
+        }
     }
 }
 
@@ -118,9 +268,7 @@ impl TryFrom<Ed25519PubKey> for PublicKey {
     type Error = Error;
 
     fn try_from(public_key: Ed25519PubKey) -> Result<PublicKey, Self::Error> {
-        tendermint::public_key::PublicKey::from_raw_ed25519(&public_key.key)
-            .map(Into::into)
-            .ok_or(Error::Crypto)
+        PublicKey::from_raw(Algorithm::Ed25519, &public_key.key)
     }
 }
 
@@ -128,49 +276,71 @@ impl TryFrom<Secp256k1PubKey> for PublicKey {
     type Error = Error;
 
     fn try_from(public_key: Secp256k1PubKey) -> Result<PublicKey, Self::Error> {
-        tendermint::public_key::PublicKey::from_raw_secp256k1(&public_key.key)
-            .map(Into::into)
-            .ok_or(Error::Crypto)
+        PublicKey::from_raw(Algorithm::Secp256k1, &public_key.key)
     }
 }
 
 impl From<PublicKey> for Any {
     fn from(public_key: PublicKey) -> Any {
-        // This is largely a workaround for `tendermint::PublicKey` being
-        // marked `non_exhaustive`.
         public_key.to_any()
     }
 }
 
-impl From<tendermint::PublicKey> for PublicKey {
-    fn from(pk: tendermint::PublicKey) -> PublicKey {
-        PublicKey(pk)
+impl TryFrom<tendermint::PublicKey> for PublicKey {
+    type Error = Error;
+
+    fn try_from(key: tendermint::PublicKey) -> Result<PublicKey, Self::Error> {
+        let algorithm = match key {
+            tendermint::PublicKey::Ed25519(_) => Algorithm::Ed25519,
+            tendermint::PublicKey::Secp256k1(_) => Algorithm::Secp256k1,
+            // `tendermint::PublicKey` is `non_exhaustive`
+            other => {
+                return Err(Error::UnsupportedPublicKey {
+                    key_type: format!("{:?}", other),
+                })
+            }
+        };
+        Ok(PublicKey { key, algorithm })
     }
 }
 
 impl From<PublicKey> for tendermint::PublicKey {
     fn from(pk: PublicKey) -> tendermint::PublicKey {
-        pk.0
+        pk.key
     }
 }
 
 impl FromStr for PublicKey {
     type Err = Error;
 
+    /// Parse either the Cosmos JSON format or a legacy amino Bech32 key.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Self::from_json(s)
+        if s.trim_start().starts_with('{') {
+            Self::from_json(s)
+        } else {
+            Self::from_bech32(s)
+        }
     }
 }
 
 impl ToString for PublicKey {
     fn to_string(&self) -> String {
-        self.to_json()
+        self.try_to_json().unwrap_or_default()
+    }
+}
+
+impl Serialize for PublicKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        PublicKeyJson::try_from(self)
+            .map_err(serde::ser::Error::custom)?
+            .serialize(serializer)
     }
 }
 
 /// Serde encoding type for JSON public keys.
 ///
 /// Uses Protobuf JSON encoding conventions.
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[derive(Deserialize, Serialize)]
 struct PublicKeyJson {
     /// `@type` field e.g. `/cosmos.crypto.ed25519.PubKey`.
@@ -181,17 +351,13 @@ struct PublicKeyJson {
     key: String,
 }
 
-impl From<PublicKey> for PublicKeyJson {
-    fn from(public_key: PublicKey) -> PublicKeyJson {
-        PublicKeyJson::from(&public_key)
-    }
-}
+impl TryFrom<&PublicKey> for PublicKeyJson {
+    type Error = Error;
 
-impl From<&PublicKey> for PublicKeyJson {
-    fn from(public_key: &PublicKey) -> PublicKeyJson {
+    fn try_from(public_key: &PublicKey) -> Result<PublicKeyJson, Self::Error> {
         let type_url = public_key.type_url().to_owned();
-        let key = String::from_utf8(base64::encode(public_key.to_bytes())).expect("UTF-8 error");
-        PublicKeyJson { type_url, key }
+        let key = public_key.to_base64();
+        Ok(PublicKeyJson { type_url, key })
     }
 }
 
@@ -211,19 +377,107 @@ impl TryFrom<&PublicKeyJson> for PublicKey {
             description: format!("{}", e),
         })?;
 
-        let tm_key = match json.type_url.as_str() {
-            ED25519_TYPE_URL => tendermint::PublicKey::from_raw_ed25519(&pk_bytes),
-            SECP256K1_TYPE_URL => tendermint::PublicKey::from_raw_secp256k1(&pk_bytes),
+        let algorithm = match json.type_url.as_str() {
+            ED25519_TYPE_URL => Algorithm::Ed25519,
+            SECP256K1_TYPE_URL => Algorithm::Secp256k1,
             _other => return Err(Error::Crypto),
         };
 
-        tm_key.map(Into::into).ok_or(Error::Crypto)
+        PublicKey::from_raw(algorithm, &pk_bytes)
+    }
+}
+
+// The JSON schema of a public key is the one of its Cosmos JSON encoding.
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for PublicKey {
+    fn schema_name() -> String {
+        "PublicKey".into()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        <PublicKeyJson as schemars::JsonSchema>::json_schema(gen)
+    }
+}
+
+// The SCALE encoding of a public key is its type URL followed by the raw key
+// bytes, the fields of its JSON encoding.
+#[cfg(feature = "parity-scale-codec")]
+impl parity_scale_codec::Encode for PublicKey {
+    fn encode_to<T: parity_scale_codec::Output + ?Sized>(&self, dest: &mut T) {
+        let type_url = self.type_url().to_owned();
+        parity_scale_codec::Encode::encode_to(&(type_url, self.to_bytes()), dest)
+    }
+}
+
+#[cfg(feature = "parity-scale-codec")]
+impl parity_scale_codec::Decode for PublicKey {
+    fn decode<I: parity_scale_codec::Input>(
+        input: &mut I,
+    ) -> Result<Self, parity_scale_codec::Error> {
+        let (type_url, key) = <(String, Vec<u8>) as parity_scale_codec::Decode>::decode(input)?;
+        let algorithm = match type_url.as_str() {
+            ED25519_TYPE_URL => Algorithm::Ed25519,
+            SECP256K1_TYPE_URL => Algorithm::Secp256k1,
+            _ => return Err("unsupported public key type".into()),
+        };
+        PublicKey::from_raw(algorithm, &key).map_err(|_| "invalid public key".into())
+    }
+}
+
+#[cfg(feature = "parity-scale-codec")]
+impl scale_info::TypeInfo for PublicKey {
+    type Identity = Self;
+
+    fn type_info() -> scale_info::Type {
+        scale_info::Type::builder()
+            .path(scale_info::Path::new("PublicKey", module_path!()))
+            .composite(
+                scale_info::build::Fields::named()
+                    .field(|f| f.ty::<String>().name("type_url").type_name("String"))
+                    .field(|f| f.ty::<Vec<u8>>().name("key").type_name("Vec<u8>")),
+            )
+    }
+}
+
+// Borsh encodes a public key as the SCALE codec does, see above.
+#[cfg(feature = "borsh")]
+impl borsh::BorshSerialize for PublicKey {
+    fn serialize<W: borsh::maybestd::io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> borsh::maybestd::io::Result<()> {
+        let type_url = self.type_url().to_owned();
+        borsh::BorshSerialize::serialize(&(type_url, self.to_bytes()), writer)
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl borsh::BorshDeserialize for PublicKey {
+    fn deserialize_reader<R: borsh::maybestd::io::Read>(
+        reader: &mut R,
+    ) -> borsh::maybestd::io::Result<Self> {
+        use borsh::maybestd::io::{Error as IoError, ErrorKind};
+
+        let (type_url, key) =
+            <(String, Vec<u8>) as borsh::BorshDeserialize>::deserialize_reader(reader)?;
+        let algorithm = match type_url.as_str() {
+            ED25519_TYPE_URL => Algorithm::Ed25519,
+            SECP256K1_TYPE_URL => Algorithm::Secp256k1,
+            _ => {
+                return Err(IoError::new(
+                    ErrorKind::InvalidData,
+                    "unsupported public key type",
+                ))
+            }
+        };
+        PublicKey::from_raw(algorithm, &key)
+            .map_err(|_| IoError::new(ErrorKind::InvalidData, "invalid public key"))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::PublicKey;
+    use super::{Algorithm, PublicKey};
     use alloc::string::ToString;
 
     const EXAMPLE_JSON: &str = "{\"@type\":\"/cosmos.crypto.ed25519.PubKey\",\"key\":\"sEEsVGkXvyewKLWMJbHVDRkBoerW0IIwmj1rHkabtHU=\"}";
@@ -246,5 +500,57 @@ mod tests {
             ]
         );
         assert_eq!(EXAMPLE_JSON, example_key.to_string());
+        assert_eq!(EXAMPLE_JSON, example_key.try_to_json().unwrap());
+        assert_eq!(
+            PublicKey::try_from(example_key.to_any()).unwrap(),
+            example_key
+        );
+
+        #[allow(deprecated)]
+        {
+            assert_eq!(EXAMPLE_JSON, example_key.to_json());
+            assert_eq!(example_key.try_type_url(), Ok(example_key.type_url()));
+            assert_eq!(example_key.try_to_any(), Ok(example_key.to_any()));
+        }
+    }
+
+    #[test]
+    fn alternate_encodings() {
+        const SECP256K1_HEX: &str =
+            "02d344a3db99e63434e66f68fe475e5ea41b7e0b3244fe4e8b829554c3e25f241f";
+        const SECP256K1_BASE64: &str = "AtNEo9uZ5jQ05m9o/kdeXqQbfgsyRP5Oi4KVVMPiXyQf";
+        const SECP256K1_BECH32: &str =
+            "cosmospub1addwnpepqtf5fg7mn8nrgd8xda50u367t6jpklstxfz0un5ts224fslztujp7t5mqv9";
+        const ED25519_BECH32: &str =
+            "cosmosvalconspub1zcjduepqj7f3d27es9vmzh082svc2pvqfdymdqwkv5hpp4gk6azf42pdmfjswq5hwh";
+
+        let key = PublicKey::from_hex(Algorithm::Secp256k1, SECP256K1_HEX).unwrap();
+        assert_eq!(key.algorithm(), Algorithm::Secp256k1);
+        assert_eq!(key.to_hex(), SECP256K1_HEX);
+        assert_eq!(key.to_base64(), SECP256K1_BASE64);
+        assert_eq!(
+            PublicKey::from_base64(Algorithm::Secp256k1, SECP256K1_BASE64).unwrap(),
+            key
+        );
+        assert_eq!(key.to_bech32("cosmospub").unwrap(), SECP256K1_BECH32);
+        assert_eq!(SECP256K1_BECH32.parse::<PublicKey>().unwrap(), key);
+        assert_eq!(
+            key.account_id("cosmos").unwrap().to_string(),
+            "cosmos1k28nej8nuudz60h3v3h6pactyx3e26au7wwqnc"
+        );
+
+        let key = ED25519_BECH32.parse::<PublicKey>().unwrap();
+        assert_eq!(key.type_url(), "/cosmos.crypto.ed25519.PubKey");
+        assert_eq!(key.to_bech32("cosmosvalconspub").unwrap(), ED25519_BECH32);
+        assert_eq!(
+            key.account_id("cosmos").unwrap().to_string(),
+            "cosmos10mf6f0w9mel7vgj6qqjz35wlktvvlz2qc50qtn"
+        );
+
+        assert!(PublicKey::from_hex(Algorithm::Ed25519, SECP256K1_HEX).is_err());
+        assert_eq!(
+            "Secp256k1".parse::<Algorithm>().unwrap(),
+            Algorithm::Secp256k1
+        );
     }
 }
@@ -0,0 +1,194 @@
+//! Signing keys
+
+use crate::cosmos::crypto::{Algorithm, PublicKey};
+use crate::cosmos::error::Error;
+use crate::cosmos::raw_proto::ed25519::PrivKey as Ed25519PrivKey;
+use crate::cosmos::raw_proto::secp256k1::PrivKey as Secp256k1PrivKey;
+use crate::proof::encode_signature_data;
+use crate::proof::types::sign_bytes::SignBytes;
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::vec::Vec;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::Protobuf;
+use k256::ecdsa::signature::Signer;
+use prost::Message;
+
+/// Protobuf [`Any`] type URL for Ed25519 private keys
+pub const ED25519_PRIV_KEY_TYPE_URL: &str = "/cosmos.crypto.ed25519.PrivKey";
+
+/// Protobuf [`Any`] type URL for secp256k1 private keys
+pub const SECP256K1_PRIV_KEY_TYPE_URL: &str = "/cosmos.crypto.secp256k1.PrivKey";
+
+/// Private keys a solo machine signs `SignBytes` with.
+#[derive(Clone)]
+pub enum SigningKey {
+    /// Ed25519 signing key
+    Ed25519(ed25519_consensus::SigningKey),
+    /// secp256k1 signing key
+    Secp256k1(k256::ecdsa::SigningKey),
+}
+
+impl SigningKey {
+    /// Parse an Ed25519 key from either its 32-byte seed or the 64-byte
+    /// `seed || public key` form used by the Cosmos SDK.
+    pub fn from_ed25519_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let seed = match bytes.len() {
+            32 | 64 => &bytes[..32],
+            _ => return Err(Error::Crypto),
+        };
+        ed25519_consensus::SigningKey::try_from(seed)
+            .map(Self::Ed25519)
+            .map_err(|_| Error::Crypto)
+    }
+
+    /// Parse a secp256k1 key from its 32-byte scalar.
+    pub fn from_secp256k1_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        k256::ecdsa::SigningKey::from_slice(bytes)
+            .map(Self::Secp256k1)
+            .map_err(|_| Error::Crypto)
+    }
+
+    /// Get the [`PublicKey`] matching this signing key.
+    pub fn public_key(&self) -> Result<PublicKey, Error> {
+        match self {
+            Self::Ed25519(sk) => {
+                PublicKey::from_raw(Algorithm::Ed25519, &sk.verification_key().to_bytes())
+            }
+            Self::Secp256k1(sk) => Ok(sk.verifying_key().into()),
+        }
+    }
+
+    /// Get the type URL for this [`SigningKey`].
+    pub fn type_url(&self) -> &'static str {
+        match self {
+            Self::Ed25519(_) => ED25519_PRIV_KEY_TYPE_URL,
+            Self::Secp256k1(_) => SECP256K1_PRIV_KEY_TYPE_URL,
+        }
+    }
+
+    /// Serialize this [`SigningKey`] the way the Cosmos SDK stores it: the
+    /// 64-byte `seed || public key` for Ed25519, the 32-byte scalar for secp256k1.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Self::Ed25519(sk) => {
+                let mut bytes = sk.to_bytes().to_vec();
+                bytes.extend_from_slice(&sk.verification_key().to_bytes());
+                bytes
+            }
+            Self::Secp256k1(sk) => sk.to_bytes().to_vec(),
+        }
+    }
+
+    /// Convert this [`SigningKey`] to a Protobuf [`Any`] type.
+    pub fn to_any(&self) -> Any {
+        let value = match self {
+            Self::Ed25519(_) => Ed25519PrivKey {
+                key: self.to_bytes(),
+            }
+            .encode_to_vec(),
+            Self::Secp256k1(_) => Secp256k1PrivKey {
+                key: self.to_bytes(),
+            }
+            .encode_to_vec(),
+        };
+
+        Any {
+            type_url: self.type_url().to_owned(),
+            value,
+        }
+    }
+
+    /// Sign an arbitrary message, returning the raw signature bytes.
+    pub fn sign(&self, msg: &[u8]) -> Vec<u8> {
+        match self {
+            Self::Ed25519(sk) => sk.sign(msg).to_bytes().to_vec(),
+            Self::Secp256k1(sk) => {
+                let signature: k256::ecdsa::Signature = sk.sign(msg);
+                signature.to_bytes().to_vec()
+            }
+        }
+    }
+
+    /// Sign the protobuf encoding of the given [`SignBytes`].
+    pub fn sign_bytes(&self, sign_bytes: SignBytes) -> Vec<u8> {
+        self.sign(&sign_bytes.encode_vec())
+    }
+
+    /// Sign the given [`SignBytes`] and wrap the signature in the
+    /// `SignatureDescriptor.Data` that `proof::verify_signature` checks.
+    pub fn sign_signature_data(&self, sign_bytes: SignBytes) -> Vec<u8> {
+        encode_signature_data(self.sign_bytes(sign_bytes))
+    }
+}
+
+impl core::fmt::Debug for SigningKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        write!(f, "SigningKey({})", self.type_url())
+    }
+}
+
+impl TryFrom<Any> for SigningKey {
+    type Error = Error;
+
+    fn try_from(any: Any) -> Result<SigningKey, Self::Error> {
+        SigningKey::try_from(&any)
+    }
+}
+
+impl TryFrom<&Any> for SigningKey {
+    type Error = Error;
+
+    fn try_from(any: &Any) -> Result<SigningKey, Self::Error> {
+        match any.type_url.as_str() {
+            ED25519_PRIV_KEY_TYPE_URL => {
+                let private_key =
+                    Ed25519PrivKey::decode(&*any.value).map_err(|e| Error::Other {
+                        description: format!("{}", e),
+                    })?;
+                SigningKey::from_ed25519_bytes(&private_key.key)
+            }
+            SECP256K1_PRIV_KEY_TYPE_URL => {
+                let private_key =
+                    Secp256k1PrivKey::decode(&*any.value).map_err(|e| Error::Other {
+                        description: format!("{}", e),
+                    })?;
+                SigningKey::from_secp256k1_bytes(&private_key.key)
+            }
+            _other => Err(Error::Crypto),
+        }
+    }
+}
+
+impl From<SigningKey> for Any {
+    fn from(signing_key: SigningKey) -> Any {
+        signing_key.to_any()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SigningKey;
+    use crate::proof::{encode_signature_data, verify_signature};
+
+    #[test]
+    fn sign_and_verify() {
+        let keys = [
+            SigningKey::from_ed25519_bytes(&[7u8; 32]).unwrap(),
+            SigningKey::from_secp256k1_bytes(&[7u8; 32]).unwrap(),
+        ];
+
+        for key in keys {
+            let message = b"solomachine";
+            verify_signature(
+                key.public_key().unwrap(),
+                message.to_vec(),
+                encode_signature_data(key.sign(message)),
+            )
+            .unwrap();
+
+            let decoded = SigningKey::try_from(key.to_any()).unwrap();
+            assert_eq!(decoded.to_bytes(), key.to_bytes());
+        }
+    }
+}
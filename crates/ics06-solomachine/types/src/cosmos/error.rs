@@ -58,6 +58,33 @@ pub enum Error {
         hash: Hash,
     },
 
+    /// Keyring errors, keyring error: {description}
+    Keyring {
+        /// Description of the failure
+        description: String,
+    },
+
+    /// Keyring entry not found: {name:?}
+    KeyNotFound {
+        /// Name of the missing key
+        name: String,
+    },
+
+    /// Incorrect keyring passphrase
+    InvalidPassphrase,
+
+    /// Unsupported public key type: {key_type}
+    UnsupportedPublicKey {
+        /// Debug representation of the key
+        key_type: String,
+    },
+
+    /// JSON serialization error: {description}
+    Json {
+        /// Description of the failure
+        description: String,
+    },
+
     /// other error: {description},
     Other { description: String },
 }
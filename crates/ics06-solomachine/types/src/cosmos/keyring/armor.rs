@@ -0,0 +1,266 @@
+//! ASCII armor for exported private keys.
+//!
+//! Mirrors the Cosmos SDK `keys export`/`keys import` format: the amino-encoded
+//! private key is encrypted with xsalsa20-poly1305 under the SHA-256 of a
+//! bcrypt hash of the passphrase, then wrapped in an OpenPGP-style armor block.
+
+use crate::cosmos::crypto::SigningKey;
+use crate::cosmos::error::Error;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use crypto_secretbox::aead::{Aead, KeyInit};
+use crypto_secretbox::{Nonce, XSalsa20Poly1305};
+use rand_core::{OsRng, RngCore};
+use sha2::{Digest, Sha256};
+
+/// Armor block type of exported private keys
+pub const PRIVATE_KEY_BLOCK_TYPE: &str = "TENDERMINT PRIVATE KEY";
+
+/// bcrypt cost used by the Cosmos SDK when armoring private keys
+pub const BCRYPT_SECURITY_PARAMETER: u32 = 12;
+
+/// Amino prefix of `tendermint/PrivKeyEd25519`
+const AMINO_ED25519_PRIV_KEY_PREFIX: [u8; 4] = [0xa7, 0x64, 0x32, 0x70];
+
+/// Amino prefix of `tendermint/PrivKeySecp256k1`
+const AMINO_SECP256K1_PRIV_KEY_PREFIX: [u8; 4] = [0xe1, 0xb0, 0xf7, 0x9b];
+
+const NONCE_LEN: usize = 24;
+
+/// OpenPGP CRC-24 checksum (RFC 4880, section 6.1).
+fn crc24(data: &[u8]) -> u32 {
+    let mut crc: u32 = 0x00b7_04ce;
+    for byte in data {
+        crc ^= u32::from(*byte) << 16;
+        for _ in 0..8 {
+            crc <<= 1;
+            if crc & 0x0100_0000 != 0 {
+                crc ^= 0x0186_4cfb;
+            }
+        }
+    }
+    crc & 0x00ff_ffff
+}
+
+/// Encode `data` as an armor block of the given type.
+pub fn encode_armor(block_type: &str, headers: &BTreeMap<String, String>, data: &[u8]) -> String {
+    let mut armor = format!("-----BEGIN {}-----\n", block_type);
+    for (key, value) in headers {
+        armor.push_str(&format!("{}: {}\n", key, value));
+    }
+    armor.push('\n');
+
+    let body = STANDARD.encode(data);
+    for line in body.as_bytes().chunks(64) {
+        armor.push_str(&String::from_utf8_lossy(line));
+        armor.push('\n');
+    }
+
+    let crc = crc24(data).to_be_bytes();
+    armor.push_str(&format!("={}\n", STANDARD.encode(&crc[1..])));
+    armor.push_str(&format!("-----END {}-----", block_type));
+    armor
+}
+
+/// The type, headers and payload of an armor block.
+pub type ArmorBlock = (String, BTreeMap<String, String>, Vec<u8>);
+
+/// Decode an armor block, returning its type, headers and payload.
+pub fn decode_armor(armor: &str) -> Result<ArmorBlock, Error> {
+    let malformed = |description: &str| Error::Keyring {
+        description: format!("malformed armor: {}", description),
+    };
+
+    let mut lines = armor.trim().lines().map(str::trim);
+    let block_type = lines
+        .next()
+        .and_then(|line| line.strip_prefix("-----BEGIN "))
+        .and_then(|line| line.strip_suffix("-----"))
+        .ok_or_else(|| malformed("missing BEGIN line"))?
+        .to_string();
+
+    let mut headers = BTreeMap::new();
+    for line in lines.by_ref() {
+        if line.is_empty() {
+            break;
+        }
+        let (key, value) = line
+            .split_once(": ")
+            .ok_or_else(|| malformed("invalid header"))?;
+        headers.insert(key.to_string(), value.to_string());
+    }
+
+    let mut body = String::new();
+    let mut checksum = None;
+    let end = format!("-----END {}-----", block_type);
+    for line in lines.by_ref() {
+        if line == end {
+            let data = STANDARD
+                .decode(&body)
+                .map_err(|_| malformed("invalid base64 body"))?;
+            if let Some(checksum) = checksum {
+                let expected = STANDARD
+                    .decode(checksum)
+                    .map_err(|_| malformed("invalid checksum"))?;
+                if crc24(&data).to_be_bytes()[1..] != expected[..] {
+                    return Err(malformed("checksum mismatch"));
+                }
+            }
+            return Ok((block_type, headers, data));
+        }
+        match line.strip_prefix('=') {
+            Some(crc) => checksum = Some(crc),
+            None => body.push_str(line),
+        }
+    }
+
+    Err(malformed("missing END line"))
+}
+
+/// Derive the secretbox key from a passphrase the way the Cosmos SDK does:
+/// SHA-256 over the `$2a$` bcrypt hash string.
+fn derive_key(salt: [u8; 16], passphrase: &str) -> Result<[u8; 32], Error> {
+    let hash = bcrypt::hash_with_salt(passphrase, BCRYPT_SECURITY_PARAMETER, salt)
+        .map_err(|e| Error::Keyring {
+            description: format!("{}", e),
+        })?
+        .format_for_version(bcrypt::Version::TwoA);
+    Ok(Sha256::digest(hash.as_bytes()).into())
+}
+
+fn amino_encode(signing_key: &SigningKey) -> Vec<u8> {
+    let prefix = match signing_key {
+        SigningKey::Ed25519(_) => AMINO_ED25519_PRIV_KEY_PREFIX,
+        SigningKey::Secp256k1(_) => AMINO_SECP256K1_PRIV_KEY_PREFIX,
+    };
+    let key = signing_key.to_bytes();
+    let mut bytes = prefix.to_vec();
+    bytes.push(key.len() as u8);
+    bytes.extend_from_slice(&key);
+    bytes
+}
+
+fn amino_decode(bytes: &[u8]) -> Result<SigningKey, Error> {
+    if bytes.len() < 5 || usize::from(bytes[4]) != bytes.len() - 5 {
+        return Err(Error::Crypto);
+    }
+    let (prefix, key) = (&bytes[..4], &bytes[5..]);
+    if prefix == AMINO_ED25519_PRIV_KEY_PREFIX {
+        SigningKey::from_ed25519_bytes(key)
+    } else if prefix == AMINO_SECP256K1_PRIV_KEY_PREFIX {
+        SigningKey::from_secp256k1_bytes(key)
+    } else {
+        Err(Error::Crypto)
+    }
+}
+
+fn algorithm(signing_key: &SigningKey) -> &'static str {
+    match signing_key {
+        SigningKey::Ed25519(_) => "ed25519",
+        SigningKey::Secp256k1(_) => "secp256k1",
+    }
+}
+
+/// Encrypt a private key with a passphrase and armor it, as `keys export` does.
+pub fn encrypt_armor_priv_key(signing_key: &SigningKey, passphrase: &str) -> Result<String, Error> {
+    let mut salt = [0u8; 16];
+    let mut nonce = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut salt);
+    OsRng.fill_bytes(&mut nonce);
+
+    let key = derive_key(salt, passphrase)?;
+    let sealed = XSalsa20Poly1305::new(&key.into())
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            amino_encode(signing_key).as_slice(),
+        )
+        .map_err(|_| Error::Crypto)?;
+    let mut ciphertext = nonce.to_vec();
+    ciphertext.extend_from_slice(&sealed);
+
+    let mut headers = BTreeMap::new();
+    headers.insert("kdf".to_string(), "bcrypt".to_string());
+    headers.insert("salt".to_string(), hex_upper(&salt));
+    headers.insert("type".to_string(), algorithm(signing_key).to_string());
+
+    Ok(encode_armor(PRIVATE_KEY_BLOCK_TYPE, &headers, &ciphertext))
+}
+
+/// Unarmor and decrypt a private key exported with `keys export`.
+pub fn unarmor_decrypt_priv_key(armor: &str, passphrase: &str) -> Result<SigningKey, Error> {
+    let (block_type, headers, ciphertext) = decode_armor(armor)?;
+    if block_type != PRIVATE_KEY_BLOCK_TYPE {
+        return Err(Error::Keyring {
+            description: format!("unrecognized armor type: {}", block_type),
+        });
+    }
+    if headers.get("kdf").map(String::as_str) != Some("bcrypt") {
+        return Err(Error::Keyring {
+            description: "unrecognized KDF type".to_string(),
+        });
+    }
+    let salt: [u8; 16] = headers
+        .get("salt")
+        .and_then(|salt| subtle_encoding::hex::decode_upper(salt).ok())
+        .and_then(|salt| salt.try_into().ok())
+        .ok_or_else(|| Error::Keyring {
+            description: "missing or invalid salt".to_string(),
+        })?;
+    if ciphertext.len() < NONCE_LEN {
+        return Err(Error::Crypto);
+    }
+
+    let key = derive_key(salt, passphrase)?;
+    let (nonce, sealed) = ciphertext.split_at(NONCE_LEN);
+    let plaintext = XSalsa20Poly1305::new(&key.into())
+        .decrypt(Nonce::from_slice(nonce), sealed)
+        .map_err(|_| Error::InvalidPassphrase)?;
+    let signing_key = amino_decode(&plaintext)?;
+
+    if let Some(algo) = headers.get("type") {
+        if algo != algorithm(&signing_key) {
+            return Err(Error::Keyring {
+                description: format!("armored key type {} does not match the key", algo),
+            });
+        }
+    }
+    Ok(signing_key)
+}
+
+fn hex_upper(bytes: &[u8]) -> String {
+    String::from_utf8(subtle_encoding::hex::encode_upper(bytes)).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{crc24, decode_armor, encrypt_armor_priv_key, unarmor_decrypt_priv_key};
+    use crate::cosmos::crypto::SigningKey;
+    use crate::cosmos::error::Error;
+
+    #[test]
+    fn crc24_matches_openpgp() {
+        assert_eq!(crc24(b""), 0x00b7_04ce);
+        assert_eq!(crc24(b"123456789"), 0x0021_cf02);
+    }
+
+    #[test]
+    fn encrypted_armor_round_trip() {
+        let signing_key = SigningKey::from_secp256k1_bytes(&[9u8; 32]).unwrap();
+        let armor = encrypt_armor_priv_key(&signing_key, "passphrase").unwrap();
+
+        let (_, headers, _) = decode_armor(&armor).unwrap();
+        assert_eq!(headers.get("type").unwrap(), "secp256k1");
+
+        let decrypted = unarmor_decrypt_priv_key(&armor, "passphrase").unwrap();
+        assert_eq!(decrypted.to_bytes(), signing_key.to_bytes());
+
+        assert_eq!(
+            unarmor_decrypt_priv_key(&armor, "wrong").unwrap_err(),
+            Error::InvalidPassphrase
+        );
+    }
+}
@@ -0,0 +1,190 @@
+//! JWE compact serialization used by the `file` keyring backend.
+//!
+//! Every item is encrypted with `PBES2-HS256+A128KW` key wrapping and
+//! `A256GCM` content encryption, keyed by the keyring passphrase.
+
+use crate::cosmos::error::Error;
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use aes_kw::KekAes128;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+const ALG: &str = "PBES2-HS256+A128KW";
+const ENC: &str = "A256GCM";
+const ITERATION_COUNT: u32 = 8192;
+/// Upper bound on the PBKDF2 iteration count of an item, which the item itself
+/// declares; the Cosmos SDK writes [`ITERATION_COUNT`].
+const MAX_ITERATION_COUNT: u32 = 1 << 20;
+const SALT_LEN: usize = 12;
+
+/// JOSE protected header of a keyring item.
+#[derive(Deserialize, Serialize)]
+struct Header {
+    alg: String,
+    enc: String,
+    p2c: u32,
+    p2s: String,
+}
+
+fn decode_segment(segment: &str) -> Result<Vec<u8>, Error> {
+    URL_SAFE_NO_PAD.decode(segment).map_err(|e| Error::Keyring {
+        description: format!("invalid JWE segment: {}", e),
+    })
+}
+
+fn derive_kek(passphrase: &str, p2s: &[u8], p2c: u32) -> KekAes128 {
+    let mut salt = Vec::with_capacity(ALG.len() + 1 + p2s.len());
+    salt.extend_from_slice(ALG.as_bytes());
+    salt.push(0);
+    salt.extend_from_slice(p2s);
+    KekAes128::from(pbkdf2::pbkdf2_hmac_array::<Sha256, 16>(
+        passphrase.as_bytes(),
+        &salt,
+        p2c,
+    ))
+}
+
+/// Encrypt `payload` into a JWE compact token.
+pub(crate) fn encrypt(payload: &[u8], passphrase: &str) -> Result<String, Error> {
+    let mut p2s = [0u8; SALT_LEN];
+    let mut cek = [0u8; 32];
+    let mut iv = [0u8; 12];
+    OsRng.fill_bytes(&mut p2s);
+    OsRng.fill_bytes(&mut cek);
+    OsRng.fill_bytes(&mut iv);
+
+    let header = Header {
+        alg: ALG.to_string(),
+        enc: ENC.to_string(),
+        p2c: ITERATION_COUNT,
+        p2s: URL_SAFE_NO_PAD.encode(p2s),
+    };
+    let header = serde_json::to_string(&header).map_err(|e| Error::Keyring {
+        description: format!("{}", e),
+    })?;
+    let header = URL_SAFE_NO_PAD.encode(header);
+
+    let encrypted_key = derive_kek(passphrase, &p2s, ITERATION_COUNT)
+        .wrap_vec(&cek)
+        .map_err(|e| Error::Keyring {
+            description: format!("{}", e),
+        })?;
+
+    let mut ciphertext = Aes256Gcm::new(&cek.into())
+        .encrypt(
+            Nonce::from_slice(&iv),
+            Payload {
+                msg: payload,
+                aad: header.as_bytes(),
+            },
+        )
+        .map_err(|_| Error::Crypto)?;
+    let tag = ciphertext.split_off(ciphertext.len() - 16);
+
+    Ok(format!(
+        "{}.{}.{}.{}.{}",
+        header,
+        URL_SAFE_NO_PAD.encode(encrypted_key),
+        URL_SAFE_NO_PAD.encode(iv),
+        URL_SAFE_NO_PAD.encode(ciphertext),
+        URL_SAFE_NO_PAD.encode(tag)
+    ))
+}
+
+/// Decrypt a JWE compact token, returning its payload.
+///
+/// A wrong passphrase surfaces as [`Error::InvalidPassphrase`], since the
+/// content encryption key then fails to unwrap.
+pub(crate) fn decrypt(token: &str, passphrase: &str) -> Result<Vec<u8>, Error> {
+    let segments: Vec<&str> = token.trim().split('.').collect();
+    let [header_segment, encrypted_key, iv, ciphertext, tag] = segments.as_slice() else {
+        return Err(Error::Keyring {
+            description: format!("expected 5 JWE segments, found {}", segments.len()),
+        });
+    };
+
+    let header: Header =
+        serde_json::from_slice(&decode_segment(header_segment)?).map_err(|e| Error::Keyring {
+            description: format!("invalid JWE header: {}", e),
+        })?;
+    if header.alg != ALG || header.enc != ENC {
+        return Err(Error::Keyring {
+            description: format!("unsupported JWE algorithm {}/{}", header.alg, header.enc),
+        });
+    }
+    if !(1..=MAX_ITERATION_COUNT).contains(&header.p2c) {
+        return Err(Error::Keyring {
+            description: format!(
+                "JWE iteration count {} is outside of 1..={}",
+                header.p2c, MAX_ITERATION_COUNT
+            ),
+        });
+    }
+
+    let cek = derive_kek(passphrase, &decode_segment(&header.p2s)?, header.p2c)
+        .unwrap_vec(&decode_segment(encrypted_key)?)
+        .map_err(|_| Error::InvalidPassphrase)?;
+    let cipher = Aes256Gcm::new_from_slice(&cek).map_err(|_| Error::Crypto)?;
+
+    let iv = decode_segment(iv)?;
+    if iv.len() != 12 {
+        return Err(Error::Keyring {
+            description: format!("invalid JWE iv length {}", iv.len()),
+        });
+    }
+    let mut msg = decode_segment(ciphertext)?;
+    msg.extend_from_slice(&decode_segment(tag)?);
+
+    cipher
+        .decrypt(
+            Nonce::from_slice(&iv),
+            Payload {
+                msg: &msg,
+                aad: header_segment.as_bytes(),
+            },
+        )
+        .map_err(|_| Error::Crypto)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decrypt, encrypt, Header, ALG, ENC};
+    use crate::cosmos::error::Error;
+    use alloc::format;
+    use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    use base64::Engine;
+
+    #[test]
+    fn round_trip() {
+        let token = encrypt(b"solomachine", "passphrase").unwrap();
+        assert_eq!(decrypt(&token, "passphrase").unwrap(), b"solomachine");
+        assert_eq!(decrypt(&token, "wrong"), Err(Error::InvalidPassphrase));
+    }
+
+    #[test]
+    fn reject_unbounded_iteration_count() {
+        let token = encrypt(b"solomachine", "passphrase").unwrap();
+        let (_, rest) = token.split_once('.').unwrap();
+        for p2c in [0, u32::MAX] {
+            let header = serde_json::to_string(&Header {
+                alg: ALG.into(),
+                enc: ENC.into(),
+                p2c,
+                p2s: URL_SAFE_NO_PAD.encode([0u8; 12]),
+            })
+            .unwrap();
+            let token = format!("{}.{}", URL_SAFE_NO_PAD.encode(header), rest);
+            assert!(matches!(
+                decrypt(&token, "passphrase"),
+                Err(Error::Keyring { .. })
+            ));
+        }
+    }
+}
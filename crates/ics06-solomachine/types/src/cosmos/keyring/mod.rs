@@ -0,0 +1,478 @@
+//! Cosmos SDK `file` keyring backend.
+//!
+//! A `keyring-file` directory holds one encrypted item per file: `<name>.info`
+//! with the protobuf `Record` of a key, `<hex address>.address` pointing back to
+//! that record, and `keyhash` with the bcrypt hash of the keyring passphrase.
+
+pub mod armor;
+mod jwe;
+
+use crate::cosmos::crypto::{PublicKey, SigningKey};
+use crate::cosmos::error::Error;
+use crate::cosmos::raw_proto::keyring::{record, Record};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+/// Name of the keyring directory inside a Cosmos SDK home directory
+pub const KEYRING_FILE_DIR: &str = "keyring-file";
+
+const KEYHASH_FILE: &str = "keyhash";
+const INFO_SUFFIX: &str = ".info";
+const ADDRESS_SUFFIX: &str = ".address";
+
+/// bcrypt cost the Cosmos SDK uses for `keyhash`
+const KEYHASH_COST: u32 = 10;
+
+/// Keyring item as serialized by the `99designs/keyring` file backend.
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct Item {
+    key: String,
+    /// standard Base64 encoded item data
+    data: String,
+    #[serde(default)]
+    label: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    keychain_not_trust_application: bool,
+    #[serde(default)]
+    keychain_not_synchronizable: bool,
+}
+
+/// A key stored in the keyring.
+#[derive(Clone, Debug)]
+pub struct KeyRecord {
+    /// name of the key
+    pub name: String,
+    /// public key of the key
+    pub public_key: PublicKey,
+    /// private key, `None` for ledger, multisig and offline records
+    pub signing_key: Option<SigningKey>,
+}
+
+impl KeyRecord {
+    /// Account address bytes of this key.
+    pub fn address(&self) -> Vec<u8> {
+        tendermint::account::Id::from(tendermint::PublicKey::from(self.public_key))
+            .as_bytes()
+            .to_vec()
+    }
+}
+
+impl TryFrom<Record> for KeyRecord {
+    type Error = Error;
+
+    fn try_from(raw: Record) -> Result<Self, Self::Error> {
+        let public_key =
+            PublicKey::try_from(raw.pub_key.ok_or(Error::MissingField { name: "pub_key" })?)?;
+        let signing_key = match raw.item.ok_or(Error::MissingField { name: "item" })? {
+            record::Item::Local(local) => Some(SigningKey::try_from(
+                local
+                    .priv_key
+                    .ok_or(Error::MissingField { name: "priv_key" })?,
+            )?),
+            record::Item::Ledger(_) | record::Item::Multi(_) | record::Item::Offline(_) => None,
+        };
+
+        Ok(Self {
+            name: raw.name,
+            public_key,
+            signing_key,
+        })
+    }
+}
+
+impl TryFrom<KeyRecord> for Record {
+    type Error = Error;
+
+    fn try_from(value: KeyRecord) -> Result<Self, Self::Error> {
+        let signing_key = value.signing_key.ok_or_else(|| Error::Keyring {
+            description: format!("key {} has no private key to store", value.name),
+        })?;
+
+        Ok(Self {
+            name: value.name,
+            pub_key: Some(value.public_key.to_any()),
+            item: Some(record::Item::Local(record::Local {
+                priv_key: Some(signing_key.to_any()),
+            })),
+        })
+    }
+}
+
+fn io_error(e: std::io::Error) -> Error {
+    Error::Keyring {
+        description: format!("{}", e),
+    }
+}
+
+/// Escape a keyring item key into a file name, percent-encoding every byte
+/// outside of the URL unreserved set.
+fn filename_escape(key: &str) -> String {
+    let mut escaped = String::with_capacity(key.len());
+    for byte in key.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                escaped.push(char::from(byte))
+            }
+            _ => escaped.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    escaped
+}
+
+/// Reverse [`filename_escape`], returning `None` for a file name it cannot
+/// have produced.
+fn filename_unescape(file_name: &str) -> Option<String> {
+    let hex_digit = |byte: u8| char::from(byte).to_digit(16);
+    let mut key = Vec::with_capacity(file_name.len());
+    let mut rest = file_name.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let [high, low, tail @ ..] = tail else {
+                return None;
+            };
+            key.push(u8::try_from((hex_digit(*high)? << 4) | hex_digit(*low)?).ok()?);
+            rest = tail;
+        } else {
+            key.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(key).ok()
+}
+
+/// A Cosmos SDK `file` keyring directory.
+pub struct FileKeyring {
+    dir: PathBuf,
+    passphrase: String,
+}
+
+impl FileKeyring {
+    /// Open an existing keyring directory, checking the passphrase against its
+    /// `keyhash` when there is one.
+    pub fn open<P: AsRef<Path>>(dir: P, passphrase: &str) -> Result<Self, Error> {
+        let dir = dir.as_ref().to_path_buf();
+        if !dir.is_dir() {
+            return Err(Error::Keyring {
+                description: format!("{} is not a directory", dir.display()),
+            });
+        }
+
+        match fs::read_to_string(dir.join(KEYHASH_FILE)) {
+            Ok(keyhash) => {
+                let valid =
+                    bcrypt::verify(passphrase, keyhash.trim()).map_err(|e| Error::Keyring {
+                        description: format!("invalid keyhash: {}", e),
+                    })?;
+                if !valid {
+                    return Err(Error::InvalidPassphrase);
+                }
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => return Err(io_error(e)),
+        }
+
+        Ok(Self {
+            dir,
+            passphrase: passphrase.to_string(),
+        })
+    }
+
+    /// Open a keyring directory, creating it and its `keyhash` if needed.
+    pub fn create<P: AsRef<Path>>(dir: P, passphrase: &str) -> Result<Self, Error> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir).map_err(io_error)?;
+
+        let keyhash_path = dir.join(KEYHASH_FILE);
+        if !keyhash_path.exists() {
+            let keyhash = bcrypt::hash_with_result(passphrase, KEYHASH_COST)
+                .map_err(|e| Error::Keyring {
+                    description: format!("{}", e),
+                })?
+                .format_for_version(bcrypt::Version::TwoA);
+            write_private_file(&keyhash_path, keyhash.as_bytes())?;
+        }
+
+        Self::open(dir, passphrase)
+    }
+
+    /// Names of all keys in the keyring, sorted.
+    pub fn list(&self) -> Result<Vec<String>, Error> {
+        let mut names = Vec::new();
+        for entry in fs::read_dir(&self.dir).map_err(io_error)? {
+            let file_name = entry.map_err(io_error)?.file_name();
+            if let Some(name) = file_name
+                .to_str()
+                .and_then(|file_name| file_name.strip_suffix(INFO_SUFFIX))
+                .and_then(filename_unescape)
+            {
+                names.push(name);
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    /// Load the key stored under `name`.
+    pub fn get(&self, name: &str) -> Result<KeyRecord, Error> {
+        let data = self.read_item(&format!("{}{}", name, INFO_SUFFIX))?;
+        Record::decode(data.as_slice())
+            .map_err(|e| Error::Keyring {
+                description: format!("failed to decode record {}: {}", name, e),
+            })?
+            .try_into()
+    }
+
+    /// Load the key with the given account address.
+    pub fn get_by_address(&self, address: &[u8]) -> Result<KeyRecord, Error> {
+        let hex_address =
+            String::from_utf8(subtle_encoding::hex::encode(address)).map_err(|_| Error::Crypto)?;
+        let info_key = self.read_item(&format!("{}{}", hex_address, ADDRESS_SUFFIX))?;
+        let name = String::from_utf8(info_key)
+            .ok()
+            .and_then(|info_key| info_key.strip_suffix(INFO_SUFFIX).map(ToString::to_string))
+            .ok_or_else(|| Error::Keyring {
+                description: format!("invalid address entry for {}", hex_address),
+            })?;
+        self.get(&name)
+    }
+
+    /// Load the private key stored under `name`.
+    pub fn signing_key(&self, name: &str) -> Result<SigningKey, Error> {
+        self.get(name)?.signing_key.ok_or_else(|| Error::Keyring {
+            description: format!("key {} has no local private key", name),
+        })
+    }
+
+    /// Store `signing_key` under `name`, replacing any previous key of that name.
+    pub fn add(&self, name: &str, signing_key: &SigningKey) -> Result<KeyRecord, Error> {
+        let key_record = KeyRecord {
+            name: name.to_string(),
+            public_key: signing_key.public_key()?,
+            signing_key: Some(signing_key.clone()),
+        };
+        let info_key = format!("{}{}", name, INFO_SUFFIX);
+        let hex_address = String::from_utf8(subtle_encoding::hex::encode(key_record.address()))
+            .map_err(|_| Error::Crypto)?;
+
+        self.write_item(
+            &info_key,
+            &Record::try_from(key_record.clone())?.encode_to_vec(),
+        )?;
+        self.write_item(
+            &format!("{}{}", hex_address, ADDRESS_SUFFIX),
+            info_key.as_bytes(),
+        )?;
+
+        Ok(key_record)
+    }
+
+    /// Remove the key stored under `name`.
+    pub fn delete(&self, name: &str) -> Result<(), Error> {
+        let key_record = self.get(name)?;
+        let hex_address = String::from_utf8(subtle_encoding::hex::encode(key_record.address()))
+            .map_err(|_| Error::Crypto)?;
+
+        fs::remove_file(self.item_path(&format!("{}{}", name, INFO_SUFFIX))).map_err(io_error)?;
+        match fs::remove_file(self.item_path(&format!("{}{}", hex_address, ADDRESS_SUFFIX))) {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(io_error(e)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Export the private key stored under `name` as an encrypted armor.
+    pub fn export_armor(&self, name: &str, passphrase: &str) -> Result<String, Error> {
+        armor::encrypt_armor_priv_key(&self.signing_key(name)?, passphrase)
+    }
+
+    /// Import an encrypted armored private key under `name`.
+    pub fn import_armor(
+        &self,
+        name: &str,
+        armor: &str,
+        passphrase: &str,
+    ) -> Result<KeyRecord, Error> {
+        let signing_key = armor::unarmor_decrypt_priv_key(armor, passphrase)?;
+        self.add(name, &signing_key)
+    }
+
+    fn item_path(&self, key: &str) -> PathBuf {
+        self.dir.join(filename_escape(key))
+    }
+
+    fn read_item(&self, key: &str) -> Result<Vec<u8>, Error> {
+        let token = match fs::read_to_string(self.item_path(key)) {
+            Ok(token) => token,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                return Err(Error::KeyNotFound {
+                    name: key.to_string(),
+                })
+            }
+            Err(e) => return Err(io_error(e)),
+        };
+
+        let item: Item =
+            serde_json::from_slice(&jwe::decrypt(&token, &self.passphrase)?).map_err(|e| {
+                Error::Keyring {
+                    description: format!("invalid keyring item {}: {}", key, e),
+                }
+            })?;
+        STANDARD.decode(item.data).map_err(|e| Error::Keyring {
+            description: format!("invalid keyring item {}: {}", key, e),
+        })
+    }
+
+    fn write_item(&self, key: &str, data: &[u8]) -> Result<(), Error> {
+        let item = Item {
+            key: key.to_string(),
+            data: STANDARD.encode(data),
+            label: String::new(),
+            description: String::new(),
+            keychain_not_trust_application: false,
+            keychain_not_synchronizable: false,
+        };
+        let payload = serde_json::to_vec(&item).map_err(|e| Error::Keyring {
+            description: format!("{}", e),
+        })?;
+        let token = jwe::encrypt(&payload, &self.passphrase)?;
+        write_private_file(&self.item_path(key), token.as_bytes())
+    }
+}
+
+/// Write a file readable only by its owner.
+fn write_private_file(path: &Path, contents: &[u8]) -> Result<(), Error> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options
+        .open(path)
+        .and_then(|mut file| file.write_all(contents))
+        .map_err(io_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FileKeyring;
+    use crate::cosmos::crypto::public_key::{ED25519_TYPE_URL, SECP256K1_TYPE_URL};
+    use crate::cosmos::crypto::SigningKey;
+    use crate::cosmos::error::Error;
+    use crate::proof::types::sign_bytes::SignBytes;
+    use crate::proof::verify_signature;
+    use alloc::format;
+    use alloc::string::ToString;
+    use alloc::vec;
+    use ibc_proto::Protobuf;
+
+    /// Keyring directory holding a secp256k1 key `alice` and an Ed25519 key
+    /// `bob`, see `tests/fixtures/README.md`.
+    const FIXTURE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/keyring-file");
+    const FIXTURE_PASSPHRASE: &str = "U*U";
+
+    fn hex(s: &str) -> vec::Vec<u8> {
+        subtle_encoding::hex::decode(s).unwrap()
+    }
+
+    fn assert_signs_sign_bytes(signing_key: &SigningKey) {
+        let sign_bytes = SignBytes {
+            sequence: 1,
+            timestamp: 1_700_000_000_000_000_000,
+            diversifier: "diversifier".to_string(),
+            path: b"/ibc/connections/connection-0".to_vec(),
+            data: vec![1, 2, 3],
+        };
+        verify_signature(
+            signing_key.public_key().unwrap(),
+            sign_bytes.clone().encode_vec(),
+            signing_key.sign_signature_data(sign_bytes),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn read_fixture_keyring() {
+        let keyring = FileKeyring::open(FIXTURE_DIR, FIXTURE_PASSPHRASE).unwrap();
+        assert_eq!(keyring.list().unwrap(), ["alice", "bob"]);
+
+        let alice = keyring.get("alice").unwrap();
+        assert_eq!(alice.public_key.type_url(), SECP256K1_TYPE_URL);
+        assert_eq!(
+            alice.public_key.to_bytes(),
+            hex("02d344a3db99e63434e66f68fe475e5ea41b7e0b3244fe4e8b829554c3e25f241f")
+        );
+        assert_eq!(
+            alice.address(),
+            hex("b28f3cc8f3e71a2d3ef1646fa0f70b21a3956bbc")
+        );
+
+        let bob = keyring
+            .get_by_address(&hex("7ed3a4bdc5de7fe6225a002428d1dfb2d8cf8940"))
+            .unwrap();
+        assert_eq!(bob.name, "bob");
+        assert_eq!(bob.public_key.type_url(), ED25519_TYPE_URL);
+        assert_eq!(
+            bob.public_key.to_bytes(),
+            hex("979316abd98159b15de754198505804b49b681d6652e10d516d7449aa82dda65")
+        );
+
+        for key_record in [alice, bob] {
+            let signing_key = key_record.signing_key.unwrap();
+            assert_eq!(signing_key.public_key().unwrap(), key_record.public_key);
+            assert_signs_sign_bytes(&signing_key);
+        }
+    }
+
+    #[test]
+    fn reject_wrong_passphrase() {
+        assert!(matches!(
+            FileKeyring::open(FIXTURE_DIR, "wrong"),
+            Err(Error::InvalidPassphrase)
+        ));
+    }
+
+    #[test]
+    fn write_and_read_back() {
+        let dir = std::env::temp_dir().join(format!("solomachine-keyring-{}", std::process::id()));
+        let keyring = FileKeyring::create(&dir, "passphrase").unwrap();
+
+        let signing_key = SigningKey::from_ed25519_bytes(&[3u8; 32]).unwrap();
+        let added = keyring.add("carol", &signing_key).unwrap();
+        assert_eq!(keyring.list().unwrap(), ["carol"]);
+        assert_eq!(
+            keyring.get_by_address(&added.address()).unwrap().public_key,
+            added.public_key
+        );
+
+        let armor = keyring.export_armor("carol", "export").unwrap();
+        keyring
+            .import_armor("dave/ed25519", &armor, "export")
+            .unwrap();
+        assert_eq!(
+            keyring.signing_key("dave/ed25519").unwrap().to_bytes(),
+            signing_key.to_bytes()
+        );
+        assert_eq!(keyring.list().unwrap(), ["carol", "dave/ed25519"]);
+
+        keyring.delete("carol").unwrap();
+        assert!(matches!(
+            keyring.get("carol"),
+            Err(Error::KeyNotFound { .. })
+        ));
+        assert!(matches!(
+            FileKeyring::open(&dir, "wrong"),
+            Err(Error::InvalidPassphrase)
+        ));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,3 +1,8 @@
+pub mod account_id;
 pub mod crypto;
 pub mod error;
+#[cfg(feature = "keyring")]
+pub mod keyring;
 pub mod raw_proto;
+
+pub use self::account_id::AccountId;
@@ -1,2 +1,3 @@
 pub mod ed25519;
+pub mod keyring;
 pub mod secp256k1;
@@ -1,10 +1,21 @@
+use crate::versioned::Version;
+use alloc::boxed::Box;
 use alloc::string::String;
 use alloc::string::ToString;
+use core::time::Duration;
 use displaydoc::Display;
 use ibc_core::channel::types::error::ChannelError;
 use ibc_core::client::types::error::ClientError;
+use ibc_core::client::types::Height;
 use ibc_core::connection::types::error::ConnectionError;
-use ibc_core::primitives::ParseTimestampError;
+use ibc_core::primitives::{ParseTimestampError, Timestamp};
+
+/// Codespace of the errors registered by ibc-go's `06-solomachine` module.
+pub const SOLOMACHINE_CODESPACE: &str = "06-solomachine";
+/// Codespace of the errors registered by ibc-go's `02-client` module.
+pub const CLIENT_CODESPACE: &str = "client";
+/// Codespace of errors without a registered equivalent, as in the Cosmos SDK.
+pub const UNDEFINED_CODESPACE: &str = "undefined";
 
 #[derive(Debug, Display)]
 pub enum Error {
@@ -40,6 +51,8 @@ pub enum Error {
     SignatureAndDataIsEmpty,
     /// Sequence cannot be zero
     SequenceCannotZero,
+    /// inactivity period `{0:?}` does not fit in 64 bits of nanoseconds
+    InactivityPeriodTooLong(Duration),
     /// Proof cannot be empty
     ProofCannotEmpty,
     /// ChannelEnd is empty
@@ -48,14 +61,219 @@ pub enum Error {
     ClientStateIsEmpty,
     /// ConnectionEnd is empty
     ConnectionEndIsEmpty,
+    /// signature cannot be empty
+    SignatureCannotEmpty,
+    /// data cannot be empty
+    DataCannotEmpty,
+    /// path cannot be empty
+    PathCannotEmpty,
+    /// misbehaviour signatures cannot be equal
+    MisbehaviourSignaturesCannotEqual,
+    /// misbehaviour signature data must be signed over different messages
+    MisbehaviourDataCannotEqual,
+    /// client state is frozen
+    ClientStateIsFrozen,
+    /// consensus state does not match the client state consensus state
+    ConsensusStateMismatch,
+    /// subject and substitute client identifiers must differ: `{client_id}`
+    SameSubjectAndSubstitute { client_id: String },
+    /// timestamp `{timestamp}` is less than the consensus state timestamp `{consensus_timestamp}`
+    TimestampRegression {
+        timestamp: Timestamp,
+        consensus_timestamp: Timestamp,
+    },
+    /// header timestamp `{timestamp}` is less than the consensus state timestamp `{consensus_timestamp}`
+    HeaderTimestampRegression {
+        timestamp: Timestamp,
+        consensus_timestamp: Timestamp,
+    },
+    /// client state sequence `{expected}` does not match the proof sequence `{got}`
+    SequenceMismatch { expected: Height, got: Height },
+    /// failed to decode proof into type TimestampedSignatureData: `{reason}`
+    UndecodableProof { reason: String },
+    /// failed to decode signature data: `{0}`
+    UndecodableSignatureData(prost::DecodeError),
+    /// multi signatures are not supported
+    MultiSignatureUnsupported,
+    /// malformed signature: `{reason}`
+    MalformedSignature { reason: String },
+    /// signature verification failed: `{reason}`
+    SignatureVerificationFailed { reason: String },
+    /// invalid {kind} signature: `{error}`
+    InvalidSignature {
+        kind: SignatureKind,
+        error: Box<Error>,
+    },
+    /// header timestamp `{timestamp}` is more than `{max_clock_drift:?}` ahead of the host timestamp `{host_timestamp}`
+    HeaderTimestampTooFarInFuture {
+        timestamp: Timestamp,
+        host_timestamp: Timestamp,
+        max_clock_drift: Duration,
+    },
+    /// proof timestamp `{timestamp}` is more than `{max_clock_drift:?}` ahead of the host timestamp `{host_timestamp}`
+    TimestampTooFarInFuture {
+        timestamp: Timestamp,
+        host_timestamp: Timestamp,
+        max_clock_drift: Duration,
+    },
+    /// proof timestamp `{timestamp}` is more than `{max_proof_age:?}` behind the host timestamp `{host_timestamp}`
+    ProofTooOld {
+        timestamp: Timestamp,
+        host_timestamp: Timestamp,
+        max_proof_age: Duration,
+    },
+    /// {field} size {size} exceeds the limit of {limit}
+    InputTooLarge {
+        field: &'static str,
+        size: usize,
+        limit: usize,
+    },
+    /// unknown solo machine message or version: `{type_url}`
+    UnknownSolomachineType { type_url: String },
+    /// not a solo machine type url: `{type_url}`
+    NotSolomachineType { type_url: String },
+    /// {message} cannot be converted from {from} to {to}
+    UnsupportedVersionConversion {
+        message: &'static str,
+        from: Version,
+        to: Version,
+    },
+    /// invalid proto3 JSON: `{reason}`
+    InvalidProtoJson { reason: String },
     /// Other : `{0}`
     Other(String),
 }
 
+/// The signature a verification failure refers to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Display)]
+pub enum SignatureKind {
+    /// header
+    Header,
+    /// proof
+    Proof,
+    /// misbehaviour first
+    MisbehaviourOne,
+    /// misbehaviour second
+    MisbehaviourTwo,
+}
+
+impl Error {
+    /// Wraps the error as a failure to verify the `kind` signature.
+    pub fn for_signature(self, kind: SignatureKind) -> Self {
+        Self::InvalidSignature {
+            kind,
+            error: Box::new(self),
+        }
+    }
+
+    /// Returns the ABCI codespace of the error, `06-solomachine` for the errors
+    /// ibc-go registers in its solo machine module. See [`Error::code`].
+    pub fn codespace(&self) -> &'static str {
+        self.abci().0
+    }
+
+    /// Returns the ABCI code ibc-go reports for the error within its
+    /// [`Error::codespace`]:
+    ///
+    /// | codespace        | code | ibc-go error                     |
+    /// |------------------|------|----------------------------------|
+    /// | `06-solomachine` | 2    | `ErrInvalidHeader`               |
+    /// | `06-solomachine` | 3    | `ErrInvalidSequence`             |
+    /// | `06-solomachine` | 4    | `ErrInvalidSignatureAndData`     |
+    /// | `06-solomachine` | 5    | `ErrSignatureVerificationFailed` |
+    /// | `06-solomachine` | 6    | `ErrInvalidProof`                |
+    /// | `client`         | 3    | `ErrInvalidClient`               |
+    /// | `client`         | 5    | `ErrClientFrozen`                |
+    /// | `client`         | 8    | `ErrInvalidConsensus`            |
+    /// | `client`         | 13   | `ErrInvalidMisbehaviour`         |
+    ///
+    /// Errors without an ibc-go equivalent report code 1 of the `undefined`
+    /// codespace, the Cosmos SDK internal error.
+    pub fn code(&self) -> u32 {
+        self.abci().1
+    }
+
+    fn abci(&self) -> (&'static str, u32) {
+        match self {
+            Self::HeaderTimestampRegression { .. }
+            | Self::HeaderTimestampTooFarInFuture { .. }
+            | Self::DriversifierContainOnlySpaces
+            | Self::PublicKeyParseFailed(_)
+            | Self::PublicKeyIsEmpty
+            | Self::InvalidSignature {
+                kind: SignatureKind::Header,
+                ..
+            } => (SOLOMACHINE_CODESPACE, 2),
+            Self::SequenceMismatch { .. } => (SOLOMACHINE_CODESPACE, 3),
+            Self::SignatureAndDataIsEmpty
+            | Self::SignatureCannotEmpty
+            | Self::DataCannotEmpty
+            | Self::PathCannotEmpty
+            | Self::UndecodableSignatureData(_)
+            | Self::MultiSignatureUnsupported
+            | Self::MalformedSignature { .. }
+            | Self::InputTooLarge { .. } => (SOLOMACHINE_CODESPACE, 4),
+            Self::SignatureVerificationFailed { .. }
+            | Self::InvalidSignature {
+                kind: SignatureKind::Proof,
+                ..
+            } => (SOLOMACHINE_CODESPACE, 5),
+            Self::TimestampRegression { .. }
+            | Self::TimestampTooFarInFuture { .. }
+            | Self::ProofTooOld { .. }
+            | Self::ProofCannotEmpty
+            | Self::UndecodableProof { .. } => (SOLOMACHINE_CODESPACE, 6),
+            // ibc-go returns the verification error of a misbehaviour signature as is
+            Self::InvalidSignature { error, .. } => error.abci(),
+            Self::SequenceCannotZero
+            | Self::TimeStampIsEmpty
+            | Self::InactivityPeriodTooLong(_) => (CLIENT_CODESPACE, 3),
+            Self::ClientStateIsFrozen => (CLIENT_CODESPACE, 5),
+            Self::ConsensusStateIsEmpty
+            | Self::ConsensusStateMismatch
+            | Self::EmptyConsensusStatePublicKey => (CLIENT_CODESPACE, 8),
+            Self::MisbehaviourSignaturesCannotEqual | Self::MisbehaviourDataCannotEqual => {
+                (CLIENT_CODESPACE, 13)
+            }
+            _ => (UNDEFINED_CODESPACE, 1),
+        }
+    }
+
+    /// Maps an error raised while verifying a header to the ibc-core error.
+    pub fn into_header_error(self) -> ClientError {
+        match self {
+            Self::ClientStateIsFrozen | Self::ClientError(_) => self.into(),
+            e => ClientError::HeaderVerificationFailure {
+                reason: e.to_string(),
+            },
+        }
+    }
+
+    /// Maps an error raised while verifying misbehaviour to the ibc-core error.
+    pub fn into_misbehaviour_error(self) -> ClientError {
+        match self {
+            Self::ClientStateIsFrozen | Self::ClientError(_) => self.into(),
+            e => ClientError::MisbehaviourHandlingFailure {
+                reason: e.to_string(),
+            },
+        }
+    }
+}
+
 impl From<Error> for ClientError {
     fn from(e: Error) -> Self {
-        Self::ClientSpecific {
-            description: e.to_string(),
+        match e {
+            Error::ClientError(e) => e,
+            Error::ClientStateIsFrozen => Self::ClientFrozen {
+                description: e.to_string(),
+            },
+            Error::SequenceMismatch { expected, got } => Self::InvalidProofHeight {
+                latest_height: expected,
+                proof_height: got,
+            },
+            e => Self::ClientSpecific {
+                description: e.to_string(),
+            },
         }
     }
 }
@@ -63,3 +281,82 @@ impl From<Error> for ClientError {
 pub trait IntoResult<T, E> {
     fn into_result(self) -> Result<T, E>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_abci_codes() {
+        let timestamp = Timestamp::none();
+        let cases: Vec<(Error, &str, u32)> = vec![
+            (
+                Error::HeaderTimestampRegression {
+                    timestamp,
+                    consensus_timestamp: timestamp,
+                },
+                SOLOMACHINE_CODESPACE,
+                2,
+            ),
+            (
+                Error::SequenceMismatch {
+                    expected: Height::new(0, 2).unwrap(),
+                    got: Height::new(0, 1).unwrap(),
+                },
+                SOLOMACHINE_CODESPACE,
+                3,
+            ),
+            (Error::SignatureCannotEmpty, SOLOMACHINE_CODESPACE, 4),
+            (
+                Error::SignatureVerificationFailed {
+                    reason: String::new(),
+                }
+                .for_signature(SignatureKind::Proof),
+                SOLOMACHINE_CODESPACE,
+                5,
+            ),
+            (
+                Error::MultiSignatureUnsupported.for_signature(SignatureKind::Header),
+                SOLOMACHINE_CODESPACE,
+                2,
+            ),
+            (
+                Error::MultiSignatureUnsupported.for_signature(SignatureKind::MisbehaviourTwo),
+                SOLOMACHINE_CODESPACE,
+                4,
+            ),
+            (
+                Error::HeaderTimestampTooFarInFuture {
+                    timestamp,
+                    host_timestamp: timestamp,
+                    max_clock_drift: Duration::ZERO,
+                },
+                SOLOMACHINE_CODESPACE,
+                2,
+            ),
+            (
+                Error::TimestampTooFarInFuture {
+                    timestamp,
+                    host_timestamp: timestamp,
+                    max_clock_drift: Duration::ZERO,
+                },
+                SOLOMACHINE_CODESPACE,
+                6,
+            ),
+            (Error::ProofCannotEmpty, SOLOMACHINE_CODESPACE, 6),
+            (Error::ClientStateIsFrozen, CLIENT_CODESPACE, 5),
+            (Error::MisbehaviourDataCannotEqual, CLIENT_CODESPACE, 13),
+            (Error::Other(String::new()), UNDEFINED_CODESPACE, 1),
+        ];
+        for (error, codespace, code) in cases {
+            assert_eq!(
+                (error.codespace(), error.code()),
+                (codespace, code),
+                "{}",
+                error
+            );
+        }
+    }
+}
@@ -2,6 +2,7 @@
 
 use crate::cosmos::crypto::PublicKey;
 use crate::error::Error;
+use crate::limits::VerificationLimits;
 use alloc::string::ToString;
 use bytes::Buf;
 use core::fmt::{Display, Error as FmtError, Formatter};
@@ -15,8 +16,24 @@ use prost::Message;
 
 pub const SOLOMACHINE_HEADER_TYPE_URL: &str = "/ibc.lightclients.solomachine.v3.Header";
 
+/// SentinelHeaderPath defines a placeholder path value used for headers in solomachine client updates
+pub const SENTINEL_HEADER_PATH: &str = "solomachine:header";
+
 /// Header defines a solo machine consensus header
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
 #[derive(Clone, PartialEq)]
 pub struct Header {
     /// Timestamp of the consensus state
@@ -48,6 +65,49 @@ impl Display for Header {
     }
 }
 
+impl Header {
+    // ValidateBasic ensures that the timestamp and signature are non-empty and
+    // that the new diversifier is not only whitespace.
+    pub fn validate_basic(&self) -> Result<(), Error> {
+        if self.timestamp.into_tm_time().is_none() {
+            return Err(Error::TimeStampIsEmpty);
+        }
+
+        if !self.new_diversifier.is_empty() && self.new_diversifier.trim().is_empty() {
+            return Err(Error::DriversifierContainOnlySpaces);
+        }
+
+        if self.signature.is_empty() {
+            return Err(Error::SignatureCannotEmpty);
+        }
+
+        Ok(())
+    }
+
+    #[deprecated(since = "0.1.4", note = "use `validate_basic` instead")]
+    pub fn valida_basic(&self) -> Result<(), Error> {
+        self.validate_basic()
+    }
+
+    /// Decodes a header from an `Any`, rejecting it before decoding if the
+    /// encoded message is larger than `limits` allow, and after decoding if
+    /// its signature or new diversifier are.
+    pub fn decode_with_limits(raw: Any, limits: &VerificationLimits) -> Result<Self, Error> {
+        if raw.type_url != SOLOMACHINE_HEADER_TYPE_URL {
+            return Err(Error::ClientError(ClientError::UnknownHeaderType {
+                header_type: raw.type_url,
+            }));
+        }
+        limits.check_message(raw.value.len())?;
+
+        let header = decode_header(raw.value.as_slice())?;
+        limits.check_signature(&header.signature)?;
+        limits.check_diversifier(&header.new_diversifier)?;
+
+        Ok(header)
+    }
+}
+
 impl Protobuf<RawSmHeader> for Header {}
 
 impl TryFrom<RawSmHeader> for Header {
@@ -23,18 +23,32 @@ extern crate alloc;
 #[cfg(any(test, feature = "std"))]
 extern crate std;
 
+#[cfg(feature = "candid")]
+pub mod candid_types;
 pub mod client_state;
 pub mod consensus_state;
 pub mod cosmos;
 pub mod error;
 pub mod header;
+pub mod limits;
 pub mod misbehaviour;
+pub mod msgs;
 pub mod proof;
+#[cfg(feature = "serde")]
+pub mod proto_json;
+pub mod time_policy;
+pub mod v1;
+pub mod v2;
+pub mod versioned;
 
 pub use client_state::*;
 pub use consensus_state::*;
 pub use header::*;
+pub use limits::VerificationLimits;
 pub use misbehaviour::*;
+pub use msgs::MsgBuilder;
+pub use time_policy::TimePolicy;
+pub use versioned::{SolomachineAny, Version};
 
 /// Re-exports necessary proto types for Solomachine light client implementation
 /// from `ibc-proto` crate.
@@ -60,4 +74,217 @@ mod tests {
     pub fn test_sm_client_type() {
         let _ = ClientType::from_str(SOLOMACHINE_CLIENT_TYPE).unwrap();
     }
+
+    #[cfg(feature = "parity-scale-codec")]
+    #[test]
+    fn test_scale_round_trip() {
+        use crate::cosmos::crypto::PublicKey;
+        use crate::proof::types::signature_and_data::SignatureAndData;
+        use alloc::vec;
+        use core::time::Duration;
+        use ibc_core::client::types::Height;
+        use ibc_core::primitives::Timestamp;
+        use parity_scale_codec::{Decode, Encode};
+        use scale_info::TypeInfo;
+
+        fn round_trip<T: Encode + Decode + PartialEq>(value: &T) -> T {
+            let decoded = T::decode(&mut value.encode().as_slice()).unwrap();
+            assert!(decoded == *value);
+            decoded
+        }
+
+        const EXAMPLE_JSON: &str = "{\"@type\":\"/cosmos.crypto.ed25519.PubKey\",\"key\":\"sEEsVGkXvyewKLWMJbHVDRkBoerW0IIwmj1rHkabtHU=\"}";
+        let public_key = EXAMPLE_JSON.parse::<PublicKey>().unwrap();
+        let timestamp = Timestamp::from_nanoseconds(10).unwrap();
+        let sequence = Height::new(0, 3).unwrap();
+        let consensus_state = ConsensusState::new(public_key, "testing".into(), timestamp);
+        let signature_and_data = |data: u8| SignatureAndData {
+            signature: vec![1, data],
+            path: b"path".to_vec(),
+            data: vec![data],
+            timestamp,
+        };
+
+        round_trip(&public_key);
+        round_trip(&consensus_state);
+        round_trip(&signature_and_data(1));
+        round_trip(&Header {
+            timestamp,
+            signature: vec![1, 2, 3],
+            new_public_key: public_key,
+            new_diversifier: "testing".into(),
+        });
+        round_trip(&Misbehaviour {
+            sequence,
+            signature_one: signature_and_data(1),
+            signature_two: signature_and_data(2),
+        });
+        round_trip(
+            &ClientState::new(sequence, false, consensus_state)
+                .with_inactivity_period(Duration::from_secs(3600)),
+        );
+
+        // unknown public key types are rejected
+        let encoded = (
+            alloc::string::String::from("/unknown.PubKey"),
+            public_key.to_bytes(),
+        )
+            .encode();
+        assert!(PublicKey::decode(&mut encoded.as_slice()).is_err());
+
+        assert_eq!(PublicKey::type_info().path.ident(), Some("PublicKey"));
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn test_borsh_encoding() {
+        use crate::cosmos::crypto::PublicKey;
+        use crate::proof::types::sign_bytes::SignBytes;
+        use crate::proof::types::signature_and_data::SignatureAndData;
+        use crate::v2::DataType;
+        use alloc::vec;
+        use alloc::vec::Vec;
+        use borsh::{BorshDeserialize, BorshSerialize};
+        use core::time::Duration;
+        use ibc_core::client::types::Height;
+        use ibc_core::primitives::Timestamp;
+        use subtle_encoding::hex;
+
+        fn round_trip<T: BorshSerialize + BorshDeserialize + PartialEq>(value: &T) -> Vec<u8> {
+            let encoded = value.try_to_vec().unwrap();
+            assert!(T::try_from_slice(&encoded).unwrap() == *value);
+            encoded
+        }
+
+        // Golden encodings: these must not change between releases, or
+        // persisted values become unreadable.
+        const EXAMPLE_JSON: &str = "{\"@type\":\"/cosmos.crypto.ed25519.PubKey\",\"key\":\"sEEsVGkXvyewKLWMJbHVDRkBoerW0IIwmj1rHkabtHU=\"}";
+        let public_key = EXAMPLE_JSON.parse::<PublicKey>().unwrap();
+        assert_eq!(
+            round_trip(&public_key),
+            hex::decode("1d0000002f636f736d6f732e63727970746f2e656432353531392e5075624b657920000000b0412c546917bf27b028b58c25b1d50d1901a1ead6d082309a3d6b1e469bb475").unwrap()
+        );
+        assert_eq!(
+            round_trip(&SignBytes {
+                sequence: 1,
+                timestamp: 10,
+                diversifier: "testing".into(),
+                path: b"path".to_vec(),
+                data: vec![1, 2],
+            }),
+            hex::decode("01000000000000000a000000000000000700000074657374696e670400000070617468020000000102").unwrap()
+        );
+        assert_eq!(
+            round_trip(&v2::SignBytes {
+                sequence: 1,
+                timestamp: 10,
+                diversifier: "testing".into(),
+                data_type: DataType::PacketCommitment,
+                data: vec![1, 2],
+            }),
+            hex::decode("01000000000000000a000000000000000700000074657374696e6705020000000102")
+                .unwrap()
+        );
+
+        let timestamp = Timestamp::from_nanoseconds(10).unwrap();
+        let sequence = Height::new(0, 3).unwrap();
+        let consensus_state = ConsensusState::new(public_key, "testing".into(), timestamp);
+        let signature_and_data = |data: u8| SignatureAndData {
+            signature: vec![1, data],
+            path: b"path".to_vec(),
+            data: vec![data],
+            timestamp,
+        };
+        assert_eq!(
+            round_trip(&consensus_state),
+            hex::decode(concat!(
+                "1d0000002f636f736d6f732e63727970746f2e656432353531392e5075624b6579",
+                "20000000b0412c546917bf27b028b58c25b1d50d1901a1ead6d082309a3d6b1e469bb475",
+                "0700000074657374696e67",
+                "0a00000000000000",
+                "20000000b0412c546917bf27b028b58c25b1d50d1901a1ead6d082309a3d6b1e469bb475",
+            ))
+            .unwrap()
+        );
+        assert_eq!(
+            round_trip(&Header {
+                timestamp,
+                signature: vec![1, 2, 3],
+                new_public_key: public_key,
+                new_diversifier: "testing".into(),
+            }),
+            hex::decode(concat!(
+                "0a00000000000000",
+                "03000000010203",
+                "1d0000002f636f736d6f732e63727970746f2e656432353531392e5075624b6579",
+                "20000000b0412c546917bf27b028b58c25b1d50d1901a1ead6d082309a3d6b1e469bb475",
+                "0700000074657374696e67",
+            ))
+            .unwrap()
+        );
+        assert_eq!(
+            round_trip(&Misbehaviour {
+                sequence,
+                signature_one: signature_and_data(1),
+                signature_two: signature_and_data(2),
+            }),
+            hex::decode(concat!(
+                "00000000000000000300000000000000",
+                "020000000101",
+                "0400000070617468",
+                "0100000001",
+                "0a00000000000000",
+                "020000000102",
+                "0400000070617468",
+                "0100000002",
+                "0a00000000000000",
+            ))
+            .unwrap()
+        );
+
+        // the extension fields are encoded last, unset as `None` and `false`
+        assert_eq!(
+            round_trip(&ClientState::new(sequence, false, consensus_state.clone())),
+            hex::decode(concat!(
+                "00000000000000000300000000000000",
+                "00",
+                "1d0000002f636f736d6f732e63727970746f2e656432353531392e5075624b6579",
+                "20000000b0412c546917bf27b028b58c25b1d50d1901a1ead6d082309a3d6b1e469bb475",
+                "0700000074657374696e67",
+                "0a00000000000000",
+                "20000000b0412c546917bf27b028b58c25b1d50d1901a1ead6d082309a3d6b1e469bb475",
+                "00",
+                "00",
+            ))
+            .unwrap()
+        );
+        // an inactivity period is encoded as its seconds and subsecond nanoseconds
+        assert_eq!(
+            round_trip(
+                &ClientState::new(sequence, true, consensus_state)
+                    .with_inactivity_period(Duration::from_millis(1500))
+            ),
+            hex::decode(concat!(
+                "00000000000000000300000000000000",
+                "01",
+                "1d0000002f636f736d6f732e63727970746f2e656432353531392e5075624b6579",
+                "20000000b0412c546917bf27b028b58c25b1d50d1901a1ead6d082309a3d6b1e469bb475",
+                "0700000074657374696e67",
+                "0a00000000000000",
+                "20000000b0412c546917bf27b028b58c25b1d50d1901a1ead6d082309a3d6b1e469bb475",
+                "0101000000000000000065cd1d",
+                "00",
+            ))
+            .unwrap()
+        );
+
+        // unknown public key types are rejected
+        let encoded = (
+            alloc::string::String::from("/unknown.PubKey"),
+            public_key.to_bytes(),
+        )
+            .try_to_vec()
+            .unwrap();
+        assert!(PublicKey::try_from_slice(&encoded).is_err());
+    }
 }
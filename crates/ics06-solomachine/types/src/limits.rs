@@ -0,0 +1,274 @@
+//! Size limits applied to untrusted solo machine inputs before they are
+//! decoded, hashed or verified.
+
+use crate::error::Error;
+use ibc_proto::ibc::core::commitment::v1::MerklePath;
+
+/// Upper bounds on the size of the proofs, headers and misbehaviours a solo
+/// machine client accepts.
+///
+/// The limits are host configuration rather than client state: they are not
+/// stored with a client, so a host changes them for every client at once and
+/// they cannot be lost when a client state is encoded and decoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VerificationLimits {
+    /// maximum size of an encoded proof, header or misbehaviour, and of the
+    /// sign bytes built from them
+    pub max_message_size: usize,
+    /// maximum size of an encoded `SignatureDescriptor.Data`
+    pub max_signature_size: usize,
+    /// maximum size of the data a signature is made over
+    pub max_data_size: usize,
+    /// maximum number of `MerklePath` key path segments
+    pub max_path_segments: usize,
+    /// maximum size of a path, summed over its segments
+    pub max_path_size: usize,
+    /// maximum size of a diversifier
+    pub max_diversifier_size: usize,
+}
+
+impl Default for VerificationLimits {
+    fn default() -> Self {
+        Self {
+            max_message_size: 64 * 1024,
+            max_signature_size: 1024,
+            max_data_size: 16 * 1024,
+            max_path_segments: 8,
+            max_path_size: 1024,
+            max_diversifier_size: 256,
+        }
+    }
+}
+
+impl VerificationLimits {
+    /// Checks the size of an encoded proof, header, misbehaviour or sign bytes.
+    pub fn check_message(&self, size: usize) -> Result<(), Error> {
+        check("message", size, self.max_message_size)
+    }
+
+    /// Checks the size of an encoded signature.
+    pub fn check_signature(&self, signature: &[u8]) -> Result<(), Error> {
+        check("signature", signature.len(), self.max_signature_size)
+    }
+
+    /// Checks the size of signed data.
+    pub fn check_data(&self, data: &[u8]) -> Result<(), Error> {
+        check("data", data.len(), self.max_data_size)
+    }
+
+    /// Checks the size of a raw path, such as a `SignatureAndData` path.
+    pub fn check_path(&self, path: &[u8]) -> Result<(), Error> {
+        check("path", path.len(), self.max_path_size)
+    }
+
+    /// Checks the number of segments and the total size of a merkle path.
+    pub fn check_merkle_path(&self, merkle_path: &MerklePath) -> Result<(), Error> {
+        check(
+            "path segments",
+            merkle_path.key_path.len(),
+            self.max_path_segments,
+        )?;
+        check(
+            "path",
+            merkle_path.key_path.iter().map(|key| key.len()).sum(),
+            self.max_path_size,
+        )
+    }
+
+    /// Checks the size of a diversifier.
+    pub fn check_diversifier(&self, diversifier: &str) -> Result<(), Error> {
+        check("diversifier", diversifier.len(), self.max_diversifier_size)
+    }
+}
+
+fn check(field: &'static str, size: usize, limit: usize) -> Result<(), Error> {
+    if size > limit {
+        return Err(Error::InputTooLarge { field, size, limit });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cosmos::crypto::PublicKey;
+    use crate::proof::types::signature_and_data::SignatureAndData;
+    use crate::proof::types::timestamped_signature_data::TimestampedSignatureData;
+    use crate::proof::{encode_signature_data, verify_signature_with_limits};
+    use crate::{ClientState, ConsensusState, Header, Misbehaviour};
+    use alloc::string::ToString;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use ibc_core::client::types::Height;
+    use ibc_core::commitment_types::commitment::CommitmentProofBytes;
+    use ibc_core::primitives::Timestamp;
+    use ibc_proto::google::protobuf::Any;
+    use ibc_proto::Protobuf;
+
+    const PUBLIC_KEY_JSON: &str = "{\"@type\":\"/cosmos.crypto.ed25519.PubKey\",\"key\":\"sEEsVGkXvyewKLWMJbHVDRkBoerW0IIwmj1rHkabtHU=\"}";
+
+    fn limits() -> VerificationLimits {
+        VerificationLimits {
+            max_message_size: 512,
+            max_signature_size: 128,
+            max_data_size: 64,
+            max_path_segments: 2,
+            max_path_size: 64,
+            max_diversifier_size: 16,
+        }
+    }
+
+    fn assert_too_large<T>(result: Result<T, Error>, expected: &str) {
+        match result {
+            Err(Error::InputTooLarge { field, .. }) => assert_eq!(field, expected),
+            Err(e) => panic!("expected {} to be too large, got {}", expected, e),
+            Ok(_) => panic!("expected {} to be too large", expected),
+        }
+    }
+
+    fn header(signature: Vec<u8>, new_diversifier: &str) -> Header {
+        Header {
+            timestamp: Timestamp::from_nanoseconds(10).unwrap(),
+            signature,
+            new_public_key: PUBLIC_KEY_JSON.parse::<PublicKey>().unwrap(),
+            new_diversifier: new_diversifier.into(),
+        }
+    }
+
+    fn signature_and_data(data: Vec<u8>) -> SignatureAndData {
+        SignatureAndData {
+            signature: encode_signature_data(vec![1; 64]),
+            path: b"/ibc/connections/connection-0".to_vec(),
+            data,
+            timestamp: Timestamp::from_nanoseconds(10).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_header_decode_with_limits() {
+        let signature = encode_signature_data(vec![1; 64]);
+        let accepted = header(signature.clone(), "testing");
+        assert_eq!(
+            Header::decode_with_limits(Any::from(accepted.clone()), &limits()).unwrap(),
+            accepted
+        );
+
+        let oversized = header(encode_signature_data(vec![1; 256]), "testing");
+        assert_too_large(
+            Header::decode_with_limits(Any::from(oversized), &limits()),
+            "signature",
+        );
+
+        let oversized = header(signature, &"d".repeat(17));
+        assert_too_large(
+            Header::decode_with_limits(Any::from(oversized), &limits()),
+            "diversifier",
+        );
+
+        let oversized = header(encode_signature_data(vec![1; 1024]), "testing");
+        assert_too_large(
+            Header::decode_with_limits(Any::from(oversized), &limits()),
+            "message",
+        );
+    }
+
+    #[test]
+    fn test_misbehaviour_decode_with_limits() {
+        let misbehaviour = |data_two: Vec<u8>| Misbehaviour {
+            sequence: Height::new(0, 1).unwrap(),
+            signature_one: signature_and_data(vec![1; 32]),
+            signature_two: signature_and_data(data_two),
+        };
+
+        let accepted = misbehaviour(vec![2; 32]);
+        assert!(
+            Misbehaviour::decode_with_limits(Any::from(accepted.clone()), &limits()).unwrap()
+                == accepted
+        );
+
+        assert_too_large(
+            Misbehaviour::decode_with_limits(Any::from(misbehaviour(vec![2; 65])), &limits()),
+            "data",
+        );
+
+        let mut oversized = misbehaviour(vec![2; 32]);
+        oversized.signature_one.path = vec![b'p'; 65];
+        assert_too_large(
+            Misbehaviour::decode_with_limits(Any::from(oversized), &limits()),
+            "path",
+        );
+    }
+
+    #[test]
+    fn test_produce_verification_args_with_limits() {
+        let consensus_state = ConsensusState::new(
+            PUBLIC_KEY_JSON.parse::<PublicKey>().unwrap(),
+            "testing".to_string(),
+            Timestamp::from_nanoseconds(1).unwrap(),
+        );
+        let client_state = ClientState::new(Height::new(0, 1).unwrap(), false, consensus_state);
+
+        let proof = |signature: Vec<u8>| -> CommitmentProofBytes {
+            TimestampedSignatureData {
+                signature_data: encode_signature_data(signature),
+                timestamp: Timestamp::from_nanoseconds(10).unwrap(),
+            }
+            .encode_vec()
+            .try_into()
+            .unwrap()
+        };
+
+        client_state
+            .produce_verification_args(&proof(vec![1; 64]), &limits())
+            .unwrap();
+        assert_too_large(
+            client_state.produce_verification_args(&proof(vec![1; 256]), &limits()),
+            "signature",
+        );
+        assert_too_large(
+            client_state.produce_verification_args(&proof(vec![1; 1024]), &limits()),
+            "message",
+        );
+    }
+
+    #[test]
+    fn test_verify_signature_with_limits() {
+        let public_key = PUBLIC_KEY_JSON.parse::<PublicKey>().unwrap();
+        assert_too_large(
+            verify_signature_with_limits(
+                public_key,
+                vec![0; 16],
+                encode_signature_data(vec![1; 256]),
+                &limits(),
+            ),
+            "signature",
+        );
+        assert_too_large(
+            verify_signature_with_limits(
+                public_key,
+                vec![0; 1024],
+                encode_signature_data(vec![1; 64]),
+                &limits(),
+            ),
+            "message",
+        );
+    }
+
+    #[test]
+    fn test_check_merkle_path() {
+        let path = |key_path: Vec<&str>| MerklePath {
+            key_path: key_path.into_iter().map(Into::into).collect(),
+        };
+        limits()
+            .check_merkle_path(&path(vec!["ibc", "connections/connection-0"]))
+            .unwrap();
+        assert_too_large(
+            limits().check_merkle_path(&path(vec!["a", "b", "c"])),
+            "path segments",
+        );
+        assert_too_large(
+            limits().check_merkle_path(&path(vec!["ibc", &"k".repeat(64)])),
+            "path",
+        );
+    }
+}
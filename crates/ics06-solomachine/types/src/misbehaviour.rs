@@ -1,6 +1,7 @@
 //! Defines the misbehaviour type for the solomachine light client
 
 use crate::error::Error;
+use crate::limits::VerificationLimits;
 use crate::proof::types::signature_and_data::SignatureAndData;
 use bytes::Buf;
 use ibc_core::client::types::error::ClientError;
@@ -16,6 +17,19 @@ pub const SOLOMACHINE_MISBEHAVIOUR_TYPE_URL: &str = "/ibc.lightclients.solomachi
 /// Misbehaviour defines misbehaviour for a solo machine which consists
 /// of a sequence and two signatures over different messages at that sequence.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
 #[derive(Clone, PartialEq)]
 pub struct Misbehaviour {
     /// The sequence number at which the infraction occurred
@@ -26,6 +40,56 @@ pub struct Misbehaviour {
     pub signature_two: SignatureAndData,
 }
 
+impl Misbehaviour {
+    // ValidateBasic implements Misbehaviour interface.
+    pub fn validate_basic(&self) -> Result<(), Error> {
+        if self.sequence.revision_height() == 0 {
+            return Err(Error::SequenceCannotZero);
+        }
+
+        self.signature_one.validate_basic()?;
+        self.signature_two.validate_basic()?;
+
+        // misbehaviour signatures cannot be equal
+        if self.signature_one.signature == self.signature_two.signature {
+            return Err(Error::MisbehaviourSignaturesCannotEqual);
+        }
+
+        // message data signed cannot be equal
+        if self.signature_one.data == self.signature_two.data {
+            return Err(Error::MisbehaviourDataCannotEqual);
+        }
+
+        Ok(())
+    }
+
+    #[deprecated(since = "0.1.4", note = "use `validate_basic` instead")]
+    pub fn valida_basic(&self) -> Result<(), Error> {
+        self.validate_basic()
+    }
+
+    /// Decodes a misbehaviour from an `Any`, rejecting it before decoding if
+    /// the encoded message is larger than `limits` allow, and after decoding
+    /// if either signature, path or data is.
+    pub fn decode_with_limits(raw: Any, limits: &VerificationLimits) -> Result<Self, Error> {
+        if raw.type_url != SOLOMACHINE_MISBEHAVIOUR_TYPE_URL {
+            return Err(Error::ClientError(ClientError::UnknownMisbehaviourType {
+                misbehaviour_type: raw.type_url,
+            }));
+        }
+        limits.check_message(raw.value.len())?;
+
+        let misbehaviour = decode_misbehaviour(raw.value.as_slice())?;
+        for signature_and_data in [&misbehaviour.signature_one, &misbehaviour.signature_two] {
+            limits.check_signature(&signature_and_data.signature)?;
+            limits.check_path(&signature_and_data.path)?;
+            limits.check_data(&signature_and_data.data)?;
+        }
+
+        Ok(misbehaviour)
+    }
+}
+
 impl Protobuf<RawSmMisbehaviour> for Misbehaviour {}
 
 impl TryFrom<RawSmMisbehaviour> for Misbehaviour {
@@ -107,7 +171,7 @@ impl core::fmt::Display for Misbehaviour {
         write!(
             f,
             "Sequence({}), SignatureOne({}), SignatureTwo({})",
-            self.sequence, self.signature_two, self.signature_two
+            self.sequence, self.signature_one, self.signature_two
         )
     }
 }
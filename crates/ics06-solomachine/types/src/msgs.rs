@@ -0,0 +1,211 @@
+//! Builders for the ICS-02 client messages a relayer submits for a solo
+//! machine client.
+
+use crate::client_state::ClientState;
+use crate::consensus_state::ConsensusState;
+use crate::error::Error;
+use crate::header::Header;
+use crate::misbehaviour::Misbehaviour;
+use ibc_core::host::types::identifiers::ClientId;
+use ibc_core::primitives::prelude::*;
+use ibc_core::primitives::Signer;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::core::client::v1::{
+    MsgCreateClient, MsgRecoverClient, MsgSubmitMisbehaviour, MsgUpdateClient,
+};
+
+/// Builds ready-to-broadcast client messages on behalf of a signer,
+/// validating the solo machine payload before it is wrapped.
+#[derive(Clone, Debug)]
+pub struct MsgBuilder {
+    signer: Signer,
+}
+
+impl MsgBuilder {
+    /// Create a new builder for the given signer address.
+    pub fn new(signer: Signer) -> Self {
+        Self { signer }
+    }
+
+    /// The signer of every message built.
+    pub fn signer(&self) -> &Signer {
+        &self.signer
+    }
+
+    /// Build a `MsgCreateClient`. The consensus state must be the one the
+    /// client state carries, and the client must not be frozen.
+    pub fn create_client(
+        &self,
+        client_state: ClientState,
+        consensus_state: ConsensusState,
+    ) -> Result<MsgCreateClient, Error> {
+        client_state.validate_basic()?;
+        consensus_state.validate_basic()?;
+
+        if client_state.is_frozen {
+            return Err(Error::ClientStateIsFrozen);
+        }
+
+        let expected = &client_state.consensus_state;
+        if expected.public_key != consensus_state.public_key
+            || expected.diversifier != consensus_state.diversifier
+            || expected.timestamp != consensus_state.timestamp
+        {
+            return Err(Error::ConsensusStateMismatch);
+        }
+
+        Ok(MsgCreateClient {
+            client_state: Some(Any::from(client_state)),
+            consensus_state: Some(Any::from(consensus_state)),
+            signer: self.signer.to_string(),
+        })
+    }
+
+    /// Build a `MsgUpdateClient` carrying a solo machine header.
+    pub fn update_client(
+        &self,
+        client_id: &ClientId,
+        header: Header,
+    ) -> Result<MsgUpdateClient, Error> {
+        header.validate_basic()?;
+
+        Ok(MsgUpdateClient {
+            client_id: client_id.to_string(),
+            client_message: Some(Any::from(header)),
+            signer: self.signer.to_string(),
+        })
+    }
+
+    /// Build a `MsgSubmitMisbehaviour` carrying solo machine misbehaviour.
+    #[allow(deprecated)]
+    pub fn submit_misbehaviour(
+        &self,
+        client_id: &ClientId,
+        misbehaviour: Misbehaviour,
+    ) -> Result<MsgSubmitMisbehaviour, Error> {
+        misbehaviour.validate_basic()?;
+
+        Ok(MsgSubmitMisbehaviour {
+            client_id: client_id.to_string(),
+            misbehaviour: Some(Any::from(misbehaviour)),
+            signer: self.signer.to_string(),
+        })
+    }
+
+    /// Build a `MsgRecoverClient` replacing a frozen or expired subject
+    /// client with a substitute.
+    pub fn recover_client(
+        &self,
+        subject_client_id: &ClientId,
+        substitute_client_id: &ClientId,
+    ) -> Result<MsgRecoverClient, Error> {
+        if subject_client_id == substitute_client_id {
+            return Err(Error::SameSubjectAndSubstitute {
+                client_id: subject_client_id.to_string(),
+            });
+        }
+
+        Ok(MsgRecoverClient {
+            subject_client_id: subject_client_id.to_string(),
+            substitute_client_id: substitute_client_id.to_string(),
+            signer: self.signer.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MsgBuilder;
+    use crate::client_state::ClientState;
+    use crate::consensus_state::ConsensusState;
+    use crate::cosmos::crypto::PublicKey;
+    use crate::error::Error;
+    use crate::header::Header;
+    use crate::misbehaviour::Misbehaviour;
+    use crate::proof::types::signature_and_data::SignatureAndData;
+    use alloc::string::ToString;
+    use alloc::vec;
+    use ibc_core::client::types::Height;
+    use ibc_core::host::types::identifiers::ClientId;
+    use ibc_core::primitives::{Signer, Timestamp};
+
+    const PUBLIC_KEY_JSON: &str = "{\"@type\":\"/cosmos.crypto.ed25519.PubKey\",\"key\":\"sEEsVGkXvyewKLWMJbHVDRkBoerW0IIwmj1rHkabtHU=\"}";
+
+    fn fixture() -> (MsgBuilder, ClientId, ConsensusState) {
+        let builder = MsgBuilder::new(Signer::from("cosmos1relayer".to_string()));
+        let client_id = ClientId::new(crate::client_type(), 0).unwrap();
+        let consensus_state = ConsensusState::new(
+            PUBLIC_KEY_JSON.parse::<PublicKey>().unwrap(),
+            "diversifier".into(),
+            Timestamp::from_nanoseconds(1).unwrap(),
+        );
+        (builder, client_id, consensus_state)
+    }
+
+    #[test]
+    fn build_messages() {
+        let (builder, client_id, consensus_state) = fixture();
+        let client_state =
+            ClientState::new(Height::new(0, 1).unwrap(), false, consensus_state.clone());
+
+        let msg = builder
+            .create_client(client_state.clone(), consensus_state.clone())
+            .unwrap();
+        assert_eq!(msg.signer, "cosmos1relayer");
+
+        let mut other = consensus_state.clone();
+        other.diversifier = "other".into();
+        assert!(matches!(
+            builder.create_client(client_state, other),
+            Err(Error::ConsensusStateMismatch)
+        ));
+
+        let header = Header {
+            timestamp: Timestamp::from_nanoseconds(2).unwrap(),
+            signature: vec![1, 2, 3],
+            new_public_key: consensus_state.public_key,
+            new_diversifier: "diversifier".into(),
+        };
+        let msg = builder.update_client(&client_id, header.clone()).unwrap();
+        assert_eq!(msg.client_id, client_id.to_string());
+        assert!(matches!(
+            builder.update_client(
+                &client_id,
+                Header {
+                    signature: vec![],
+                    ..header
+                }
+            ),
+            Err(Error::SignatureCannotEmpty)
+        ));
+
+        let signature = |signature: u8, data: u8| SignatureAndData {
+            signature: vec![signature],
+            path: b"/ibc".to_vec(),
+            data: vec![data],
+            timestamp: Timestamp::from_nanoseconds(2).unwrap(),
+        };
+        let misbehaviour = Misbehaviour {
+            sequence: Height::new(0, 1).unwrap(),
+            signature_one: signature(1, 1),
+            signature_two: signature(2, 2),
+        };
+        builder
+            .submit_misbehaviour(&client_id, misbehaviour.clone())
+            .unwrap();
+        assert!(matches!(
+            builder.submit_misbehaviour(
+                &client_id,
+                Misbehaviour {
+                    signature_two: signature(2, 1),
+                    ..misbehaviour
+                }
+            ),
+            Err(Error::MisbehaviourDataCannotEqual)
+        ));
+
+        let substitute = ClientId::new(crate::client_type(), 1).unwrap();
+        builder.recover_client(&client_id, &substitute).unwrap();
+        assert!(builder.recover_client(&client_id, &client_id).is_err());
+    }
+}
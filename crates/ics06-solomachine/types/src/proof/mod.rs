@@ -1,8 +1,13 @@
 use crate::cosmos::crypto::PublicKey;
 use crate::error::Error;
-use crate::proof::types::signature_and_data::SignatureAndData;
+use crate::limits::VerificationLimits;
 use alloc::format;
+use alloc::string::String;
 use alloc::vec::Vec;
+use ibc_proto::cosmos::tx::signing::v1beta1::signature_descriptor::data::{Single, Sum};
+use ibc_proto::cosmos::tx::signing::v1beta1::signature_descriptor::Data as RawSignatureData;
+use ibc_proto::ibc::core::commitment::v1::MerklePath;
+use prost::Message;
 use tendermint::crypto::signature::Verifier;
 use tendermint::Signature;
 
@@ -17,14 +22,68 @@ pub mod types;
 pub fn verify_signature(
     publik_key: PublicKey,
     sign_bytes: Vec<u8>,
-    signature_and_data: SignatureAndData,
+    signature_data: Vec<u8>,
 ) -> Result<(), Error> {
-    let signature = Signature::try_from(signature_and_data.signature)
-        .map_err(|e| Error::Other(format!("{}", e)))?;
+    let signature = Signature::try_from(decode_signature_data(&signature_data)?).map_err(|e| {
+        Error::MalformedSignature {
+            reason: format!("{}", e),
+        }
+    })?;
     tendermint::crypto::default::signature::Verifier::verify(
         publik_key.into(),
         &sign_bytes,
         &signature,
     )
-    .map_err(|e| Error::Other(format!("{}", e)))
+    .map_err(|e| Error::SignatureVerificationFailed {
+        reason: format!("{}", e),
+    })
+}
+
+// Verify_signature_with_limits is verify_signature with the signature data
+// and sign bytes checked against `limits` before they are decoded and hashed.
+pub fn verify_signature_with_limits(
+    publik_key: PublicKey,
+    sign_bytes: Vec<u8>,
+    signature_data: Vec<u8>,
+    limits: &VerificationLimits,
+) -> Result<(), Error> {
+    limits.check_signature(&signature_data)?;
+    limits.check_message(sign_bytes.len())?;
+    verify_signature(publik_key, sign_bytes, signature_data)
+}
+
+// Encode_signature_data marshals a raw signature into the
+// `cosmos.tx.signing.v1beta1.SignatureDescriptor.Data` carried by proofs,
+// header signatures and misbehaviour `SignatureAndData` signatures.
+// ref: https://github.com/cosmos/ibc-go/blob/v7.3.0/testing/solomachine.go#L467
+pub fn encode_signature_data(signature: Vec<u8>) -> Vec<u8> {
+    RawSignatureData {
+        sum: Some(Sum::Single(Single { mode: 0, signature })),
+    }
+    .encode_to_vec()
+}
+
+// Decode_signature_data unmarshals a `SignatureDescriptor.Data` and returns the
+// raw signature. Only single signatures are supported.
+// ref: https://github.com/cosmos/ibc-go/blob/v7.3.0/modules/light-clients/06-solomachine/codec.go#L18
+pub fn decode_signature_data(signature_data: &[u8]) -> Result<Vec<u8>, Error> {
+    let raw = RawSignatureData::decode(signature_data).map_err(Error::UndecodableSignatureData)?;
+    match raw.sum {
+        Some(Sum::Single(single)) => Ok(single.signature),
+        Some(Sum::Multi(_)) => Err(Error::MultiSignatureUnsupported),
+        None => Err(Error::SignatureCannotEmpty),
+    }
+}
+
+// Sign_bytes_path formats a prefixed merkle path the way `MerklePath.String()`
+// does in ibc-go, e.g. `/ibc/connections/connection-0`. Membership and
+// non-membership sign bytes commit to this path.
+// ref: https://github.com/cosmos/ibc-go/blob/v7.3.0/modules/light-clients/06-solomachine/client_state.go#L170
+pub fn sign_bytes_path(merkle_path: &MerklePath) -> Vec<u8> {
+    let mut path = String::new();
+    for key in &merkle_path.key_path {
+        path.push('/');
+        path.push_str(key);
+    }
+    path.into_bytes()
 }
@@ -1,13 +1,23 @@
 use crate::error::Error;
-use alloc::format;
-use alloc::string::String;
-use alloc::vec::Vec;
-use ibc_proto::ibc::core::commitment::v1::MerklePath;
+use ibc_core::primitives::prelude::*;
 use ibc_proto::ibc::lightclients::solomachine::v3::SignBytes as RawSignBytes;
 use ibc_proto::Protobuf;
-use prost::Message;
 
 /// SignBytes defines the signed bytes used for signature verification.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
 #[derive(Clone, PartialEq)]
 pub struct SignBytes {
     /// the sequence number
@@ -17,7 +27,7 @@ pub struct SignBytes {
     /// the public key diversifier
     pub diversifier: String,
     /// the standardised path bytes
-    pub path: MerklePath,
+    pub path: Vec<u8>,
     /// the marshaled data bytes
     pub data: Vec<u8>,
 }
@@ -28,13 +38,11 @@ impl TryFrom<RawSignBytes> for SignBytes {
     type Error = Error;
 
     fn try_from(raw: RawSignBytes) -> Result<Self, Self::Error> {
-        let path = MerklePath::decode(raw.path.as_ref())
-            .map_err(|e| Error::Other(format!("decode MerklePath Failed({})", e)))?;
         Ok(Self {
             sequence: raw.sequence,
             timestamp: raw.timestamp,
             diversifier: raw.diversifier,
-            path,
+            path: raw.path,
             data: raw.data,
         })
     }
@@ -46,7 +54,7 @@ impl From<SignBytes> for RawSignBytes {
             sequence: value.sequence,
             timestamp: value.timestamp,
             diversifier: value.diversifier,
-            path: value.path.encode_to_vec(),
+            path: value.path,
             data: value.data,
         }
     }
@@ -1,26 +1,60 @@
 use crate::error::Error;
-use alloc::format;
-use alloc::vec::Vec;
+use ibc_core::primitives::prelude::*;
 use ibc_core::primitives::Timestamp;
-use ibc_proto::ibc::core::commitment::v1::MerklePath;
 use ibc_proto::ibc::lightclients::solomachine::v3::SignatureAndData as RawSignatureAndData;
 use ibc_proto::Protobuf;
-use prost::Message;
 
 /// SignatureAndData contains a signature and the data signed over to create that
 /// signature.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
 #[derive(Clone, PartialEq)]
 pub struct SignatureAndData {
     /// the signature
     pub signature: Vec<u8>,
     /// the standardised path bytes
-    pub path: MerklePath,
+    pub path: Vec<u8>,
     /// the marshaled data bytes
     pub data: Vec<u8>,
     /// the proof timestamp
     pub timestamp: Timestamp,
 }
+impl SignatureAndData {
+    // ValidateBasic ensures that the signature and data fields are non-empty.
+    pub fn validate_basic(&self) -> Result<(), Error> {
+        if self.signature.is_empty() {
+            return Err(Error::SignatureCannotEmpty);
+        }
+        if self.data.is_empty() {
+            return Err(Error::DataCannotEmpty);
+        }
+        if self.path.is_empty() {
+            return Err(Error::PathCannotEmpty);
+        }
+        if self.timestamp.into_tm_time().is_none() {
+            return Err(Error::TimeStampIsEmpty);
+        }
+        Ok(())
+    }
+
+    #[deprecated(since = "0.1.4", note = "use `validate_basic` instead")]
+    pub fn valida_basic(&self) -> Result<(), Error> {
+        self.validate_basic()
+    }
+}
+
 impl core::fmt::Display for SignatureAndData {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
         write!(
@@ -41,11 +75,9 @@ impl TryFrom<RawSignatureAndData> for SignatureAndData {
         let data = raw.data;
         let timestamp =
             Timestamp::from_nanoseconds(raw.timestamp).map_err(Error::ParseTimeError)?;
-        let path = MerklePath::decode(raw.path.as_ref())
-            .map_err(|e| Error::Other(format!("decode MerklePath Failed({})", e)))?;
         Ok(Self {
             signature,
-            path,
+            path: raw.path,
             data,
             timestamp,
         })
@@ -56,7 +88,7 @@ impl From<SignatureAndData> for RawSignatureAndData {
     fn from(value: SignatureAndData) -> Self {
         Self {
             signature: value.signature,
-            path: value.path.encode_to_vec(),
+            path: value.path,
             data: value.data,
             timestamp: value.timestamp.nanoseconds(),
         }
@@ -1,16 +1,29 @@
 use crate::error::Error;
-use crate::proof::types::signature_and_data::SignatureAndData;
-use alloc::format;
+use ibc_core::primitives::prelude::*;
 use ibc_core::primitives::Timestamp;
 use ibc_proto::ibc::lightclients::solomachine::v3::TimestampedSignatureData as RawTimestampedSignatureData;
 use ibc_proto::Protobuf;
 
 /// TimestampedSignatureData contains the signature data and the timestamp of the
 /// signature.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
 #[derive(Clone, PartialEq)]
 pub struct TimestampedSignatureData {
-    /// the signature data
-    pub signature_data: SignatureAndData,
+    /// the marshaled `SignatureDescriptor.Data`
+    pub signature_data: Vec<u8>,
     /// the proof timestamp
     pub timestamp: Timestamp,
 }
@@ -22,8 +35,7 @@ impl TryFrom<RawTimestampedSignatureData> for TimestampedSignatureData {
 
     fn try_from(raw: RawTimestampedSignatureData) -> Result<Self, Self::Error> {
         Ok(Self {
-            signature_data: SignatureAndData::decode_vec(&raw.signature_data)
-                .map_err(|e| Error::Other(format!("decode SignatureAndData Error({})", e)))?,
+            signature_data: raw.signature_data,
             timestamp: Timestamp::from_nanoseconds(raw.timestamp).map_err(Error::ParseTimeError)?,
         })
     }
@@ -32,7 +44,7 @@ impl TryFrom<RawTimestampedSignatureData> for TimestampedSignatureData {
 impl From<TimestampedSignatureData> for RawTimestampedSignatureData {
     fn from(value: TimestampedSignatureData) -> Self {
         Self {
-            signature_data: value.signature_data.encode_vec(),
+            signature_data: value.signature_data,
             timestamp: value.timestamp.nanoseconds(),
         }
     }
@@ -0,0 +1,577 @@
+//! Canonical proto3 JSON encoding of the solo machine domain types.
+//!
+//! The serde derives of the domain types produce Rust-shaped JSON. [`ProtoJson`]
+//! instead produces the JSON ibc-go emits through its gRPC gateway and CLI
+//! (`jsonpb` with the original field names and defaults emitted): snake_case
+//! names, `uint64` values as strings, bytes as standard base64 and messages
+//! packed into an `Any` carrying their `@type`.
+//!
+//! Decoding also accepts what other proto3 JSON encoders emit: `uint64` values
+//! as numbers and field names in lowerCamelCase.
+//!
+//! The client state extension fields are emitted only when set, as ibc-go has
+//! no field for them.
+
+use crate::client_state::{ClientState, SOLOMACHINE_CLIENT_STATE_TYPE_URL};
+use crate::consensus_state::{ConsensusState, SOLOMACHINE_CONSENSUS_STATE_TYPE_URL};
+use crate::cosmos::crypto::public_key::{ED25519_TYPE_URL, SECP256K1_TYPE_URL};
+use crate::cosmos::crypto::{Algorithm, PublicKey};
+use crate::cosmos::error::Error as CosmosError;
+use crate::error::Error;
+use crate::header::{Header, SOLOMACHINE_HEADER_TYPE_URL};
+use crate::misbehaviour::{Misbehaviour, SOLOMACHINE_MISBEHAVIOUR_TYPE_URL};
+use crate::proof::types::signature_and_data::SignatureAndData;
+use core::time::Duration;
+use ibc_core::client::types::Height;
+use ibc_core::primitives::prelude::*;
+use ibc_core::primitives::Timestamp;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/// Conversion to and from canonical proto3 JSON.
+pub trait ProtoJson: Sized {
+    /// Encodes the value as proto3 JSON.
+    fn to_proto_json(&self) -> Result<String, Error>;
+
+    /// Decodes a value from proto3 JSON.
+    fn from_proto_json(json: &str) -> Result<Self, Error>;
+}
+
+fn invalid(reason: impl ToString) -> Error {
+    Error::InvalidProtoJson {
+        reason: reason.to_string(),
+    }
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String, Error> {
+    serde_json::to_string(value).map_err(invalid)
+}
+
+fn from_json<T: DeserializeOwned>(json: &str) -> Result<T, Error> {
+    serde_json::from_str(json).map_err(invalid)
+}
+
+fn check_type_url(type_url: Option<&str>, expected: &str) -> Result<(), Error> {
+    match type_url {
+        Some(type_url) if type_url == expected => Ok(()),
+        Some(type_url) => Err(invalid(format!(
+            "expected @type {expected}, got {type_url}"
+        ))),
+        None => Err(invalid(format!("missing @type {expected}"))),
+    }
+}
+
+/// `uint64` fields, encoded as decimal strings and decoded from decimal
+/// strings or numbers.
+mod u64_string {
+    use core::fmt;
+    use ibc_core::primitives::prelude::*;
+    use serde::de::{Error, Visitor};
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        deserializer.deserialize_any(U64Visitor)
+    }
+
+    struct U64Visitor;
+
+    impl Visitor<'_> for U64Visitor {
+        type Value = u64;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a uint64 as a decimal string or a number")
+        }
+
+        fn visit_u64<E: Error>(self, value: u64) -> Result<u64, E> {
+            Ok(value)
+        }
+
+        fn visit_str<E: Error>(self, value: &str) -> Result<u64, E> {
+            value.parse().map_err(E::custom)
+        }
+    }
+}
+
+/// `bytes` fields, encoded as standard base64 with padding.
+mod base64_bytes {
+    use ibc_core::primitives::prelude::*;
+    use serde::{de::Error as _, Deserialize, Deserializer, Serializer};
+    use subtle_encoding::base64;
+
+    pub fn serialize<S: Serializer>(value: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        let encoded =
+            String::from_utf8(base64::encode(value)).map_err(serde::ser::Error::custom)?;
+        serializer.serialize_str(&encoded)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        base64::decode(String::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+/// `google.protobuf.Duration` values, encoded as seconds with an `s` suffix.
+mod duration_string {
+    use core::time::Duration;
+    use ibc_core::primitives::prelude::*;
+    use serde::{de::Error as _, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        value: &Option<Duration>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(duration) => serializer.serialize_str(&format(*duration)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Duration>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|duration| parse(&duration).ok_or_else(|| D::Error::custom("invalid duration")))
+            .transpose()
+    }
+
+    pub(super) fn format(duration: Duration) -> String {
+        let nanos = duration.subsec_nanos();
+        if nanos == 0 {
+            return format!("{}s", duration.as_secs());
+        }
+        let fraction = format!("{nanos:09}");
+        format!("{}.{}s", duration.as_secs(), fraction.trim_end_matches('0'))
+    }
+
+    pub(super) fn parse(duration: &str) -> Option<Duration> {
+        let duration = duration.strip_suffix('s')?;
+        let (secs, fraction) = match duration.split_once('.') {
+            Some((secs, fraction)) => (secs, fraction),
+            None => (duration, ""),
+        };
+        if fraction.len() > 9 || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let nanos = if fraction.is_empty() {
+            0
+        } else {
+            format!("{fraction:0<9}").parse().ok()?
+        };
+        Some(Duration::new(secs.parse().ok()?, nanos))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonPublicKey {
+    #[serde(rename = "@type")]
+    type_url: String,
+    #[serde(with = "base64_bytes")]
+    key: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonConsensusState {
+    #[serde(rename = "@type", default, skip_serializing_if = "Option::is_none")]
+    type_url: Option<String>,
+    #[serde(alias = "publicKey")]
+    public_key: JsonPublicKey,
+    diversifier: String,
+    #[serde(with = "u64_string")]
+    timestamp: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonClientState {
+    #[serde(rename = "@type")]
+    type_url: Option<String>,
+    #[serde(with = "u64_string")]
+    sequence: u64,
+    #[serde(alias = "isFrozen")]
+    is_frozen: bool,
+    #[serde(alias = "consensusState")]
+    consensus_state: JsonConsensusState,
+    #[serde(
+        with = "duration_string",
+        alias = "inactivityPeriod",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    inactivity_period: Option<Duration>,
+    #[serde(
+        alias = "allowUpdateAfterProposal",
+        default,
+        skip_serializing_if = "core::ops::Not::not"
+    )]
+    allow_update_after_proposal: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonHeader {
+    #[serde(rename = "@type")]
+    type_url: Option<String>,
+    #[serde(with = "u64_string")]
+    timestamp: u64,
+    #[serde(with = "base64_bytes")]
+    signature: Vec<u8>,
+    #[serde(alias = "newPublicKey")]
+    new_public_key: JsonPublicKey,
+    #[serde(alias = "newDiversifier")]
+    new_diversifier: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonSignatureAndData {
+    #[serde(with = "base64_bytes")]
+    signature: Vec<u8>,
+    #[serde(with = "base64_bytes")]
+    path: Vec<u8>,
+    #[serde(with = "base64_bytes")]
+    data: Vec<u8>,
+    #[serde(with = "u64_string")]
+    timestamp: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonMisbehaviour {
+    #[serde(rename = "@type")]
+    type_url: Option<String>,
+    #[serde(with = "u64_string")]
+    sequence: u64,
+    #[serde(alias = "signatureOne")]
+    signature_one: JsonSignatureAndData,
+    #[serde(alias = "signatureTwo")]
+    signature_two: JsonSignatureAndData,
+}
+
+fn timestamp(nanoseconds: u64) -> Result<Timestamp, Error> {
+    Timestamp::from_nanoseconds(nanoseconds).map_err(Error::ParseTimeError)
+}
+
+fn sequence(sequence: u64) -> Result<Height, Error> {
+    Height::new(0, sequence).map_err(Error::InvalidHeight)
+}
+
+impl TryFrom<&PublicKey> for JsonPublicKey {
+    type Error = Error;
+
+    fn try_from(value: &PublicKey) -> Result<Self, Self::Error> {
+        Ok(Self {
+            type_url: value.type_url().to_string(),
+            key: value.to_bytes(),
+        })
+    }
+}
+
+impl TryFrom<JsonPublicKey> for PublicKey {
+    type Error = Error;
+
+    fn try_from(value: JsonPublicKey) -> Result<Self, Self::Error> {
+        let algorithm = match value.type_url.as_str() {
+            ED25519_TYPE_URL => Algorithm::Ed25519,
+            SECP256K1_TYPE_URL => Algorithm::Secp256k1,
+            _ => {
+                return Err(Error::PublicKeyParseFailed(
+                    CosmosError::UnsupportedPublicKey {
+                        key_type: value.type_url,
+                    },
+                ))
+            }
+        };
+        PublicKey::from_raw(algorithm, &value.key).map_err(Error::PublicKeyParseFailed)
+    }
+}
+
+impl JsonConsensusState {
+    fn new(value: &ConsensusState, type_url: Option<&str>) -> Result<Self, Error> {
+        Ok(Self {
+            type_url: type_url.map(ToString::to_string),
+            public_key: (&value.public_key).try_into()?,
+            diversifier: value.diversifier.clone(),
+            timestamp: value.timestamp.nanoseconds(),
+        })
+    }
+
+    fn into_domain(self) -> Result<ConsensusState, Error> {
+        Ok(ConsensusState::new(
+            self.public_key.try_into()?,
+            self.diversifier,
+            timestamp(self.timestamp)?,
+        ))
+    }
+}
+
+impl JsonSignatureAndData {
+    fn new(value: &SignatureAndData) -> Self {
+        Self {
+            signature: value.signature.clone(),
+            path: value.path.clone(),
+            data: value.data.clone(),
+            timestamp: value.timestamp.nanoseconds(),
+        }
+    }
+
+    fn into_domain(self) -> Result<SignatureAndData, Error> {
+        Ok(SignatureAndData {
+            signature: self.signature,
+            path: self.path,
+            data: self.data,
+            timestamp: timestamp(self.timestamp)?,
+        })
+    }
+}
+
+impl ProtoJson for PublicKey {
+    fn to_proto_json(&self) -> Result<String, Error> {
+        to_json(&JsonPublicKey::try_from(self)?)
+    }
+
+    fn from_proto_json(json: &str) -> Result<Self, Error> {
+        from_json::<JsonPublicKey>(json)?.try_into()
+    }
+}
+
+impl ProtoJson for ConsensusState {
+    fn to_proto_json(&self) -> Result<String, Error> {
+        to_json(&JsonConsensusState::new(
+            self,
+            Some(SOLOMACHINE_CONSENSUS_STATE_TYPE_URL),
+        )?)
+    }
+
+    fn from_proto_json(json: &str) -> Result<Self, Error> {
+        let consensus_state: JsonConsensusState = from_json(json)?;
+        check_type_url(
+            consensus_state.type_url.as_deref(),
+            SOLOMACHINE_CONSENSUS_STATE_TYPE_URL,
+        )?;
+        consensus_state.into_domain()
+    }
+}
+
+impl ProtoJson for ClientState {
+    fn to_proto_json(&self) -> Result<String, Error> {
+        to_json(&JsonClientState {
+            type_url: Some(SOLOMACHINE_CLIENT_STATE_TYPE_URL.to_string()),
+            sequence: self.sequence.revision_height(),
+            is_frozen: self.is_frozen,
+            consensus_state: JsonConsensusState::new(&self.consensus_state, None)?,
+            inactivity_period: self.inactivity_period,
+            allow_update_after_proposal: self.allow_update_after_proposal,
+        })
+    }
+
+    fn from_proto_json(json: &str) -> Result<Self, Error> {
+        let client_state: JsonClientState = from_json(json)?;
+        check_type_url(
+            client_state.type_url.as_deref(),
+            SOLOMACHINE_CLIENT_STATE_TYPE_URL,
+        )?;
+        Ok(ClientState {
+            inactivity_period: client_state.inactivity_period,
+            allow_update_after_proposal: client_state.allow_update_after_proposal,
+            ..ClientState::new(
+                sequence(client_state.sequence)?,
+                client_state.is_frozen,
+                client_state.consensus_state.into_domain()?,
+            )
+        })
+    }
+}
+
+impl ProtoJson for Header {
+    fn to_proto_json(&self) -> Result<String, Error> {
+        to_json(&JsonHeader {
+            type_url: Some(SOLOMACHINE_HEADER_TYPE_URL.to_string()),
+            timestamp: self.timestamp.nanoseconds(),
+            signature: self.signature.clone(),
+            new_public_key: (&self.new_public_key).try_into()?,
+            new_diversifier: self.new_diversifier.clone(),
+        })
+    }
+
+    fn from_proto_json(json: &str) -> Result<Self, Error> {
+        let header: JsonHeader = from_json(json)?;
+        check_type_url(header.type_url.as_deref(), SOLOMACHINE_HEADER_TYPE_URL)?;
+        Ok(Header {
+            timestamp: timestamp(header.timestamp)?,
+            signature: header.signature,
+            new_public_key: header.new_public_key.try_into()?,
+            new_diversifier: header.new_diversifier,
+        })
+    }
+}
+
+impl ProtoJson for SignatureAndData {
+    fn to_proto_json(&self) -> Result<String, Error> {
+        to_json(&JsonSignatureAndData::new(self))
+    }
+
+    fn from_proto_json(json: &str) -> Result<Self, Error> {
+        from_json::<JsonSignatureAndData>(json)?.into_domain()
+    }
+}
+
+impl ProtoJson for Misbehaviour {
+    fn to_proto_json(&self) -> Result<String, Error> {
+        to_json(&JsonMisbehaviour {
+            type_url: Some(SOLOMACHINE_MISBEHAVIOUR_TYPE_URL.to_string()),
+            sequence: self.sequence.revision_height(),
+            signature_one: JsonSignatureAndData::new(&self.signature_one),
+            signature_two: JsonSignatureAndData::new(&self.signature_two),
+        })
+    }
+
+    fn from_proto_json(json: &str) -> Result<Self, Error> {
+        let misbehaviour: JsonMisbehaviour = from_json(json)?;
+        check_type_url(
+            misbehaviour.type_url.as_deref(),
+            SOLOMACHINE_MISBEHAVIOUR_TYPE_URL,
+        )?;
+        Ok(Misbehaviour {
+            sequence: sequence(misbehaviour.sequence)?,
+            signature_one: misbehaviour.signature_one.into_domain()?,
+            signature_two: misbehaviour.signature_two.into_domain()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    const EXAMPLE_KEY: &str = "{\"@type\":\"/cosmos.crypto.ed25519.PubKey\",\"key\":\"sEEsVGkXvyewKLWMJbHVDRkBoerW0IIwmj1rHkabtHU=\"}";
+
+    fn round_trip<T: ProtoJson + PartialEq>(value: &T, expected: &str) {
+        let json = value.to_proto_json().unwrap();
+        assert_eq!(json, expected);
+        assert!(T::from_proto_json(&json).unwrap() == *value);
+    }
+
+    #[test]
+    fn test_proto_json_matches_ibc_go() {
+        let public_key = EXAMPLE_KEY.parse::<PublicKey>().unwrap();
+        let timestamp = Timestamp::from_nanoseconds(10).unwrap();
+        let consensus_state = ConsensusState::new(public_key, "testing".into(), timestamp);
+        let signature_and_data = |data: u8| SignatureAndData {
+            signature: vec![1, data],
+            path: b"path".to_vec(),
+            data: vec![data],
+            timestamp,
+        };
+
+        round_trip(&public_key, EXAMPLE_KEY);
+        round_trip(
+            &consensus_state,
+            &format!("{{\"@type\":\"/ibc.lightclients.solomachine.v3.ConsensusState\",\"public_key\":{EXAMPLE_KEY},\"diversifier\":\"testing\",\"timestamp\":\"10\"}}"),
+        );
+        round_trip(
+            &ClientState::new(Height::new(0, 3).unwrap(), false, consensus_state.clone()),
+            &format!("{{\"@type\":\"/ibc.lightclients.solomachine.v3.ClientState\",\"sequence\":\"3\",\"is_frozen\":false,\"consensus_state\":{{\"public_key\":{EXAMPLE_KEY},\"diversifier\":\"testing\",\"timestamp\":\"10\"}}}}"),
+        );
+        round_trip(
+            &Header {
+                timestamp,
+                signature: vec![1, 2, 3],
+                new_public_key: public_key,
+                new_diversifier: "testing".into(),
+            },
+            &format!("{{\"@type\":\"/ibc.lightclients.solomachine.v3.Header\",\"timestamp\":\"10\",\"signature\":\"AQID\",\"new_public_key\":{EXAMPLE_KEY},\"new_diversifier\":\"testing\"}}"),
+        );
+        round_trip(
+            &signature_and_data(1),
+            "{\"signature\":\"AQE=\",\"path\":\"cGF0aA==\",\"data\":\"AQ==\",\"timestamp\":\"10\"}",
+        );
+        round_trip(
+            &Misbehaviour {
+                sequence: Height::new(0, 3).unwrap(),
+                signature_one: signature_and_data(1),
+                signature_two: signature_and_data(2),
+            },
+            "{\"@type\":\"/ibc.lightclients.solomachine.v3.Misbehaviour\",\"sequence\":\"3\",\"signature_one\":{\"signature\":\"AQE=\",\"path\":\"cGF0aA==\",\"data\":\"AQ==\",\"timestamp\":\"10\"},\"signature_two\":{\"signature\":\"AQI=\",\"path\":\"cGF0aA==\",\"data\":\"Ag==\",\"timestamp\":\"10\"}}",
+        );
+    }
+
+    #[test]
+    fn test_proto_json_extension_fields() {
+        let public_key = EXAMPLE_KEY.parse::<PublicKey>().unwrap();
+        let consensus_state = ConsensusState::new(
+            public_key,
+            "testing".into(),
+            Timestamp::from_nanoseconds(10).unwrap(),
+        );
+        let client_state = ClientState {
+            allow_update_after_proposal: true,
+            ..ClientState::new(Height::new(0, 3).unwrap(), true, consensus_state)
+                .with_inactivity_period(Duration::from_millis(1500))
+        };
+
+        let json = client_state.to_proto_json().unwrap();
+        assert!(
+            json.ends_with(",\"inactivity_period\":\"1.5s\",\"allow_update_after_proposal\":true}")
+        );
+        assert!(ClientState::from_proto_json(&json).unwrap() == client_state);
+
+        assert_eq!(duration_string::format(Duration::from_secs(2)), "2s");
+        assert_eq!(
+            duration_string::parse("0.000000001s"),
+            Some(Duration::from_nanos(1))
+        );
+        assert_eq!(duration_string::parse("1.0000000001s"), None);
+        assert_eq!(duration_string::parse("1"), None);
+    }
+
+    #[test]
+    fn test_proto_json_accepts_numbers_and_camel_case() {
+        let public_key = EXAMPLE_KEY.parse::<PublicKey>().unwrap();
+        let consensus_state = ConsensusState::new(
+            public_key,
+            "testing".into(),
+            Timestamp::from_nanoseconds(10).unwrap(),
+        );
+        let client_state = ClientState {
+            allow_update_after_proposal: true,
+            ..ClientState::new(Height::new(0, 3).unwrap(), true, consensus_state)
+                .with_inactivity_period(Duration::from_millis(1500))
+        };
+
+        let camel_case = format!("{{\"@type\":\"/ibc.lightclients.solomachine.v3.ClientState\",\"sequence\":3,\"isFrozen\":true,\"consensusState\":{{\"publicKey\":{EXAMPLE_KEY},\"diversifier\":\"testing\",\"timestamp\":10}},\"inactivityPeriod\":\"1.5s\",\"allowUpdateAfterProposal\":true}}");
+        assert!(ClientState::from_proto_json(&camel_case).unwrap() == client_state);
+
+        // encoding keeps the ibc-go field names and string values
+        assert!(client_state
+            .to_proto_json()
+            .unwrap()
+            .starts_with("{\"@type\":\"/ibc.lightclients.solomachine.v3.ClientState\",\"sequence\":\"3\",\"is_frozen\":true,"));
+
+        let numeric =
+            "{\"signature\":\"AQE=\",\"path\":\"cGF0aA==\",\"data\":\"AQ==\",\"timestamp\":10}";
+        let signature_and_data = SignatureAndData::from_proto_json(numeric).unwrap();
+        assert_eq!(signature_and_data.timestamp.nanoseconds(), 10);
+
+        let misbehaviour = "{\"@type\":\"/ibc.lightclients.solomachine.v3.Misbehaviour\",\"sequence\":\"3\",\"signatureOne\":{\"signature\":\"AQE=\",\"path\":\"cGF0aA==\",\"data\":\"AQ==\",\"timestamp\":\"10\"},\"signatureTwo\":{\"signature\":\"AQI=\",\"path\":\"cGF0aA==\",\"data\":\"Ag==\",\"timestamp\":10}}";
+        assert!(Misbehaviour::from_proto_json(misbehaviour).is_ok());
+
+        let header = format!("{{\"@type\":\"/ibc.lightclients.solomachine.v3.Header\",\"timestamp\":10,\"signature\":\"AQID\",\"newPublicKey\":{EXAMPLE_KEY},\"newDiversifier\":\"testing\"}}");
+        assert!(Header::from_proto_json(&header).is_ok());
+    }
+
+    #[test]
+    fn test_proto_json_rejects_mismatches() {
+        let header = format!("{{\"@type\":\"/ibc.lightclients.solomachine.v2.Header\",\"timestamp\":\"10\",\"signature\":\"AQID\",\"new_public_key\":{EXAMPLE_KEY},\"new_diversifier\":\"testing\"}}");
+        assert!(Header::from_proto_json(&header).is_err());
+
+        let untyped = "{\"public_key\":{\"@type\":\"/cosmos.crypto.ed25519.PubKey\",\"key\":\"sEEsVGkXvyewKLWMJbHVDRkBoerW0IIwmj1rHkabtHU=\"},\"diversifier\":\"testing\",\"timestamp\":\"10\"}";
+        assert!(ConsensusState::from_proto_json(untyped).is_err());
+
+        let negative =
+            "{\"signature\":\"AQE=\",\"path\":\"cGF0aA==\",\"data\":\"AQ==\",\"timestamp\":-10}";
+        assert!(SignatureAndData::from_proto_json(negative).is_err());
+
+        let unknown_key = "{\"@type\":\"/unknown.PubKey\",\"key\":\"AQID\"}";
+        assert!(PublicKey::from_proto_json(unknown_key).is_err());
+    }
+}
@@ -0,0 +1,124 @@
+//! Optional checks of solo machine timestamps against the host clock.
+
+use crate::error::Error;
+use core::time::Duration;
+use ibc_core::primitives::Timestamp;
+
+/// Bounds on how far header and proof timestamps may stray from the host
+/// time. Both are disabled by default.
+///
+/// Like [`VerificationLimits`](crate::VerificationLimits) the policy is host
+/// configuration rather than client state, and is checked against the host
+/// time of the verifying host.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TimePolicy {
+    /// maximum time a header or proof timestamp may be ahead of the host time
+    pub max_clock_drift: Option<Duration>,
+    /// maximum time a proof timestamp may be behind the host time
+    pub max_proof_age: Option<Duration>,
+}
+
+impl TimePolicy {
+    /// Returns true if any check is configured.
+    pub fn is_enabled(&self) -> bool {
+        self.max_clock_drift.is_some() || self.max_proof_age.is_some()
+    }
+
+    /// Checks that a header `timestamp` is at most `max_clock_drift` ahead of
+    /// the host.
+    pub fn check_header(
+        &self,
+        timestamp: Timestamp,
+        host_timestamp: Timestamp,
+    ) -> Result<(), Error> {
+        match self.exceeded_clock_drift(timestamp, host_timestamp) {
+            Some(max_clock_drift) => Err(Error::HeaderTimestampTooFarInFuture {
+                timestamp,
+                host_timestamp,
+                max_clock_drift,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks that a proof `timestamp` is at most `max_clock_drift` ahead of
+    /// and at most `max_proof_age` behind the host.
+    pub fn check_proof(
+        &self,
+        timestamp: Timestamp,
+        host_timestamp: Timestamp,
+    ) -> Result<(), Error> {
+        if let Some(max_clock_drift) = self.exceeded_clock_drift(timestamp, host_timestamp) {
+            return Err(Error::TimestampTooFarInFuture {
+                timestamp,
+                host_timestamp,
+                max_clock_drift,
+            });
+        }
+        if let Some(max_proof_age) = self.max_proof_age {
+            if let Some(age) = host_timestamp.duration_since(&timestamp) {
+                if age > max_proof_age {
+                    return Err(Error::ProofTooOld {
+                        timestamp,
+                        host_timestamp,
+                        max_proof_age,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Exceeded_clock_drift returns the maximum clock drift if `timestamp` is
+    // further ahead of the host than it allows.
+    fn exceeded_clock_drift(
+        &self,
+        timestamp: Timestamp,
+        host_timestamp: Timestamp,
+    ) -> Option<Duration> {
+        let max_clock_drift = self.max_clock_drift?;
+        let drift = timestamp.duration_since(&host_timestamp)?;
+        (drift > max_clock_drift).then_some(max_clock_drift)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timestamp(seconds: u64) -> Timestamp {
+        Timestamp::from_nanoseconds(seconds * 1_000_000_000).unwrap()
+    }
+
+    #[test]
+    fn test_time_policy() {
+        let host = timestamp(100);
+
+        let disabled = TimePolicy::default();
+        assert!(!disabled.is_enabled());
+        disabled.check_proof(timestamp(1_000_000), host).unwrap();
+        disabled.check_proof(timestamp(1), host).unwrap();
+
+        let policy = TimePolicy {
+            max_clock_drift: Some(Duration::from_secs(10)),
+            max_proof_age: Some(Duration::from_secs(60)),
+        };
+        assert!(policy.is_enabled());
+        policy.check_proof(timestamp(110), host).unwrap();
+        policy.check_proof(timestamp(40), host).unwrap();
+        policy.check_header(timestamp(110), host).unwrap();
+        policy.check_header(timestamp(1), host).unwrap();
+        assert!(matches!(
+            policy.check_header(timestamp(111), host),
+            Err(Error::HeaderTimestampTooFarInFuture { .. })
+        ));
+        assert!(matches!(
+            policy.check_proof(timestamp(111), host),
+            Err(Error::TimestampTooFarInFuture { .. })
+        ));
+        assert!(matches!(
+            policy.check_proof(timestamp(39), host),
+            Err(Error::ProofTooOld { .. })
+        ));
+    }
+}
@@ -0,0 +1,167 @@
+//! Domain types of the `ibc.lightclients.solomachine.v1` protocol.
+//!
+//! v1 only differs from v2 in its client state, which records the sequence
+//! the client was frozen at instead of a frozen flag. Its consensus states,
+//! headers and misbehaviours share the v2 encoding and are decoded into the
+//! [`v2`](crate::v2) domain types.
+
+use crate::error::Error;
+use crate::v2;
+use ibc_core::client::types::error::ClientError;
+use ibc_core::client::types::Height;
+use ibc_core::primitives::prelude::*;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::lightclients::solomachine::v2::ConsensusState as RawSmConsensusState;
+use ibc_proto::Protobuf;
+use prost::Message;
+
+pub const SOLOMACHINE_V1_CLIENT_STATE_TYPE_URL: &str =
+    "/ibc.lightclients.solomachine.v1.ClientState";
+pub const SOLOMACHINE_V1_CONSENSUS_STATE_TYPE_URL: &str =
+    "/ibc.lightclients.solomachine.v1.ConsensusState";
+pub const SOLOMACHINE_V1_HEADER_TYPE_URL: &str = "/ibc.lightclients.solomachine.v1.Header";
+pub const SOLOMACHINE_V1_MISBEHAVIOUR_TYPE_URL: &str =
+    "/ibc.lightclients.solomachine.v1.Misbehaviour";
+
+/// Protobuf messages of `ibc.lightclients.solomachine.v1` that differ from v2.
+pub mod raw {
+    /// ClientState defines a solo machine client that tracks the current
+    /// consensus state and if the client is frozen.
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct ClientState {
+        /// latest sequence of the client state
+        #[prost(uint64, tag = "1")]
+        pub sequence: u64,
+        /// frozen sequence of the solo machine
+        #[prost(uint64, tag = "2")]
+        pub frozen_sequence: u64,
+        #[prost(message, optional, tag = "3")]
+        pub consensus_state:
+            ::core::option::Option<::ibc_proto::ibc::lightclients::solomachine::v2::ConsensusState>,
+        /// when set to true, will allow governance to update a solo machine client.
+        /// The client will be unfrozen if it is frozen.
+        #[prost(bool, tag = "4")]
+        pub allow_update_after_proposal: bool,
+    }
+}
+
+use raw::ClientState as RawSmClientState;
+
+/// ClientState defines a v1 solo machine client that tracks the current
+/// consensus state and the sequence it was frozen at.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Clone, PartialEq, Debug)]
+pub struct ClientState {
+    /// latest sequence of the client state
+    pub sequence: Height,
+    /// sequence the client was frozen at, 0 if it is not frozen
+    pub frozen_sequence: u64,
+    /// consensus state of the solo machine
+    pub consensus_state: v2::ConsensusState,
+    /// when set to true, will allow governance to update a solo machine client.
+    /// The client will be unfrozen if it is frozen.
+    pub allow_update_after_proposal: bool,
+}
+
+// A v1 client is frozen if its frozen sequence is set, as in ibc-go's v1
+// `ClientState.IsFrozen`. The frozen sequence itself has no v2 field and
+// converting back sets it to the latest sequence, which is where ibc-go v1
+// freezes clients on misbehaviour.
+impl From<ClientState> for v2::ClientState {
+    fn from(value: ClientState) -> Self {
+        Self {
+            sequence: value.sequence,
+            is_frozen: value.frozen_sequence != 0,
+            consensus_state: value.consensus_state,
+            allow_update_after_proposal: value.allow_update_after_proposal,
+        }
+    }
+}
+
+impl From<v2::ClientState> for ClientState {
+    fn from(value: v2::ClientState) -> Self {
+        Self {
+            sequence: value.sequence,
+            frozen_sequence: if value.is_frozen {
+                value.sequence.revision_height()
+            } else {
+                0
+            },
+            consensus_state: value.consensus_state,
+            allow_update_after_proposal: value.allow_update_after_proposal,
+        }
+    }
+}
+
+impl Protobuf<RawSmClientState> for ClientState {}
+
+impl TryFrom<RawSmClientState> for ClientState {
+    type Error = Error;
+
+    fn try_from(raw: RawSmClientState) -> Result<Self, Self::Error> {
+        let sequence = Height::new(0, raw.sequence).map_err(Error::InvalidHeight)?;
+        let consensus_state = raw
+            .consensus_state
+            .ok_or(Error::ConsensusStateIsEmpty)?
+            .try_into()?;
+
+        Ok(Self {
+            sequence,
+            frozen_sequence: raw.frozen_sequence,
+            consensus_state,
+            allow_update_after_proposal: raw.allow_update_after_proposal,
+        })
+    }
+}
+
+impl From<ClientState> for RawSmClientState {
+    fn from(value: ClientState) -> Self {
+        Self {
+            sequence: value.sequence.revision_height(),
+            frozen_sequence: value.frozen_sequence,
+            consensus_state: Some(RawSmConsensusState::from(value.consensus_state)),
+            allow_update_after_proposal: value.allow_update_after_proposal,
+        }
+    }
+}
+
+impl Protobuf<Any> for ClientState {}
+
+impl TryFrom<Any> for ClientState {
+    type Error = ClientError;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        match raw.type_url.as_str() {
+            SOLOMACHINE_V1_CLIENT_STATE_TYPE_URL => RawSmClientState::decode(raw.value.as_slice())
+                .map_err(Error::Decode)?
+                .try_into()
+                .map_err(Into::into),
+            _ => Err(ClientError::UnknownClientStateType {
+                client_state_type: raw.type_url,
+            }),
+        }
+    }
+}
+
+impl From<ClientState> for Any {
+    fn from(client_state: ClientState) -> Self {
+        Any {
+            type_url: SOLOMACHINE_V1_CLIENT_STATE_TYPE_URL.to_string(),
+            value: Protobuf::<RawSmClientState>::encode_vec(client_state),
+        }
+    }
+}
@@ -0,0 +1,232 @@
+//! Defines the v2 solo machine `ClientState` type and its migration to v3
+
+use crate::client_state::{ClientState as SmClientState, SOLOMACHINE_CLIENT_STATE_TYPE_URL};
+use crate::error::Error;
+use crate::v2::consensus_state::ConsensusState;
+use ibc_core::client::types::error::ClientError;
+use ibc_core::client::types::Height;
+use ibc_core::primitives::prelude::*;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::lightclients::solomachine::v2::ClientState as RawSmClientState;
+use ibc_proto::Protobuf;
+use prost::Message;
+
+pub const SOLOMACHINE_V2_CLIENT_STATE_TYPE_URL: &str =
+    "/ibc.lightclients.solomachine.v2.ClientState";
+
+/// ClientState defines a v2 solo machine client that tracks the current
+/// consensus state and if the client is frozen.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Clone, PartialEq, Debug)]
+pub struct ClientState {
+    /// latest sequence of the client state
+    pub sequence: Height,
+    /// frozen sequence of the solo machine
+    pub is_frozen: bool,
+    /// consensus state of the solo machine
+    pub consensus_state: ConsensusState,
+    /// when set to true, will allow governance to update a solo machine client.
+    /// The client will be unfrozen if it is frozen.
+    pub allow_update_after_proposal: bool,
+}
+
+// The v2 to v3 migration follows ibc-go's `migrateSolomachine`, which keeps
+// the sequence, frozen flag and consensus state. `allow_update_after_proposal`
+// has no v3 field and is kept in the v3 client state extension instead.
+// ref: https://github.com/cosmos/ibc-go/blob/v7.3.0/modules/core/02-client/migrations/v7/solomachine.go
+impl From<ClientState> for SmClientState {
+    fn from(value: ClientState) -> Self {
+        SmClientState {
+            allow_update_after_proposal: value.allow_update_after_proposal,
+            ..SmClientState::new(
+                value.sequence,
+                value.is_frozen,
+                value.consensus_state.into(),
+            )
+        }
+    }
+}
+
+impl From<SmClientState> for ClientState {
+    fn from(value: SmClientState) -> Self {
+        Self {
+            sequence: value.sequence,
+            is_frozen: value.is_frozen,
+            consensus_state: value.consensus_state.into(),
+            allow_update_after_proposal: value.allow_update_after_proposal,
+        }
+    }
+}
+
+/// Migrate_client_state decodes a stored solo machine client state and
+/// returns it as a v3 client state: v2 client states are migrated and v3 ones
+/// are returned as they are.
+pub fn migrate_client_state(stored: Any) -> Result<SmClientState, ClientError> {
+    match stored.type_url.as_str() {
+        SOLOMACHINE_V2_CLIENT_STATE_TYPE_URL => Ok(ClientState::try_from(stored)?.into()),
+        SOLOMACHINE_CLIENT_STATE_TYPE_URL => SmClientState::try_from(stored),
+        _ => Err(ClientError::UnknownClientStateType {
+            client_state_type: stored.type_url,
+        }),
+    }
+}
+
+impl Protobuf<RawSmClientState> for ClientState {}
+
+impl TryFrom<RawSmClientState> for ClientState {
+    type Error = Error;
+
+    fn try_from(raw: RawSmClientState) -> Result<Self, Self::Error> {
+        let sequence = Height::new(0, raw.sequence).map_err(Error::InvalidHeight)?;
+        let consensus_state = raw
+            .consensus_state
+            .ok_or(Error::ConsensusStateIsEmpty)?
+            .try_into()?;
+
+        Ok(Self {
+            sequence,
+            is_frozen: raw.is_frozen,
+            consensus_state,
+            allow_update_after_proposal: raw.allow_update_after_proposal,
+        })
+    }
+}
+
+impl From<ClientState> for RawSmClientState {
+    fn from(value: ClientState) -> Self {
+        Self {
+            sequence: value.sequence.revision_height(),
+            is_frozen: value.is_frozen,
+            consensus_state: Some(value.consensus_state.into()),
+            allow_update_after_proposal: value.allow_update_after_proposal,
+        }
+    }
+}
+
+impl Protobuf<Any> for ClientState {}
+
+impl TryFrom<Any> for ClientState {
+    type Error = ClientError;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        match raw.type_url.as_str() {
+            SOLOMACHINE_V2_CLIENT_STATE_TYPE_URL => RawSmClientState::decode(raw.value.as_slice())
+                .map_err(Error::Decode)?
+                .try_into()
+                .map_err(Into::into),
+            _ => Err(ClientError::UnknownClientStateType {
+                client_state_type: raw.type_url,
+            }),
+        }
+    }
+}
+
+impl From<ClientState> for Any {
+    fn from(client_state: ClientState) -> Self {
+        Any {
+            type_url: SOLOMACHINE_V2_CLIENT_STATE_TYPE_URL.to_string(),
+            value: Protobuf::<RawSmClientState>::encode_vec(client_state),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus_state::ConsensusState as SmConsensusState;
+    use crate::cosmos::crypto::PublicKey;
+    use crate::v2::proof::SignatureAndData;
+    use crate::v2::DataType;
+    use ibc_core::primitives::Timestamp;
+    use ibc_proto::ibc::lightclients::solomachine::v2::{
+        DataType as RawDataType, SignatureAndData as RawSignatureAndData,
+    };
+
+    fn v2_client_state(allow_update_after_proposal: bool) -> ClientState {
+        const EXAMPLE_JSON: &str = "{\"@type\":\"/cosmos.crypto.ed25519.PubKey\",\"key\":\"sEEsVGkXvyewKLWMJbHVDRkBoerW0IIwmj1rHkabtHU=\"}";
+        ClientState {
+            sequence: Height::new(0, 7).unwrap(),
+            is_frozen: true,
+            consensus_state: ConsensusState {
+                public_key: EXAMPLE_JSON.parse::<PublicKey>().unwrap(),
+                diversifier: "testing".into(),
+                timestamp: Timestamp::from_nanoseconds(10).unwrap(),
+            },
+            allow_update_after_proposal,
+        }
+    }
+
+    #[test]
+    fn test_v2_client_state_round_trip() {
+        let client_state = v2_client_state(true);
+        let any = Any::from(client_state.clone());
+        assert_eq!(any.type_url, SOLOMACHINE_V2_CLIENT_STATE_TYPE_URL);
+        assert_eq!(ClientState::try_from(any).unwrap(), client_state);
+    }
+
+    #[test]
+    fn test_migrate_client_state() {
+        for allow_update_after_proposal in [false, true] {
+            let v2 = v2_client_state(allow_update_after_proposal);
+            let v3 = migrate_client_state(Any::from(v2.clone())).unwrap();
+            assert_eq!(v3.sequence, v2.sequence);
+            assert_eq!(v3.is_frozen, v2.is_frozen);
+            assert_eq!(
+                v3.consensus_state,
+                SmConsensusState::from(v2.consensus_state.clone())
+            );
+
+            // the migrated client survives a v3 encoding and converts back to
+            // the same v2 client state
+            let stored = Any::from(v3.clone());
+            assert_eq!(stored.type_url, SOLOMACHINE_CLIENT_STATE_TYPE_URL);
+            let reloaded = migrate_client_state(stored).unwrap();
+            assert_eq!(reloaded, v3);
+            assert_eq!(ClientState::from(reloaded), v2);
+        }
+
+        let unknown = Any {
+            type_url: "/ibc.lightclients.solomachine.v9.ClientState".into(),
+            value: vec![],
+        };
+        assert!(matches!(
+            migrate_client_state(unknown),
+            Err(ClientError::UnknownClientStateType { .. })
+        ));
+    }
+
+    #[test]
+    fn test_unknown_data_type() {
+        let raw = RawSignatureAndData {
+            signature: vec![1],
+            data_type: 42,
+            data: vec![2],
+            timestamp: 10,
+        };
+        assert!(matches!(
+            SignatureAndData::try_from(raw.clone()),
+            Err(Error::UnknownDataType(42))
+        ));
+
+        let raw = RawSignatureAndData {
+            data_type: RawDataType::PacketCommitment as i32,
+            ..raw
+        };
+        let signature_and_data = SignatureAndData::try_from(raw.clone()).unwrap();
+        assert_eq!(signature_and_data.data_type, DataType::PacketCommitment);
+        assert_eq!(RawSignatureAndData::from(signature_and_data), raw);
+    }
+}
@@ -0,0 +1,116 @@
+//! Defines the v2 solo machine `ConsensusState` type
+
+use crate::consensus_state::ConsensusState as SmConsensusState;
+use crate::cosmos::crypto::PublicKey;
+use crate::error::Error;
+use ibc_core::client::types::error::ClientError;
+use ibc_core::primitives::prelude::*;
+use ibc_core::primitives::Timestamp;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::lightclients::solomachine::v2::ConsensusState as RawSmConsensusState;
+use ibc_proto::Protobuf;
+use prost::Message;
+
+pub const SOLOMACHINE_V2_CONSENSUS_STATE_TYPE_URL: &str =
+    "/ibc.lightclients.solomachine.v2.ConsensusState";
+
+/// ConsensusState defines a v2 solo machine consensus state. Its fields are
+/// those of the v3 consensus state.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Clone, PartialEq, Debug)]
+pub struct ConsensusState {
+    /// public key of the solo machine
+    pub public_key: PublicKey,
+    /// diversifier allows the same public key to be re-used across different solo
+    /// machine clients (potentially on different chains) without being considered
+    /// misbehaviour.
+    pub diversifier: String,
+    /// timestamp of the consensus state
+    pub timestamp: Timestamp,
+}
+
+impl From<ConsensusState> for SmConsensusState {
+    fn from(value: ConsensusState) -> Self {
+        SmConsensusState::new(value.public_key, value.diversifier, value.timestamp)
+    }
+}
+
+impl From<SmConsensusState> for ConsensusState {
+    fn from(value: SmConsensusState) -> Self {
+        Self {
+            public_key: value.public_key,
+            diversifier: value.diversifier,
+            timestamp: value.timestamp,
+        }
+    }
+}
+
+impl Protobuf<RawSmConsensusState> for ConsensusState {}
+
+impl TryFrom<RawSmConsensusState> for ConsensusState {
+    type Error = Error;
+
+    fn try_from(raw: RawSmConsensusState) -> Result<Self, Self::Error> {
+        let public_key = PublicKey::try_from(raw.public_key.ok_or(Error::PublicKeyIsEmpty)?)
+            .map_err(Error::PublicKeyParseFailed)?;
+        let timestamp =
+            Timestamp::from_nanoseconds(raw.timestamp).map_err(Error::ParseTimeError)?;
+        Ok(Self {
+            public_key,
+            diversifier: raw.diversifier,
+            timestamp,
+        })
+    }
+}
+
+impl From<ConsensusState> for RawSmConsensusState {
+    fn from(value: ConsensusState) -> Self {
+        Self {
+            public_key: Some(value.public_key.to_any()),
+            diversifier: value.diversifier,
+            timestamp: value.timestamp.nanoseconds(),
+        }
+    }
+}
+
+impl Protobuf<Any> for ConsensusState {}
+
+impl TryFrom<Any> for ConsensusState {
+    type Error = ClientError;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        match raw.type_url.as_str() {
+            SOLOMACHINE_V2_CONSENSUS_STATE_TYPE_URL => {
+                RawSmConsensusState::decode(raw.value.as_slice())
+                    .map_err(Error::Decode)?
+                    .try_into()
+                    .map_err(Into::into)
+            }
+            _ => Err(ClientError::UnknownConsensusStateType {
+                consensus_state_type: raw.type_url,
+            }),
+        }
+    }
+}
+
+impl From<ConsensusState> for Any {
+    fn from(consensus_state: ConsensusState) -> Self {
+        Any {
+            type_url: SOLOMACHINE_V2_CONSENSUS_STATE_TYPE_URL.to_string(),
+            value: Protobuf::<RawSmConsensusState>::encode_vec(consensus_state),
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//! Defines the domain type for v2 solo machine headers
+
+use crate::cosmos::crypto::PublicKey;
+use crate::error::Error;
+use ibc_core::client::types::error::ClientError;
+use ibc_core::client::types::Height;
+use ibc_core::primitives::prelude::*;
+use ibc_core::primitives::Timestamp;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::lightclients::solomachine::v2::Header as RawSmHeader;
+use ibc_proto::Protobuf;
+use prost::Message;
+
+pub const SOLOMACHINE_V2_HEADER_TYPE_URL: &str = "/ibc.lightclients.solomachine.v2.Header";
+
+/// Header defines a v2 solo machine consensus header, which unlike v3 carries
+/// the sequence it updates the public key at.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Clone, PartialEq, Debug)]
+pub struct Header {
+    /// Sequence to update the solo machine public key at
+    pub sequence: Height,
+    /// Timestamp of the consensus state
+    pub timestamp: Timestamp,
+    /// Signature of the new public key
+    pub signature: Vec<u8>,
+    /// New public key of the validator
+    pub new_public_key: PublicKey,
+    /// New diversifier of the validator
+    pub new_diversifier: String,
+}
+
+impl Protobuf<RawSmHeader> for Header {}
+
+impl TryFrom<RawSmHeader> for Header {
+    type Error = Error;
+
+    fn try_from(raw: RawSmHeader) -> Result<Self, Self::Error> {
+        let sequence = Height::new(0, raw.sequence).map_err(Error::InvalidHeight)?;
+        let timestamp =
+            Timestamp::from_nanoseconds(raw.timestamp).map_err(Error::ParseTimeError)?;
+        let new_public_key =
+            PublicKey::try_from(raw.new_public_key.ok_or(Error::PublicKeyIsEmpty)?)
+                .map_err(Error::PublicKeyParseFailed)?;
+
+        Ok(Self {
+            sequence,
+            timestamp,
+            signature: raw.signature,
+            new_public_key,
+            new_diversifier: raw.new_diversifier,
+        })
+    }
+}
+
+impl From<Header> for RawSmHeader {
+    fn from(value: Header) -> Self {
+        Self {
+            sequence: value.sequence.revision_height(),
+            timestamp: value.timestamp.nanoseconds(),
+            signature: value.signature,
+            new_public_key: Some(value.new_public_key.to_any()),
+            new_diversifier: value.new_diversifier,
+        }
+    }
+}
+
+impl Protobuf<Any> for Header {}
+
+impl TryFrom<Any> for Header {
+    type Error = ClientError;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        match raw.type_url.as_str() {
+            SOLOMACHINE_V2_HEADER_TYPE_URL => RawSmHeader::decode(raw.value.as_slice())
+                .map_err(Error::Decode)?
+                .try_into()
+                .map_err(Into::into),
+            _ => Err(ClientError::UnknownHeaderType {
+                header_type: raw.type_url,
+            }),
+        }
+    }
+}
+
+impl From<Header> for Any {
+    fn from(header: Header) -> Self {
+        Any {
+            type_url: SOLOMACHINE_V2_HEADER_TYPE_URL.to_string(),
+            value: Protobuf::<RawSmHeader>::encode_vec(header),
+        }
+    }
+}
@@ -0,0 +1,112 @@
+//! Defines the misbehaviour type of the v2 solo machine light client
+
+use crate::error::Error;
+use crate::v2::proof::SignatureAndData;
+use ibc_core::client::types::error::ClientError;
+use ibc_core::client::types::Height;
+use ibc_core::host::types::identifiers::ClientId;
+use ibc_core::primitives::prelude::*;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::lightclients::solomachine::v2::Misbehaviour as RawSmMisbehaviour;
+use ibc_proto::Protobuf;
+use prost::Message;
+
+pub const SOLOMACHINE_V2_MISBEHAVIOUR_TYPE_URL: &str =
+    "/ibc.lightclients.solomachine.v2.Misbehaviour";
+
+/// Misbehaviour defines misbehaviour for a v2 solo machine which consists
+/// of a sequence and two signatures over different messages at that sequence.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Clone, PartialEq, Debug)]
+pub struct Misbehaviour {
+    /// The client the misbehaviour was submitted for
+    pub client_id: ClientId,
+    /// The sequence number at which the infraction occurred
+    pub sequence: Height,
+    /// The first signature
+    pub signature_one: SignatureAndData,
+    /// The second signature
+    pub signature_two: SignatureAndData,
+}
+
+impl Protobuf<RawSmMisbehaviour> for Misbehaviour {}
+
+impl TryFrom<RawSmMisbehaviour> for Misbehaviour {
+    type Error = Error;
+
+    fn try_from(raw: RawSmMisbehaviour) -> Result<Self, Self::Error> {
+        let client_id = raw
+            .client_id
+            .parse()
+            .map_err(|_| Error::InvalidRawClientId {
+                client_id: raw.client_id.clone(),
+            })?;
+        let sequence = Height::new(0, raw.sequence).map_err(Error::InvalidHeight)?;
+        let signature_one = raw
+            .signature_one
+            .ok_or(Error::SignatureAndDataIsEmpty)?
+            .try_into()?;
+        let signature_two = raw
+            .signature_two
+            .ok_or(Error::SignatureAndDataIsEmpty)?
+            .try_into()?;
+
+        Ok(Self {
+            client_id,
+            sequence,
+            signature_one,
+            signature_two,
+        })
+    }
+}
+
+impl From<Misbehaviour> for RawSmMisbehaviour {
+    fn from(value: Misbehaviour) -> Self {
+        Self {
+            client_id: value.client_id.to_string(),
+            sequence: value.sequence.revision_height(),
+            signature_one: Some(value.signature_one.into()),
+            signature_two: Some(value.signature_two.into()),
+        }
+    }
+}
+
+impl Protobuf<Any> for Misbehaviour {}
+
+impl TryFrom<Any> for Misbehaviour {
+    type Error = ClientError;
+
+    fn try_from(raw: Any) -> Result<Self, ClientError> {
+        match raw.type_url.as_str() {
+            SOLOMACHINE_V2_MISBEHAVIOUR_TYPE_URL => RawSmMisbehaviour::decode(raw.value.as_slice())
+                .map_err(Error::Decode)?
+                .try_into()
+                .map_err(Into::into),
+            _ => Err(ClientError::UnknownMisbehaviourType {
+                misbehaviour_type: raw.type_url,
+            }),
+        }
+    }
+}
+
+impl From<Misbehaviour> for Any {
+    fn from(misbehaviour: Misbehaviour) -> Self {
+        Any {
+            type_url: SOLOMACHINE_V2_MISBEHAVIOUR_TYPE_URL.to_string(),
+            value: Protobuf::<RawSmMisbehaviour>::encode_vec(misbehaviour),
+        }
+    }
+}
@@ -0,0 +1,21 @@
+//! Domain types of the `ibc.lightclients.solomachine.v2` protocol, which some
+//! counterparties still run, and the migration of v2 client states to v3.
+//!
+//! v2 differs from v3 in that sign bytes and misbehaviour signatures carry a
+//! [`DataType`] instead of a path, headers and misbehaviours carry their own
+//! sequence and the client state has an `allow_update_after_proposal` flag.
+//!
+//! The protobuf messages are those of
+//! `ibc_proto::ibc::lightclients::solomachine::v2`.
+
+pub mod client_state;
+pub mod consensus_state;
+pub mod header;
+pub mod misbehaviour;
+pub mod proof;
+
+pub use client_state::*;
+pub use consensus_state::*;
+pub use header::*;
+pub use misbehaviour::*;
+pub use proof::*;
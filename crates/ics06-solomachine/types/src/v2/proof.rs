@@ -0,0 +1,245 @@
+//! Defines the v2 solo machine `SignatureAndData` and `SignBytes` types, which
+//! are tagged with a `DataType` rather than a path.
+
+use crate::error::Error;
+use ibc_core::primitives::prelude::*;
+use ibc_core::primitives::Timestamp;
+use ibc_proto::ibc::lightclients::solomachine::v2::{
+    DataType as RawDataType, SignBytes as RawSignBytes, SignatureAndData as RawSignatureAndData,
+};
+use ibc_proto::Protobuf;
+
+// Data_type_from_i32 converts a raw `DataType` and rejects unknown values.
+pub(crate) fn data_type_from_i32(data_type: i32) -> Result<DataType, Error> {
+    RawDataType::try_from(data_type)
+        .map(Into::into)
+        .map_err(|_| Error::UnknownDataType(data_type))
+}
+
+/// DataType defines the type of solo machine proof being created. This is done
+/// to preserve uniqueness of different data sign byte encodings.
+///
+/// Domain counterpart of the ibc-proto `DataType`, carrying the codec derives
+/// of the other domain types.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum DataType {
+    /// Default State
+    UninitializedUnspecified,
+    /// Data type for client state verification
+    ClientState,
+    /// Data type for consensus state verification
+    ConsensusState,
+    /// Data type for connection state verification
+    ConnectionState,
+    /// Data type for channel state verification
+    ChannelState,
+    /// Data type for packet commitment verification
+    PacketCommitment,
+    /// Data type for packet acknowledgement verification
+    PacketAcknowledgement,
+    /// Data type for packet receipt absence verification
+    PacketReceiptAbsence,
+    /// Data type for next sequence recv verification
+    NextSequenceRecv,
+    /// Data type for header verification
+    Header,
+}
+
+impl From<RawDataType> for DataType {
+    fn from(raw: RawDataType) -> Self {
+        match raw {
+            RawDataType::UninitializedUnspecified => Self::UninitializedUnspecified,
+            RawDataType::ClientState => Self::ClientState,
+            RawDataType::ConsensusState => Self::ConsensusState,
+            RawDataType::ConnectionState => Self::ConnectionState,
+            RawDataType::ChannelState => Self::ChannelState,
+            RawDataType::PacketCommitment => Self::PacketCommitment,
+            RawDataType::PacketAcknowledgement => Self::PacketAcknowledgement,
+            RawDataType::PacketReceiptAbsence => Self::PacketReceiptAbsence,
+            RawDataType::NextSequenceRecv => Self::NextSequenceRecv,
+            RawDataType::Header => Self::Header,
+        }
+    }
+}
+
+impl From<DataType> for RawDataType {
+    fn from(data_type: DataType) -> Self {
+        match data_type {
+            DataType::UninitializedUnspecified => Self::UninitializedUnspecified,
+            DataType::ClientState => Self::ClientState,
+            DataType::ConsensusState => Self::ConsensusState,
+            DataType::ConnectionState => Self::ConnectionState,
+            DataType::ChannelState => Self::ChannelState,
+            DataType::PacketCommitment => Self::PacketCommitment,
+            DataType::PacketAcknowledgement => Self::PacketAcknowledgement,
+            DataType::PacketReceiptAbsence => Self::PacketReceiptAbsence,
+            DataType::NextSequenceRecv => Self::NextSequenceRecv,
+            DataType::Header => Self::Header,
+        }
+    }
+}
+
+/// SignatureAndData contains a signature and the data signed over to create that
+/// signature.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Clone, PartialEq, Debug)]
+pub struct SignatureAndData {
+    /// the signature
+    pub signature: Vec<u8>,
+    /// the type of the signed data
+    pub data_type: DataType,
+    /// the marshaled data bytes
+    pub data: Vec<u8>,
+    /// the proof timestamp
+    pub timestamp: Timestamp,
+}
+
+impl SignatureAndData {
+    // ValidateBasic ensures that the signature and data fields are non-empty
+    // and that the data type is initialised.
+    pub fn validate_basic(&self) -> Result<(), Error> {
+        if self.signature.is_empty() {
+            return Err(Error::SignatureCannotEmpty);
+        }
+        if self.data.is_empty() {
+            return Err(Error::DataCannotEmpty);
+        }
+        if self.data_type == DataType::UninitializedUnspecified {
+            return Err(Error::UnknownDataType(
+                RawDataType::from(self.data_type) as i32
+            ));
+        }
+        if self.timestamp.into_tm_time().is_none() {
+            return Err(Error::TimeStampIsEmpty);
+        }
+        Ok(())
+    }
+
+    #[deprecated(since = "0.1.4", note = "use `validate_basic` instead")]
+    pub fn valida_basic(&self) -> Result<(), Error> {
+        self.validate_basic()
+    }
+}
+
+impl core::fmt::Display for SignatureAndData {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        write!(
+            f,
+            "signature: {:?}, data_type: {:?}, data: {:?}, timestamp: {}",
+            self.signature, self.data_type, self.data, self.timestamp
+        )
+    }
+}
+
+impl Protobuf<RawSignatureAndData> for SignatureAndData {}
+
+impl TryFrom<RawSignatureAndData> for SignatureAndData {
+    type Error = Error;
+
+    fn try_from(raw: RawSignatureAndData) -> Result<Self, Self::Error> {
+        let data_type = data_type_from_i32(raw.data_type)?;
+        let timestamp =
+            Timestamp::from_nanoseconds(raw.timestamp).map_err(Error::ParseTimeError)?;
+        Ok(Self {
+            signature: raw.signature,
+            data_type,
+            data: raw.data,
+            timestamp,
+        })
+    }
+}
+
+impl From<SignatureAndData> for RawSignatureAndData {
+    fn from(value: SignatureAndData) -> Self {
+        Self {
+            signature: value.signature,
+            data_type: RawDataType::from(value.data_type) as i32,
+            data: value.data,
+            timestamp: value.timestamp.nanoseconds(),
+        }
+    }
+}
+
+/// SignBytes defines the signed bytes used for signature verification.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Clone, PartialEq, Debug)]
+pub struct SignBytes {
+    /// the sequence number
+    pub sequence: u64,
+    /// the proof timestamp
+    pub timestamp: u64,
+    /// the public key diversifier
+    pub diversifier: String,
+    /// the type of the data used
+    pub data_type: DataType,
+    /// the marshaled data bytes
+    pub data: Vec<u8>,
+}
+
+impl Protobuf<RawSignBytes> for SignBytes {}
+
+impl TryFrom<RawSignBytes> for SignBytes {
+    type Error = Error;
+
+    fn try_from(raw: RawSignBytes) -> Result<Self, Self::Error> {
+        Ok(Self {
+            sequence: raw.sequence,
+            timestamp: raw.timestamp,
+            diversifier: raw.diversifier,
+            data_type: data_type_from_i32(raw.data_type)?,
+            data: raw.data,
+        })
+    }
+}
+
+impl From<SignBytes> for RawSignBytes {
+    fn from(value: SignBytes) -> Self {
+        Self {
+            sequence: value.sequence,
+            timestamp: value.timestamp,
+            diversifier: value.diversifier,
+            data_type: RawDataType::from(value.data_type) as i32,
+            data: value.data,
+        }
+    }
+}
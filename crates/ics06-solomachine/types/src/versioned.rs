@@ -0,0 +1,564 @@
+//! Decoding of solo machine `Any`s regardless of their protocol version.
+//!
+//! Hosts holding clients of mixed generations decode every solo machine type
+//! URL through [`SolomachineAny::decode`], which tags the domain value with
+//! the version it was encoded with, and re-encode it with
+//! [`SolomachineAny::encode`] at a chosen target version.
+
+use crate::client_state::{ClientState, SOLOMACHINE_CLIENT_STATE_TYPE_URL};
+use crate::consensus_state::{ConsensusState, SOLOMACHINE_CONSENSUS_STATE_TYPE_URL};
+use crate::error::Error;
+use crate::header::{Header, SOLOMACHINE_HEADER_TYPE_URL};
+use crate::misbehaviour::{Misbehaviour, SOLOMACHINE_MISBEHAVIOUR_TYPE_URL};
+use crate::v1::{
+    self, SOLOMACHINE_V1_CLIENT_STATE_TYPE_URL, SOLOMACHINE_V1_CONSENSUS_STATE_TYPE_URL,
+    SOLOMACHINE_V1_HEADER_TYPE_URL, SOLOMACHINE_V1_MISBEHAVIOUR_TYPE_URL,
+};
+use crate::v2::{
+    self, SOLOMACHINE_V2_CLIENT_STATE_TYPE_URL, SOLOMACHINE_V2_CONSENSUS_STATE_TYPE_URL,
+    SOLOMACHINE_V2_HEADER_TYPE_URL, SOLOMACHINE_V2_MISBEHAVIOUR_TYPE_URL,
+};
+use displaydoc::Display;
+use ibc_core::primitives::prelude::*;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::lightclients::solomachine::v2::{
+    ClientState as RawV2ClientState, ConsensusState as RawV2ConsensusState, Header as RawV2Header,
+    Misbehaviour as RawV2Misbehaviour,
+};
+use ibc_proto::Protobuf;
+use prost::Message;
+
+/// Package prefix shared by the type URLs of every solo machine version.
+const SOLOMACHINE_TYPE_URL_PREFIX: &str = "/ibc.lightclients.solomachine.";
+
+/// Version of the `ibc.lightclients.solomachine` protobuf package.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
+pub enum Version {
+    /// v1
+    V1,
+    /// v2
+    V2,
+    /// v3
+    V3,
+}
+
+/// A solo machine client state of any version.
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Clone, PartialEq, Debug)]
+pub enum VersionedClientState {
+    V1(v1::ClientState),
+    V2(v2::ClientState),
+    V3(ClientState),
+}
+
+/// A solo machine consensus state of any version. v1 and v2 share the v2
+/// domain type.
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Clone, PartialEq, Debug)]
+pub enum VersionedConsensusState {
+    V1(v2::ConsensusState),
+    V2(v2::ConsensusState),
+    V3(ConsensusState),
+}
+
+/// A solo machine header of any version. v1 and v2 share the v2 domain type.
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Clone, PartialEq)]
+pub enum VersionedHeader {
+    V1(v2::Header),
+    V2(v2::Header),
+    V3(Header),
+}
+
+/// A solo machine misbehaviour of any version. v1 and v2 share the v2 domain
+/// type.
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Clone, PartialEq)]
+pub enum VersionedMisbehaviour {
+    V1(v2::Misbehaviour),
+    V2(v2::Misbehaviour),
+    V3(Misbehaviour),
+}
+
+/// A decoded solo machine `Any`, tagged with its message and version.
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[derive(Clone, PartialEq)]
+pub enum SolomachineAny {
+    ClientState(VersionedClientState),
+    ConsensusState(VersionedConsensusState),
+    Header(VersionedHeader),
+    Misbehaviour(VersionedMisbehaviour),
+}
+
+fn decode_raw<R, T>(value: &[u8]) -> Result<T, Error>
+where
+    R: Message + Default,
+    T: TryFrom<R, Error = Error>,
+{
+    R::decode(value).map_err(Error::Decode)?.try_into()
+}
+
+fn unsupported(message: &'static str, from: Version, to: Version) -> Error {
+    Error::UnsupportedVersionConversion { message, from, to }
+}
+
+impl SolomachineAny {
+    /// Decodes an `Any` carrying any solo machine client state, consensus
+    /// state, header or misbehaviour type URL.
+    pub fn decode(raw: Any) -> Result<Self, Error> {
+        let value = raw.value.as_slice();
+        let decoded = match raw.type_url.as_str() {
+            SOLOMACHINE_V1_CLIENT_STATE_TYPE_URL => {
+                Self::ClientState(VersionedClientState::V1(decode_raw::<
+                    v1::raw::ClientState,
+                    _,
+                >(value)?))
+            }
+            SOLOMACHINE_V2_CLIENT_STATE_TYPE_URL => Self::ClientState(VersionedClientState::V2(
+                decode_raw::<RawV2ClientState, _>(value)?,
+            )),
+            SOLOMACHINE_CLIENT_STATE_TYPE_URL => Self::ClientState(VersionedClientState::V3(
+                ClientState::try_from(raw).map_err(Error::ClientError)?,
+            )),
+            SOLOMACHINE_V1_CONSENSUS_STATE_TYPE_URL => {
+                Self::ConsensusState(VersionedConsensusState::V1(decode_raw::<
+                    RawV2ConsensusState,
+                    _,
+                >(value)?))
+            }
+            SOLOMACHINE_V2_CONSENSUS_STATE_TYPE_URL => {
+                Self::ConsensusState(VersionedConsensusState::V2(decode_raw::<
+                    RawV2ConsensusState,
+                    _,
+                >(value)?))
+            }
+            SOLOMACHINE_CONSENSUS_STATE_TYPE_URL => {
+                Self::ConsensusState(VersionedConsensusState::V3(
+                    ConsensusState::try_from(raw).map_err(Error::ClientError)?,
+                ))
+            }
+            SOLOMACHINE_V1_HEADER_TYPE_URL => {
+                Self::Header(VersionedHeader::V1(decode_raw::<RawV2Header, _>(value)?))
+            }
+            SOLOMACHINE_V2_HEADER_TYPE_URL => {
+                Self::Header(VersionedHeader::V2(decode_raw::<RawV2Header, _>(value)?))
+            }
+            SOLOMACHINE_HEADER_TYPE_URL => Self::Header(VersionedHeader::V3(
+                Header::try_from(raw).map_err(Error::ClientError)?,
+            )),
+            SOLOMACHINE_V1_MISBEHAVIOUR_TYPE_URL => Self::Misbehaviour(VersionedMisbehaviour::V1(
+                decode_raw::<RawV2Misbehaviour, _>(value)?,
+            )),
+            SOLOMACHINE_V2_MISBEHAVIOUR_TYPE_URL => Self::Misbehaviour(VersionedMisbehaviour::V2(
+                decode_raw::<RawV2Misbehaviour, _>(value)?,
+            )),
+            SOLOMACHINE_MISBEHAVIOUR_TYPE_URL => Self::Misbehaviour(VersionedMisbehaviour::V3(
+                Misbehaviour::try_from(raw).map_err(Error::ClientError)?,
+            )),
+            _ if raw.type_url.starts_with(SOLOMACHINE_TYPE_URL_PREFIX) => {
+                return Err(Error::UnknownSolomachineType {
+                    type_url: raw.type_url,
+                })
+            }
+            _ => {
+                return Err(Error::NotSolomachineType {
+                    type_url: raw.type_url,
+                })
+            }
+        };
+        Ok(decoded)
+    }
+
+    /// Returns the version the value was encoded with.
+    pub fn version(&self) -> Version {
+        match self {
+            Self::ClientState(client_state) => client_state.version(),
+            Self::ConsensusState(consensus_state) => consensus_state.version(),
+            Self::Header(header) => header.version(),
+            Self::Misbehaviour(misbehaviour) => misbehaviour.version(),
+        }
+    }
+
+    /// Converts the value to `target` and encodes it with the type URL of
+    /// that version.
+    pub fn encode(self, target: Version) -> Result<Any, Error> {
+        let any = match self {
+            Self::ClientState(client_state) => client_state.into_version(target)?.into(),
+            Self::ConsensusState(consensus_state) => consensus_state.into_version(target).into(),
+            Self::Header(header) => header.into_version(target)?.into(),
+            Self::Misbehaviour(misbehaviour) => misbehaviour.into_version(target)?.into(),
+        };
+        Ok(any)
+    }
+}
+
+impl From<SolomachineAny> for Any {
+    fn from(value: SolomachineAny) -> Self {
+        match value {
+            SolomachineAny::ClientState(client_state) => client_state.into(),
+            SolomachineAny::ConsensusState(consensus_state) => consensus_state.into(),
+            SolomachineAny::Header(header) => header.into(),
+            SolomachineAny::Misbehaviour(misbehaviour) => misbehaviour.into(),
+        }
+    }
+}
+
+impl VersionedClientState {
+    /// Returns the version of the client state.
+    pub fn version(&self) -> Version {
+        match self {
+            Self::V1(_) => Version::V1,
+            Self::V2(_) => Version::V2,
+            Self::V3(_) => Version::V3,
+        }
+    }
+
+    /// Converts the client state to `target` through v2, which every version
+    /// converts to and from. Fields without an equivalent in `target`, such as
+    /// the v1 frozen sequence or the v3 inactivity period, are dropped.
+    pub fn into_version(self, target: Version) -> Result<Self, Error> {
+        if self.version() == target {
+            return Ok(self);
+        }
+        let client_state = match self {
+            Self::V1(client_state) => client_state.into(),
+            Self::V2(client_state) => client_state,
+            Self::V3(client_state) => client_state.into(),
+        };
+        Ok(match target {
+            Version::V1 => Self::V1(client_state.into()),
+            Version::V2 => Self::V2(client_state),
+            Version::V3 => Self::V3(client_state.into()),
+        })
+    }
+}
+
+impl From<VersionedClientState> for Any {
+    fn from(value: VersionedClientState) -> Self {
+        match value {
+            VersionedClientState::V1(client_state) => client_state.into(),
+            VersionedClientState::V2(client_state) => client_state.into(),
+            VersionedClientState::V3(client_state) => client_state.into(),
+        }
+    }
+}
+
+impl VersionedConsensusState {
+    /// Returns the version of the consensus state.
+    pub fn version(&self) -> Version {
+        match self {
+            Self::V1(_) => Version::V1,
+            Self::V2(_) => Version::V2,
+            Self::V3(_) => Version::V3,
+        }
+    }
+
+    /// Converts the consensus state to `target`. Consensus states have the
+    /// same fields in every version.
+    pub fn into_version(self, target: Version) -> Self {
+        let consensus_state = match self {
+            Self::V1(consensus_state) | Self::V2(consensus_state) => consensus_state,
+            Self::V3(consensus_state) => consensus_state.into(),
+        };
+        match target {
+            Version::V1 => Self::V1(consensus_state),
+            Version::V2 => Self::V2(consensus_state),
+            Version::V3 => Self::V3(consensus_state.into()),
+        }
+    }
+}
+
+impl From<VersionedConsensusState> for Any {
+    fn from(value: VersionedConsensusState) -> Self {
+        match value {
+            VersionedConsensusState::V1(consensus_state) => Any {
+                type_url: SOLOMACHINE_V1_CONSENSUS_STATE_TYPE_URL.to_string(),
+                value: Protobuf::<RawV2ConsensusState>::encode_vec(consensus_state),
+            },
+            VersionedConsensusState::V2(consensus_state) => consensus_state.into(),
+            VersionedConsensusState::V3(consensus_state) => consensus_state.into(),
+        }
+    }
+}
+
+impl VersionedHeader {
+    /// Returns the version of the header.
+    pub fn version(&self) -> Version {
+        match self {
+            Self::V1(_) => Version::V1,
+            Self::V2(_) => Version::V2,
+            Self::V3(_) => Version::V3,
+        }
+    }
+
+    /// Converts the header to `target`. v1 and v2 headers are interchangeable,
+    /// but v3 headers sign over a path rather than a data type, so a header
+    /// cannot be converted between v3 and the earlier versions without
+    /// invalidating its signature.
+    pub fn into_version(self, target: Version) -> Result<Self, Error> {
+        match (self, target) {
+            (Self::V1(header) | Self::V2(header), Version::V1) => Ok(Self::V1(header)),
+            (Self::V1(header) | Self::V2(header), Version::V2) => Ok(Self::V2(header)),
+            (Self::V3(header), Version::V3) => Ok(Self::V3(header)),
+            (header, target) => Err(unsupported("Header", header.version(), target)),
+        }
+    }
+}
+
+impl From<VersionedHeader> for Any {
+    fn from(value: VersionedHeader) -> Self {
+        match value {
+            VersionedHeader::V1(header) => Any {
+                type_url: SOLOMACHINE_V1_HEADER_TYPE_URL.to_string(),
+                value: Protobuf::<RawV2Header>::encode_vec(header),
+            },
+            VersionedHeader::V2(header) => header.into(),
+            VersionedHeader::V3(header) => header.into(),
+        }
+    }
+}
+
+impl VersionedMisbehaviour {
+    /// Returns the version of the misbehaviour.
+    pub fn version(&self) -> Version {
+        match self {
+            Self::V1(_) => Version::V1,
+            Self::V2(_) => Version::V2,
+            Self::V3(_) => Version::V3,
+        }
+    }
+
+    /// Converts the misbehaviour to `target`. As for headers, only v1 and v2
+    /// misbehaviours are interchangeable.
+    pub fn into_version(self, target: Version) -> Result<Self, Error> {
+        match (self, target) {
+            (Self::V1(misbehaviour) | Self::V2(misbehaviour), Version::V1) => {
+                Ok(Self::V1(misbehaviour))
+            }
+            (Self::V1(misbehaviour) | Self::V2(misbehaviour), Version::V2) => {
+                Ok(Self::V2(misbehaviour))
+            }
+            (Self::V3(misbehaviour), Version::V3) => Ok(Self::V3(misbehaviour)),
+            (misbehaviour, target) => {
+                Err(unsupported("Misbehaviour", misbehaviour.version(), target))
+            }
+        }
+    }
+}
+
+impl From<VersionedMisbehaviour> for Any {
+    fn from(value: VersionedMisbehaviour) -> Self {
+        match value {
+            VersionedMisbehaviour::V1(misbehaviour) => Any {
+                type_url: SOLOMACHINE_V1_MISBEHAVIOUR_TYPE_URL.to_string(),
+                value: Protobuf::<RawV2Misbehaviour>::encode_vec(misbehaviour),
+            },
+            VersionedMisbehaviour::V2(misbehaviour) => misbehaviour.into(),
+            VersionedMisbehaviour::V3(misbehaviour) => misbehaviour.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cosmos::crypto::PublicKey;
+    use ibc_core::client::types::Height;
+    use ibc_core::primitives::Timestamp;
+
+    fn public_key() -> PublicKey {
+        const EXAMPLE_JSON: &str = "{\"@type\":\"/cosmos.crypto.ed25519.PubKey\",\"key\":\"sEEsVGkXvyewKLWMJbHVDRkBoerW0IIwmj1rHkabtHU=\"}";
+        EXAMPLE_JSON.parse::<PublicKey>().unwrap()
+    }
+
+    fn consensus_state() -> v2::ConsensusState {
+        v2::ConsensusState {
+            public_key: public_key(),
+            diversifier: "testing".into(),
+            timestamp: Timestamp::from_nanoseconds(10).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_client_state_versions() {
+        let v1_client_state = v1::ClientState {
+            sequence: Height::new(0, 5).unwrap(),
+            frozen_sequence: 5,
+            consensus_state: consensus_state(),
+            allow_update_after_proposal: true,
+        };
+        let decoded = SolomachineAny::decode(Any::from(v1_client_state.clone())).unwrap();
+        assert_eq!(decoded.version(), Version::V1);
+
+        // v1 -> v3 keeps the frozen state and allow_update_after_proposal
+        let v3_any = decoded.encode(Version::V3).unwrap();
+        assert_eq!(v3_any.type_url, SOLOMACHINE_CLIENT_STATE_TYPE_URL);
+        let decoded = SolomachineAny::decode(v3_any).unwrap();
+        assert_eq!(decoded.version(), Version::V3);
+        match &decoded {
+            SolomachineAny::ClientState(VersionedClientState::V3(client_state)) => {
+                assert!(client_state.is_frozen);
+                assert!(client_state.allow_update_after_proposal);
+            }
+            _ => panic!("expected a v3 client state"),
+        }
+
+        // and back to v1
+        let v1_any = decoded.encode(Version::V1).unwrap();
+        assert_eq!(v1_any, Any::from(v1_client_state));
+    }
+
+    #[test]
+    fn test_consensus_state_versions() {
+        let v2_any = Any::from(consensus_state());
+        let decoded = SolomachineAny::decode(v2_any.clone()).unwrap();
+        assert_eq!(decoded.version(), Version::V2);
+
+        let v1_any = decoded.clone().encode(Version::V1).unwrap();
+        assert_eq!(v1_any.type_url, SOLOMACHINE_V1_CONSENSUS_STATE_TYPE_URL);
+        assert_eq!(v1_any.value, v2_any.value);
+
+        let v3_any = decoded.encode(Version::V3).unwrap();
+        assert_eq!(
+            SolomachineAny::decode(v3_any)
+                .unwrap()
+                .encode(Version::V2)
+                .unwrap(),
+            v2_any
+        );
+    }
+
+    #[test]
+    fn test_header_versions() {
+        let header = v2::Header {
+            sequence: Height::new(0, 1).unwrap(),
+            timestamp: Timestamp::from_nanoseconds(10).unwrap(),
+            signature: vec![1, 2, 3],
+            new_public_key: public_key(),
+            new_diversifier: "testing".into(),
+        };
+        let decoded = SolomachineAny::decode(Any::from(header)).unwrap();
+        assert_eq!(decoded.version(), Version::V2);
+
+        let v1_any = decoded.clone().encode(Version::V1).unwrap();
+        assert_eq!(v1_any.type_url, SOLOMACHINE_V1_HEADER_TYPE_URL);
+        assert!(
+            SolomachineAny::decode(v1_any).unwrap() == {
+                match decoded.clone() {
+                    SolomachineAny::Header(VersionedHeader::V2(header)) => {
+                        SolomachineAny::Header(VersionedHeader::V1(header))
+                    }
+                    _ => panic!("expected a v2 header"),
+                }
+            }
+        );
+
+        assert!(matches!(
+            decoded.encode(Version::V3),
+            Err(Error::UnsupportedVersionConversion {
+                message: "Header",
+                from: Version::V2,
+                to: Version::V3,
+            })
+        ));
+    }
+
+    #[test]
+    fn test_unknown_versions() {
+        let unknown_version = Any {
+            type_url: "/ibc.lightclients.solomachine.v4.ClientState".into(),
+            value: vec![],
+        };
+        assert!(matches!(
+            SolomachineAny::decode(unknown_version),
+            Err(Error::UnknownSolomachineType { .. })
+        ));
+
+        let unknown_message = Any {
+            type_url: "/ibc.lightclients.solomachine.v2.SignBytes".into(),
+            value: vec![],
+        };
+        assert!(matches!(
+            SolomachineAny::decode(unknown_message),
+            Err(Error::UnknownSolomachineType { .. })
+        ));
+
+        let other_client = Any {
+            type_url: "/ibc.lightclients.tendermint.v1.ClientState".into(),
+            value: vec![],
+        };
+        assert!(matches!(
+            SolomachineAny::decode(other_client),
+            Err(Error::NotSolomachineType { .. })
+        ));
+    }
+}
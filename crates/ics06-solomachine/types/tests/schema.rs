@@ -0,0 +1,58 @@
+//! Emits the JSON schemas of the solo machine domain types to `schemas/` and
+//! checks them against the committed snapshots.
+//!
+//! A missing or outdated snapshot fails the test. Run with `UPDATE_SCHEMAS=1`
+//! to write the snapshots after a deliberate change, and commit them.
+#![cfg(feature = "schema")]
+
+use std::fs;
+use std::path::PathBuf;
+
+use ibc_client_solomachine_types::cosmos::crypto::PublicKey;
+use ibc_client_solomachine_types::proof::types::signature_and_data::SignatureAndData;
+use ibc_client_solomachine_types::{ClientState, ConsensusState, Header, Misbehaviour};
+use schemars::schema::RootSchema;
+use schemars::schema_for;
+
+fn check_snapshot(name: &str, schema: RootSchema) {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("schemas");
+    let path = dir.join(format!("{name}.json"));
+    let emitted = serde_json_std::to_string_pretty(&schema).unwrap() + "\n";
+
+    if std::env::var_os("UPDATE_SCHEMAS").is_some() {
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&path, &emitted).unwrap();
+        return;
+    }
+
+    let snapshot = fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "missing schema snapshot of {name}, run with UPDATE_SCHEMAS=1 to write {}",
+            path.display()
+        )
+    });
+    assert_eq!(
+        snapshot,
+        emitted,
+        "schema of {name} changed, run with UPDATE_SCHEMAS=1 to update {}",
+        path.display()
+    );
+}
+
+#[test]
+fn schemas_match_snapshots() {
+    check_snapshot("client_state", schema_for!(ClientState));
+    check_snapshot("consensus_state", schema_for!(ConsensusState));
+    check_snapshot("header", schema_for!(Header));
+    check_snapshot("misbehaviour", schema_for!(Misbehaviour));
+    check_snapshot("signature_and_data", schema_for!(SignatureAndData));
+    check_snapshot("public_key", schema_for!(PublicKey));
+}
+
+#[test]
+fn public_key_schema_is_its_json_form() {
+    let schema = serde_json_std::to_value(schema_for!(PublicKey)).unwrap();
+    let properties = &schema["properties"];
+    assert_eq!(properties["@type"]["type"], "string");
+    assert_eq!(properties["key"]["type"], "string");
+}
@@ -0,0 +1,463 @@
+//! Storage adapter running the solo machine client on the store `08-wasm`
+//! dedicated to the contract.
+//!
+//! The store is already scoped to the client, so the client ids passed by the
+//! client handlers are ignored. States are kept in the layout ibc-go uses for
+//! its own clients:
+//!
+//! - `clientState`: the solo machine client state, wrapped in a
+//!   `wasm.v1.ClientState`,
+//! - `consensusStates/{height}`: the consensus states, wrapped in a
+//!   `wasm.v1.ConsensusState`,
+//! - `consensusStates/{height}/processedTime` and `processedHeight`: the host
+//!   time and height the consensus states were stored at,
+//! - `iterateConsensusStates{revision}{height}`: the consensus state keys,
+//!   ordered by height.
+
+use crate::raw::{
+    ClientState as RawWasmClientState, ConsensusState as RawWasmConsensusState,
+    WASM_CLIENT_STATE_TYPE_URL, WASM_CONSENSUS_STATE_TYPE_URL,
+};
+use cosmwasm_std::{Env, Order, Storage};
+use ibc_client_solomachine::client_state::ClientState;
+use ibc_client_solomachine::consensus_state::ConsensusState as SmConsensusState;
+use ibc_client_solomachine::context::{CommonContext, ValidationContext};
+use ibc_core::client::context::client_state::ClientStateCommon;
+use ibc_core::client::context::consensus_state::ConsensusState;
+use ibc_core::client::context::{ClientExecutionContext, ClientValidationContext};
+use ibc_core::client::types::error::ClientError;
+use ibc_core::client::types::Height;
+use ibc_core::commitment_types::commitment::CommitmentRoot;
+use ibc_core::handler::types::error::ContextError;
+use ibc_core::host::types::identifiers::{ClientId, ClientType};
+use ibc_core::host::types::path::{ClientConsensusStatePath, ClientStatePath};
+use ibc_core::primitives::Timestamp;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::Protobuf;
+use prost::Message;
+use std::str::FromStr;
+
+pub const WASM_CLIENT_TYPE: &str = "08-wasm";
+
+pub const CLIENT_STATE_KEY: &[u8] = b"clientState";
+pub const CONSENSUS_STATE_PREFIX: &str = "consensusStates";
+pub const ITERATE_CONSENSUS_STATE_PREFIX: &[u8] = b"iterateConsensusStates";
+pub const PROCESSED_TIME_KEY: &str = "processedTime";
+pub const PROCESSED_HEIGHT_KEY: &str = "processedHeight";
+
+/// Returns the client id handed to the client handlers. Only the store the
+/// host gives the contract identifies the client.
+pub fn client_id() -> ClientId {
+    let client_type =
+        ClientType::from_str(WASM_CLIENT_TYPE).expect("Never fails because it's valid");
+    ClientId::new(client_type, 0).expect("Never fails because it's valid")
+}
+
+fn consensus_state_key(height: &Height) -> Vec<u8> {
+    format!("{CONSENSUS_STATE_PREFIX}/{height}").into_bytes()
+}
+
+fn processed_key(height: &Height, key: &str) -> Vec<u8> {
+    format!("{CONSENSUS_STATE_PREFIX}/{height}/{key}").into_bytes()
+}
+
+fn iteration_key(height: &Height) -> Vec<u8> {
+    let mut key = ITERATE_CONSENSUS_STATE_PREFIX.to_vec();
+    key.extend(height.revision_number().to_be_bytes());
+    key.extend(height.revision_height().to_be_bytes());
+    key
+}
+
+fn other(description: impl ToString) -> ClientError {
+    ClientError::Other {
+        description: description.to_string(),
+    }
+}
+
+/// The consensus states the contract stores.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AnyConsensusState {
+    Solomachine(SmConsensusState),
+}
+
+impl From<SmConsensusState> for AnyConsensusState {
+    fn from(consensus_state: SmConsensusState) -> Self {
+        Self::Solomachine(consensus_state)
+    }
+}
+
+impl TryFrom<AnyConsensusState> for SmConsensusState {
+    type Error = ClientError;
+
+    fn try_from(consensus_state: AnyConsensusState) -> Result<Self, Self::Error> {
+        match consensus_state {
+            AnyConsensusState::Solomachine(consensus_state) => Ok(consensus_state),
+        }
+    }
+}
+
+impl From<AnyConsensusState> for Any {
+    fn from(consensus_state: AnyConsensusState) -> Self {
+        match consensus_state {
+            AnyConsensusState::Solomachine(consensus_state) => consensus_state.into(),
+        }
+    }
+}
+
+impl ConsensusState for AnyConsensusState {
+    fn root(&self) -> &CommitmentRoot {
+        match self {
+            Self::Solomachine(consensus_state) => consensus_state.root(),
+        }
+    }
+
+    fn timestamp(&self) -> Timestamp {
+        match self {
+            Self::Solomachine(consensus_state) => consensus_state.timestamp(),
+        }
+    }
+
+    fn encode_vec(self) -> Vec<u8> {
+        match self {
+            Self::Solomachine(consensus_state) => {
+                <SmConsensusState as Protobuf<Any>>::encode_vec(consensus_state)
+            }
+        }
+    }
+}
+
+enum StorageRef<'a> {
+    Ref(&'a dyn Storage),
+    Mut(&'a mut dyn Storage),
+}
+
+/// Host context of the contract: its store and the block it runs in.
+pub struct Context<'a> {
+    storage: StorageRef<'a>,
+    env: Env,
+    checksum: Option<Vec<u8>>,
+}
+
+impl<'a> Context<'a> {
+    /// Read-only context, for queries.
+    pub fn new_ref(storage: &'a dyn Storage, env: Env) -> Self {
+        Self {
+            storage: StorageRef::Ref(storage),
+            env,
+            checksum: None,
+        }
+    }
+
+    /// Writable context, for instantiation and sudo calls.
+    pub fn new_mut(storage: &'a mut dyn Storage, env: Env) -> Self {
+        Self {
+            storage: StorageRef::Mut(storage),
+            env,
+            checksum: None,
+        }
+    }
+
+    /// Sets the contract checksum stored along the client state. Without it,
+    /// the checksum of the stored client state is kept.
+    pub fn with_checksum(self, checksum: Vec<u8>) -> Self {
+        Self {
+            checksum: Some(checksum),
+            ..self
+        }
+    }
+
+    fn storage(&self) -> &dyn Storage {
+        match &self.storage {
+            StorageRef::Ref(storage) => *storage,
+            StorageRef::Mut(storage) => &**storage,
+        }
+    }
+
+    fn storage_mut(&mut self) -> Result<&mut dyn Storage, ClientError> {
+        match &mut self.storage {
+            StorageRef::Ref(_) => Err(other("cannot write to a read-only context")),
+            StorageRef::Mut(storage) => Ok(&mut **storage),
+        }
+    }
+
+    fn wasm_client_state(&self) -> Result<RawWasmClientState, ClientError> {
+        let bytes = self
+            .storage()
+            .get(CLIENT_STATE_KEY)
+            .ok_or_else(|| other("client state not found"))?;
+        let any = Any::decode(bytes.as_slice()).map_err(other)?;
+        if any.type_url != WASM_CLIENT_STATE_TYPE_URL {
+            return Err(ClientError::UnknownClientStateType {
+                client_state_type: any.type_url,
+            });
+        }
+        RawWasmClientState::decode(any.value.as_slice()).map_err(other)
+    }
+
+    /// Returns the stored client state.
+    pub fn client_state(&self) -> Result<ClientState, ClientError> {
+        let wasm_client_state = self.wasm_client_state()?;
+        let any = Any::decode(wasm_client_state.data.as_slice()).map_err(other)?;
+        ClientState::try_from(any)
+    }
+
+    /// Returns the consensus state stored at `height`, if any.
+    pub fn stored_consensus_state(
+        &self,
+        height: &Height,
+    ) -> Result<Option<SmConsensusState>, ClientError> {
+        self.storage()
+            .get(&consensus_state_key(height))
+            .map(|bytes| decode_consensus_state(&bytes))
+            .transpose()
+    }
+
+    fn consensus_state_by_iteration_key(
+        &self,
+        key: Option<(Vec<u8>, Vec<u8>)>,
+    ) -> Result<Option<AnyConsensusState>, ContextError> {
+        let Some((_, consensus_state_key)) = key else {
+            return Ok(None);
+        };
+        let bytes = self
+            .storage()
+            .get(&consensus_state_key)
+            .ok_or_else(|| other("consensus state missing for its iteration key"))?;
+        Ok(Some(decode_consensus_state(&bytes)?.into()))
+    }
+}
+
+fn decode_consensus_state(bytes: &[u8]) -> Result<SmConsensusState, ClientError> {
+    let any = Any::decode(bytes).map_err(other)?;
+    if any.type_url != WASM_CONSENSUS_STATE_TYPE_URL {
+        return Err(ClientError::UnknownConsensusStateType {
+            consensus_state_type: any.type_url,
+        });
+    }
+    let wasm_consensus_state =
+        RawWasmConsensusState::decode(any.value.as_slice()).map_err(other)?;
+    SmConsensusState::try_from(Any::decode(wasm_consensus_state.data.as_slice()).map_err(other)?)
+}
+
+impl CommonContext for Context<'_> {
+    type ConversionError = ClientError;
+    type AnyConsensusState = AnyConsensusState;
+
+    fn host_timestamp(&self) -> Result<Timestamp, ContextError> {
+        Timestamp::from_nanoseconds(self.env.block.time.nanos()).map_err(|e| other(e).into())
+    }
+
+    fn host_height(&self) -> Result<Height, ContextError> {
+        Ok(Height::new(0, self.env.block.height)?)
+    }
+
+    fn consensus_state(
+        &self,
+        client_cons_state_path: &ClientConsensusStatePath,
+    ) -> Result<Self::AnyConsensusState, ContextError> {
+        let height = Height::new(
+            client_cons_state_path.revision_number,
+            client_cons_state_path.revision_height,
+        )?;
+        self.stored_consensus_state(&height)?
+            .map(Into::into)
+            .ok_or_else(|| {
+                ClientError::ConsensusStateNotFound {
+                    client_id: client_cons_state_path.client_id.clone(),
+                    height,
+                }
+                .into()
+            })
+    }
+}
+
+impl ValidationContext for Context<'_> {
+    fn next_consensus_state(
+        &self,
+        _client_id: &ClientId,
+        height: &Height,
+    ) -> Result<Option<Self::AnyConsensusState>, ContextError> {
+        // iteration keys have a fixed length, so the key of `height` followed
+        // by any byte sorts between it and the key of the next height
+        let mut start = iteration_key(height);
+        start.push(0);
+        let mut end = ITERATE_CONSENSUS_STATE_PREFIX.to_vec();
+        end.extend([u8::MAX; 17]);
+        let next = self
+            .storage()
+            .range(Some(&start), Some(&end), Order::Ascending)
+            .next();
+        self.consensus_state_by_iteration_key(next)
+    }
+
+    fn prev_consensus_state(
+        &self,
+        _client_id: &ClientId,
+        height: &Height,
+    ) -> Result<Option<Self::AnyConsensusState>, ContextError> {
+        let prev = self
+            .storage()
+            .range(
+                Some(ITERATE_CONSENSUS_STATE_PREFIX),
+                Some(&iteration_key(height)),
+                Order::Descending,
+            )
+            .next();
+        self.consensus_state_by_iteration_key(prev)
+    }
+}
+
+impl ClientValidationContext for Context<'_> {
+    fn client_update_time(
+        &self,
+        client_id: &ClientId,
+        height: &Height,
+    ) -> Result<Timestamp, ContextError> {
+        let bytes = self
+            .storage()
+            .get(&processed_key(height, PROCESSED_TIME_KEY))
+            .ok_or_else(|| {
+                other(format!(
+                    "no update time for client {} at {}",
+                    client_id, height
+                ))
+            })?;
+        let nanoseconds = u64::from_be_bytes(
+            bytes
+                .as_slice()
+                .try_into()
+                .map_err(|_| other("malformed update time"))?,
+        );
+        Timestamp::from_nanoseconds(nanoseconds).map_err(|e| other(e).into())
+    }
+
+    fn client_update_height(
+        &self,
+        client_id: &ClientId,
+        height: &Height,
+    ) -> Result<Height, ContextError> {
+        let bytes = self
+            .storage()
+            .get(&processed_key(height, PROCESSED_HEIGHT_KEY))
+            .ok_or_else(|| {
+                other(format!(
+                    "no update height for client {} at {}",
+                    client_id, height
+                ))
+            })?;
+        let height = String::from_utf8(bytes).map_err(other)?;
+        Height::from_str(&height).map_err(|e| other(e).into())
+    }
+}
+
+impl ClientExecutionContext for Context<'_> {
+    type V = Self;
+    type AnyClientState = ClientState;
+    type AnyConsensusState = AnyConsensusState;
+
+    fn store_client_state(
+        &mut self,
+        _client_state_path: ClientStatePath,
+        client_state: Self::AnyClientState,
+    ) -> Result<(), ContextError> {
+        let checksum = match &self.checksum {
+            Some(checksum) => checksum.clone(),
+            None => self.wasm_client_state()?.checksum,
+        };
+        let wasm_client_state = RawWasmClientState {
+            latest_height: Some(client_state.latest_height().into()),
+            data: Any::from(client_state).encode_to_vec(),
+            checksum,
+        };
+        let any = Any {
+            type_url: WASM_CLIENT_STATE_TYPE_URL.to_string(),
+            value: wasm_client_state.encode_to_vec(),
+        };
+        self.storage_mut()?
+            .set(CLIENT_STATE_KEY, &any.encode_to_vec());
+        Ok(())
+    }
+
+    fn store_consensus_state(
+        &mut self,
+        consensus_state_path: ClientConsensusStatePath,
+        consensus_state: Self::AnyConsensusState,
+    ) -> Result<(), ContextError> {
+        let height = Height::new(
+            consensus_state_path.revision_number,
+            consensus_state_path.revision_height,
+        )?;
+        let wasm_consensus_state = RawWasmConsensusState {
+            data: Any::from(consensus_state).encode_to_vec(),
+        };
+        let any = Any {
+            type_url: WASM_CONSENSUS_STATE_TYPE_URL.to_string(),
+            value: wasm_consensus_state.encode_to_vec(),
+        };
+        let key = consensus_state_key(&height);
+        let storage = self.storage_mut()?;
+        storage.set(&key, &any.encode_to_vec());
+        storage.set(&iteration_key(&height), &key);
+        Ok(())
+    }
+
+    fn delete_consensus_state(
+        &mut self,
+        consensus_state_path: ClientConsensusStatePath,
+    ) -> Result<(), ContextError> {
+        let height = Height::new(
+            consensus_state_path.revision_number,
+            consensus_state_path.revision_height,
+        )?;
+        let storage = self.storage_mut()?;
+        storage.remove(&consensus_state_key(&height));
+        storage.remove(&iteration_key(&height));
+        Ok(())
+    }
+
+    fn store_update_time(
+        &mut self,
+        _client_id: ClientId,
+        height: Height,
+        host_timestamp: Timestamp,
+    ) -> Result<(), ContextError> {
+        self.storage_mut()?.set(
+            &processed_key(&height, PROCESSED_TIME_KEY),
+            &host_timestamp.nanoseconds().to_be_bytes(),
+        );
+        Ok(())
+    }
+
+    fn store_update_height(
+        &mut self,
+        _client_id: ClientId,
+        height: Height,
+        host_height: Height,
+    ) -> Result<(), ContextError> {
+        self.storage_mut()?.set(
+            &processed_key(&height, PROCESSED_HEIGHT_KEY),
+            host_height.to_string().as_bytes(),
+        );
+        Ok(())
+    }
+
+    fn delete_update_time(
+        &mut self,
+        _client_id: ClientId,
+        height: Height,
+    ) -> Result<(), ContextError> {
+        self.storage_mut()?
+            .remove(&processed_key(&height, PROCESSED_TIME_KEY));
+        Ok(())
+    }
+
+    fn delete_update_height(
+        &mut self,
+        _client_id: ClientId,
+        height: Height,
+    ) -> Result<(), ContextError> {
+        self.storage_mut()?
+            .remove(&processed_key(&height, PROCESSED_HEIGHT_KEY));
+        Ok(())
+    }
+}
@@ -0,0 +1,203 @@
+//! Entry points of the contract, called by the `08-wasm` module.
+
+use crate::context::{client_id, Context};
+use crate::error::ContractError;
+use crate::msg::{
+    CheckForMisbehaviourResult, EmptyResult, InstantiateMsg, QueryMsg, StatusResult, SudoMsg,
+    TimestampAtHeightResult, UpdateStateResult,
+};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response};
+use ibc_client_solomachine::client_state::ClientState;
+use ibc_client_solomachine::context::CommonContext;
+use ibc_client_solomachine::types::error::Error as SmError;
+use ibc_client_solomachine::types::proof::types::timestamped_signature_data::TimestampedSignatureData;
+use ibc_client_solomachine::types::{
+    SOLOMACHINE_HEADER_TYPE_URL, SOLOMACHINE_MISBEHAVIOUR_TYPE_URL,
+};
+use ibc_core::client::context::client_state::{
+    ClientStateCommon, ClientStateExecution, ClientStateValidation,
+};
+use ibc_core::client::context::consensus_state::ConsensusState;
+use ibc_core::client::context::ClientExecutionContext;
+use ibc_core::client::types::{Height, UpdateKind};
+use ibc_core::commitment_types::commitment::CommitmentProofBytes;
+use ibc_core::host::types::path::{ClientConsensusStatePath, ClientStatePath};
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::Protobuf;
+use prost::Message;
+
+// Decode_any decodes the protobuf-encoded `Any` the host passes a state or a
+// client message as.
+fn decode_any(bytes: &Binary) -> Result<Any, ContractError> {
+    Ok(Any::decode(bytes.as_slice())?)
+}
+
+// Update_kind tells headers from misbehaviour, which `08-wasm` passes alike.
+fn update_kind(client_message: &Any) -> Result<UpdateKind, ContractError> {
+    match client_message.type_url.as_str() {
+        SOLOMACHINE_HEADER_TYPE_URL => Ok(UpdateKind::UpdateClient),
+        SOLOMACHINE_MISBEHAVIOUR_TYPE_URL => Ok(UpdateKind::SubmitMisbehaviour),
+        _ => Err(ContractError::UnknownClientMessage {
+            type_url: client_message.type_url.clone(),
+        }),
+    }
+}
+
+fn consensus_state_path(height: Height) -> ClientConsensusStatePath {
+    ClientConsensusStatePath::new(
+        client_id(),
+        height.revision_number(),
+        height.revision_height(),
+    )
+}
+
+// Consume_sequence advances the client past the sequence a proof was verified
+// at and to the proof timestamp, as ibc-go does: each signature proves one
+// value only.
+fn consume_sequence(
+    ctx: &mut Context<'_>,
+    client_state: ClientState,
+    proof: &Binary,
+) -> Result<(), ContractError> {
+    let proof = TimestampedSignatureData::decode_vec(proof.as_slice()).map_err(|e| {
+        SmError::UndecodableProof {
+            reason: e.to_string(),
+        }
+    })?;
+    let mut client_state = client_state.inner().clone();
+    client_state.sequence = client_state.sequence.increment();
+    client_state.consensus_state.timestamp = proof.timestamp;
+    ctx.store_client_state(
+        ClientStatePath::new(&client_id()),
+        ClientState::from(client_state),
+    )?;
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut<'_>,
+    env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let client_state = ClientState::try_from(decode_any(&msg.client_state)?)?;
+    let consensus_state = decode_any(&msg.consensus_state)?;
+    let mut ctx = Context::new_mut(deps.storage, env).with_checksum(msg.checksum.to_vec());
+
+    client_state.initialise(&mut ctx, &client_id(), consensus_state)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(deps: DepsMut<'_>, env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+    let mut ctx = Context::new_mut(deps.storage, env);
+    let client_state = ctx.client_state()?;
+    let client_id = client_id();
+
+    let result = match msg {
+        SudoMsg::UpdateState(msg) => {
+            let header = decode_any(&msg.client_message)?;
+            let heights = client_state.update_state(&mut ctx, &client_id, header)?;
+            to_json_binary(&UpdateStateResult {
+                heights: heights.into_iter().map(Into::into).collect(),
+            })?
+        }
+        SudoMsg::UpdateStateOnMisbehaviour(msg) => {
+            let client_message = decode_any(&msg.client_message)?;
+            let update_kind = update_kind(&client_message)?;
+            client_state.update_state_on_misbehaviour(
+                &mut ctx,
+                &client_id,
+                client_message,
+                &update_kind,
+            )?;
+            to_json_binary(&EmptyResult {})?
+        }
+        SudoMsg::VerifyUpgradeAndUpdateState(msg) => {
+            let upgrade_client_state = decode_any(&msg.upgrade_client_state)?;
+            let upgrade_consensus_state = decode_any(&msg.upgrade_consensus_state)?;
+            client_state.verify_upgrade_client(
+                upgrade_client_state.clone(),
+                upgrade_consensus_state.clone(),
+                CommitmentProofBytes::try_from(msg.proof_upgrade_client.to_vec())?,
+                CommitmentProofBytes::try_from(msg.proof_upgrade_consensus_state.to_vec())?,
+                &client_state.inner().consensus_state.root,
+            )?;
+            client_state.update_state_on_upgrade(
+                &mut ctx,
+                &client_id,
+                upgrade_client_state,
+                upgrade_consensus_state,
+            )?;
+            to_json_binary(&EmptyResult {})?
+        }
+        // solo machines sign proofs as they go, so the delay periods do not apply
+        SudoMsg::VerifyMembership(msg) => {
+            client_state.verify_signed_path(
+                &ctx,
+                msg.height.try_into()?,
+                &CommitmentProofBytes::try_from(msg.proof.to_vec())?,
+                &msg.path.into(),
+                msg.value.to_vec(),
+            )?;
+            consume_sequence(&mut ctx, client_state, &msg.proof)?;
+            to_json_binary(&EmptyResult {})?
+        }
+        SudoMsg::VerifyNonMembership(msg) => {
+            client_state.verify_signed_path(
+                &ctx,
+                msg.height.try_into()?,
+                &CommitmentProofBytes::try_from(msg.proof.to_vec())?,
+                &msg.path.into(),
+                vec![],
+            )?;
+            consume_sequence(&mut ctx, client_state, &msg.proof)?;
+            to_json_binary(&EmptyResult {})?
+        }
+    };
+
+    Ok(Response::default().set_data(result))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps<'_>, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    let ctx = Context::new_ref(deps.storage, env);
+    let client_state = ctx.client_state()?;
+    let client_id = client_id();
+
+    let result = match msg {
+        QueryMsg::Status(_) => to_json_binary(&StatusResult {
+            status: client_state.status(&ctx, &client_id)?.to_string(),
+        })?,
+        QueryMsg::TimestampAtHeight(msg) => {
+            let consensus_state =
+                ctx.consensus_state(&consensus_state_path(msg.height.try_into()?))?;
+            to_json_binary(&TimestampAtHeightResult {
+                timestamp: consensus_state.timestamp().nanoseconds(),
+            })?
+        }
+        QueryMsg::VerifyClientMessage(msg) => {
+            let client_message = decode_any(&msg.client_message)?;
+            let update_kind = update_kind(&client_message)?;
+            client_state.verify_client_message(&ctx, &client_id, client_message, &update_kind)?;
+            to_json_binary(&EmptyResult {})?
+        }
+        QueryMsg::CheckForMisbehaviour(msg) => {
+            let client_message = decode_any(&msg.client_message)?;
+            let update_kind = update_kind(&client_message)?;
+            let found_misbehaviour = client_state.check_for_misbehaviour(
+                &ctx,
+                &client_id,
+                client_message,
+                &update_kind,
+            )?;
+            to_json_binary(&CheckForMisbehaviourResult { found_misbehaviour })?
+        }
+    };
+
+    Ok(result)
+}
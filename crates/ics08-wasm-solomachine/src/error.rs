@@ -0,0 +1,62 @@
+use cosmwasm_std::StdError;
+use displaydoc::Display;
+use ibc_client_solomachine_types::error::Error as SmError;
+use ibc_core::client::types::error::ClientError;
+use ibc_core::commitment_types::error::CommitmentError;
+use ibc_core::handler::types::error::ContextError;
+
+#[derive(Debug, Display)]
+pub enum ContractError {
+    /// cosmwasm error: `{0}`
+    Std(StdError),
+    /// decode error: `{0}`
+    Decode(prost::DecodeError),
+    /// solomachine error: `{0}`
+    Solomachine(SmError),
+    /// client error: `{0}`
+    Client(ClientError),
+    /// context error: `{0}`
+    Context(ContextError),
+    /// commitment error: `{0}`
+    Commitment(CommitmentError),
+    /// unknown client message type url: `{type_url}`
+    UnknownClientMessage { type_url: String },
+}
+
+impl std::error::Error for ContractError {}
+
+impl From<StdError> for ContractError {
+    fn from(e: StdError) -> Self {
+        Self::Std(e)
+    }
+}
+
+impl From<prost::DecodeError> for ContractError {
+    fn from(e: prost::DecodeError) -> Self {
+        Self::Decode(e)
+    }
+}
+
+impl From<SmError> for ContractError {
+    fn from(e: SmError) -> Self {
+        Self::Solomachine(e)
+    }
+}
+
+impl From<ClientError> for ContractError {
+    fn from(e: ClientError) -> Self {
+        Self::Client(e)
+    }
+}
+
+impl From<ContextError> for ContractError {
+    fn from(e: ContextError) -> Self {
+        Self::Context(e)
+    }
+}
+
+impl From<CommitmentError> for ContractError {
+    fn from(e: CommitmentError) -> Self {
+        Self::Commitment(e)
+    }
+}
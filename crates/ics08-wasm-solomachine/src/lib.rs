@@ -0,0 +1,26 @@
+//! ICS 08: CosmWasm contract running the solo machine client as an ibc-go
+//! `08-wasm` light client.
+//!
+//! The host keeps the client in a store dedicated to the contract and drives it
+//! through the `instantiate`, `sudo` and `query` entry points. Client states,
+//! consensus states and client messages are passed as protobuf-encoded `Any`s
+//! of the solo machine v3 types.
+#![forbid(unsafe_code)]
+#![cfg_attr(not(test), deny(clippy::unwrap_used))]
+#![deny(
+    warnings,
+    trivial_casts,
+    trivial_numeric_casts,
+    unused_import_braces,
+    unused_qualifications,
+    rust_2018_idioms
+)]
+
+pub mod context;
+pub mod contract;
+pub mod error;
+pub mod msg;
+pub mod raw;
+
+pub use context::Context;
+pub use error::ContractError;
@@ -0,0 +1,172 @@
+//! The messages ibc-go's `08-wasm` module exchanges with light client
+//! contracts, as defined in `modules/light-clients/08-wasm/types/contract_api.go`.
+//!
+//! Byte fields are base64 encoded, as Go encodes `[]byte` in JSON.
+
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Binary;
+use ibc_core::client::types::error::ClientError;
+use ibc_core::client::types::Height as IbcHeight;
+use ibc_proto::ibc::core::commitment::v1::MerklePath as RawMerklePath;
+
+/// Height as `08-wasm` encodes `clienttypes.Height`, whose zero fields are
+/// omitted.
+#[cw_serde]
+#[derive(Copy, Default)]
+pub struct Height {
+    #[serde(default)]
+    pub revision_number: u64,
+    #[serde(default)]
+    pub revision_height: u64,
+}
+
+impl From<IbcHeight> for Height {
+    fn from(height: IbcHeight) -> Self {
+        Self {
+            revision_number: height.revision_number(),
+            revision_height: height.revision_height(),
+        }
+    }
+}
+
+impl TryFrom<Height> for IbcHeight {
+    type Error = ClientError;
+
+    fn try_from(height: Height) -> Result<Self, Self::Error> {
+        IbcHeight::new(height.revision_number, height.revision_height)
+    }
+}
+
+/// MerklePath is the path used to verify commitment proofs, with the
+/// commitment prefix as its first key.
+#[cw_serde]
+pub struct MerklePath {
+    pub key_path: Vec<String>,
+}
+
+impl From<MerklePath> for RawMerklePath {
+    fn from(path: MerklePath) -> Self {
+        Self {
+            key_path: path.key_path,
+        }
+    }
+}
+
+/// InstantiateMsg carries the initial client and consensus states of the
+/// client, and the checksum of the contract code.
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub client_state: Binary,
+    pub consensus_state: Binary,
+    pub checksum: Binary,
+}
+
+/// SudoMsg is sent by the host to mutate the client or to verify proofs
+/// against it.
+#[cw_serde]
+pub enum SudoMsg {
+    UpdateState(UpdateStateMsg),
+    UpdateStateOnMisbehaviour(UpdateStateOnMisbehaviourMsg),
+    VerifyUpgradeAndUpdateState(VerifyUpgradeAndUpdateStateMsg),
+    VerifyMembership(VerifyMembershipMsg),
+    VerifyNonMembership(VerifyNonMembershipMsg),
+}
+
+#[cw_serde]
+pub struct UpdateStateMsg {
+    pub client_message: Binary,
+}
+
+#[cw_serde]
+pub struct UpdateStateOnMisbehaviourMsg {
+    pub client_message: Binary,
+}
+
+#[cw_serde]
+pub struct VerifyUpgradeAndUpdateStateMsg {
+    pub upgrade_client_state: Binary,
+    pub upgrade_consensus_state: Binary,
+    pub proof_upgrade_client: Binary,
+    pub proof_upgrade_consensus_state: Binary,
+}
+
+#[cw_serde]
+pub struct VerifyMembershipMsg {
+    pub height: Height,
+    pub delay_time_period: u64,
+    pub delay_block_period: u64,
+    pub proof: Binary,
+    pub path: MerklePath,
+    pub value: Binary,
+}
+
+#[cw_serde]
+pub struct VerifyNonMembershipMsg {
+    pub height: Height,
+    pub delay_time_period: u64,
+    pub delay_block_period: u64,
+    pub proof: Binary,
+    pub path: MerklePath,
+}
+
+/// QueryMsg is sent by the host to read the client.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(StatusResult)]
+    Status(StatusMsg),
+    #[returns(TimestampAtHeightResult)]
+    TimestampAtHeight(TimestampAtHeightMsg),
+    #[returns(EmptyResult)]
+    VerifyClientMessage(VerifyClientMessageMsg),
+    #[returns(CheckForMisbehaviourResult)]
+    CheckForMisbehaviour(CheckForMisbehaviourMsg),
+}
+
+#[cw_serde]
+pub struct StatusMsg {}
+
+#[cw_serde]
+pub struct TimestampAtHeightMsg {
+    pub height: Height,
+}
+
+#[cw_serde]
+pub struct VerifyClientMessageMsg {
+    pub client_message: Binary,
+}
+
+#[cw_serde]
+pub struct CheckForMisbehaviourMsg {
+    pub client_message: Binary,
+}
+
+/// EmptyResult is returned by the calls that have no result.
+#[cw_serde]
+pub struct EmptyResult {}
+
+/// UpdateStateResult lists the heights the client stored consensus states at.
+#[cw_serde]
+pub struct UpdateStateResult {
+    pub heights: Vec<Height>,
+}
+
+/// StatusResult is the status of the client: `Active`, `Frozen` or `Expired`.
+#[cw_serde]
+pub struct StatusResult {
+    pub status: String,
+}
+
+/// TimestampAtHeightResult is the timestamp of the consensus state at the
+/// queried height, in nanoseconds since the Unix epoch.
+#[cw_serde]
+pub struct TimestampAtHeightResult {
+    pub timestamp: u64,
+}
+
+/// CheckForMisbehaviourResult reports whether the client message proves
+/// misbehaviour.
+#[cw_serde]
+pub struct CheckForMisbehaviourResult {
+    pub found_misbehaviour: bool,
+}
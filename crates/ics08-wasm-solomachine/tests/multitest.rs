@@ -0,0 +1,406 @@
+//! Drives the contract the way `08-wasm` does: instantiates it with a client
+//! and consensus state, updates it with a header, verifies proofs against it
+//! and freezes it with misbehaviour, through the cw-multi-test app.
+
+use cosmwasm_std::{from_json, Addr, Binary, DepsMut, Empty, Env, MessageInfo, Response, StdError};
+use cw_multi_test::{App, ContractWrapper, Executor};
+use ibc_client_solomachine::types::cosmos::crypto::SigningKey;
+use ibc_client_solomachine::types::header::SENTINEL_HEADER_PATH;
+use ibc_client_solomachine::types::proof::sign_bytes_path;
+use ibc_client_solomachine::types::proof::types::header_data::HeaderData;
+use ibc_client_solomachine::types::proof::types::sign_bytes::SignBytes;
+use ibc_client_solomachine::types::proof::types::signature_and_data::SignatureAndData;
+use ibc_client_solomachine::types::proof::types::timestamped_signature_data::TimestampedSignatureData;
+use ibc_client_solomachine::types::{
+    ClientState as ClientStateType, ConsensusState as ConsensusStateType, Header, Misbehaviour,
+};
+use ibc_client_solomachine_cw::context::CLIENT_STATE_KEY;
+use ibc_client_solomachine_cw::contract::{instantiate, query, sudo};
+use ibc_client_solomachine_cw::msg::{
+    CheckForMisbehaviourMsg, CheckForMisbehaviourResult, EmptyResult, Height, InstantiateMsg,
+    MerklePath, QueryMsg, StatusMsg, StatusResult, SudoMsg, TimestampAtHeightMsg,
+    TimestampAtHeightResult, UpdateStateMsg, UpdateStateOnMisbehaviourMsg, UpdateStateResult,
+    VerifyClientMessageMsg, VerifyMembershipMsg, VerifyNonMembershipMsg,
+    VerifyUpgradeAndUpdateStateMsg,
+};
+use ibc_client_solomachine_cw::raw::ClientState as RawWasmClientState;
+use ibc_core::client::types::Height as IbcHeight;
+use ibc_core::primitives::Timestamp;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::core::commitment::v1::MerklePath as RawMerklePath;
+use ibc_proto::Protobuf;
+use prost::Message;
+
+const CONNECTION_PATH: &str = "connections/connection-0";
+
+fn execute(_: DepsMut<'_>, _: Env, _: MessageInfo, _: Empty) -> Result<Response, StdError> {
+    Err(StdError::generic_err(
+        "08-wasm clients have no execute entry point",
+    ))
+}
+
+fn timestamp(nanoseconds: u64) -> Timestamp {
+    Timestamp::from_nanoseconds(nanoseconds).unwrap()
+}
+
+fn height(revision_height: u64) -> Height {
+    IbcHeight::new(0, revision_height).unwrap().into()
+}
+
+fn any_bytes(any: impl Into<Any>) -> Binary {
+    any.into().encode_to_vec().into()
+}
+
+fn merkle_path() -> MerklePath {
+    MerklePath {
+        key_path: vec!["ibc".into(), CONNECTION_PATH.into()],
+    }
+}
+
+fn sign(key: &SigningKey, sequence: u64, time: u64, diversifier: &str, data: &[u8]) -> Vec<u8> {
+    let path = sign_bytes_path(&RawMerklePath::from(merkle_path()));
+    key.sign_signature_data(SignBytes {
+        sequence,
+        timestamp: time,
+        diversifier: diversifier.into(),
+        path,
+        data: data.to_vec(),
+    })
+}
+
+fn proof(key: &SigningKey, sequence: u64, time: u64, diversifier: &str, data: &[u8]) -> Binary {
+    TimestampedSignatureData {
+        signature_data: sign(key, sequence, time, diversifier, data),
+        timestamp: timestamp(time),
+    }
+    .encode_vec()
+    .into()
+}
+
+fn query_client<T: serde::de::DeserializeOwned>(
+    app: &App,
+    contract: &Addr,
+    msg: &QueryMsg,
+) -> Result<T, StdError> {
+    app.wrap().query_wasm_smart(contract, msg)
+}
+
+// Stored_client_state reads the solo machine client state out of the wasm
+// client state the contract stores.
+fn stored_client_state(app: &App, contract: &Addr) -> ClientStateType {
+    let bytes = app
+        .wrap()
+        .query_wasm_raw(contract, CLIENT_STATE_KEY)
+        .unwrap()
+        .unwrap();
+    let wasm_client_state =
+        RawWasmClientState::decode(Any::decode(bytes.as_slice()).unwrap().value.as_slice())
+            .unwrap();
+    ClientStateType::try_from(Any::decode(wasm_client_state.data.as_slice()).unwrap()).unwrap()
+}
+
+#[test]
+fn client_lifecycle_through_08_wasm_messages() {
+    let key_one = SigningKey::from_ed25519_bytes(&[1u8; 32]).unwrap();
+    let key_two = SigningKey::from_secp256k1_bytes(&[2u8; 32]).unwrap();
+
+    let mut app = App::default();
+    let code_id = app.store_code(Box::new(
+        ContractWrapper::new(execute, instantiate, query).with_sudo(sudo),
+    ));
+
+    let consensus_state =
+        ConsensusStateType::new(key_one.public_key().unwrap(), "one".into(), timestamp(10));
+    let client_state = ClientStateType::new(
+        IbcHeight::new(0, 1).unwrap(),
+        false,
+        consensus_state.clone(),
+    );
+    let contract = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("08-wasm"),
+            &InstantiateMsg {
+                client_state: any_bytes(client_state),
+                consensus_state: any_bytes(consensus_state),
+                checksum: vec![0xab; 32].into(),
+            },
+            &[],
+            "solomachine",
+            None,
+        )
+        .unwrap();
+
+    let status: StatusResult =
+        query_client(&app, &contract, &QueryMsg::Status(StatusMsg {})).unwrap();
+    assert_eq!(status.status, "Active");
+    let at_height: TimestampAtHeightResult = query_client(
+        &app,
+        &contract,
+        &QueryMsg::TimestampAtHeight(TimestampAtHeightMsg { height: height(1) }),
+    )
+    .unwrap();
+    assert_eq!(at_height.timestamp, 10);
+
+    // rotate to the second key with a header signed by the first one
+    let header = Header {
+        timestamp: timestamp(20),
+        signature: key_one.sign_signature_data(SignBytes {
+            sequence: 1,
+            timestamp: 20,
+            diversifier: "one".into(),
+            path: SENTINEL_HEADER_PATH.as_bytes().to_vec(),
+            data: HeaderData {
+                new_pub_key: key_two.public_key().unwrap(),
+                new_diversifier: "two".into(),
+            }
+            .encode_vec(),
+        }),
+        new_public_key: key_two.public_key().unwrap(),
+        new_diversifier: "two".into(),
+    };
+    let _: EmptyResult = query_client(
+        &app,
+        &contract,
+        &QueryMsg::VerifyClientMessage(VerifyClientMessageMsg {
+            client_message: any_bytes(header.clone()),
+        }),
+    )
+    .unwrap();
+    let check: CheckForMisbehaviourResult = query_client(
+        &app,
+        &contract,
+        &QueryMsg::CheckForMisbehaviour(CheckForMisbehaviourMsg {
+            client_message: any_bytes(header.clone()),
+        }),
+    )
+    .unwrap();
+    assert!(!check.found_misbehaviour);
+
+    let response = app
+        .wasm_sudo(
+            contract.clone(),
+            &SudoMsg::UpdateState(UpdateStateMsg {
+                client_message: any_bytes(header),
+            }),
+        )
+        .unwrap();
+    let updated: UpdateStateResult = from_json(response.data.unwrap()).unwrap();
+    assert_eq!(updated.heights, vec![height(2)]);
+    let at_height: TimestampAtHeightResult = query_client(
+        &app,
+        &contract,
+        &QueryMsg::TimestampAtHeight(TimestampAtHeightMsg { height: height(2) }),
+    )
+    .unwrap();
+    assert_eq!(at_height.timestamp, 20);
+
+    // proofs are checked against the new key at the new sequence
+    let verify_membership = |sequence: u64, proof: Binary, value: &[u8]| {
+        SudoMsg::VerifyMembership(VerifyMembershipMsg {
+            height: height(sequence),
+            delay_time_period: 0,
+            delay_block_period: 0,
+            proof,
+            path: merkle_path(),
+            value: value.to_vec().into(),
+        })
+    };
+    assert!(app
+        .wasm_sudo(
+            contract.clone(),
+            &verify_membership(2, proof(&key_two, 2, 30, "two", b"connection"), b"other"),
+        )
+        .is_err());
+    assert!(app
+        .wasm_sudo(
+            contract.clone(),
+            &verify_membership(
+                2,
+                proof(&key_one, 2, 30, "one", b"connection"),
+                b"connection"
+            ),
+        )
+        .is_err());
+    app.wasm_sudo(
+        contract.clone(),
+        &verify_membership(
+            2,
+            proof(&key_two, 2, 30, "two", b"connection"),
+            b"connection",
+        ),
+    )
+    .unwrap();
+    let stored = stored_client_state(&app, &contract);
+    assert_eq!(stored.sequence, IbcHeight::new(0, 3).unwrap());
+    assert_eq!(stored.consensus_state.timestamp, timestamp(30));
+
+    // each proof consumes its sequence, the next one must be signed at the
+    // following sequence
+    assert!(app
+        .wasm_sudo(
+            contract.clone(),
+            &verify_membership(
+                2,
+                proof(&key_two, 2, 30, "two", b"connection"),
+                b"connection"
+            ),
+        )
+        .is_err());
+    assert!(app
+        .wasm_sudo(
+            contract.clone(),
+            &verify_membership(
+                3,
+                proof(&key_two, 2, 30, "two", b"connection"),
+                b"connection"
+            ),
+        )
+        .is_err());
+    app.wasm_sudo(
+        contract.clone(),
+        &SudoMsg::VerifyNonMembership(VerifyNonMembershipMsg {
+            height: height(3),
+            delay_time_period: 0,
+            delay_block_period: 0,
+            proof: proof(&key_two, 3, 40, "two", &[]),
+            path: merkle_path(),
+        }),
+    )
+    .unwrap();
+    let stored = stored_client_state(&app, &contract);
+    assert_eq!(stored.sequence, IbcHeight::new(0, 4).unwrap());
+    assert_eq!(stored.consensus_state.timestamp, timestamp(40));
+
+    // proofs may not be older than the latest proof
+    assert!(app
+        .wasm_sudo(
+            contract.clone(),
+            &verify_membership(
+                4,
+                proof(&key_two, 4, 30, "two", b"connection"),
+                b"connection"
+            ),
+        )
+        .is_err());
+    app.wasm_sudo(
+        contract.clone(),
+        &verify_membership(
+            4,
+            proof(&key_two, 4, 40, "two", b"connection"),
+            b"connection",
+        ),
+    )
+    .unwrap();
+
+    // solo machine clients cannot be upgraded
+    assert!(app
+        .wasm_sudo(
+            contract.clone(),
+            &SudoMsg::VerifyUpgradeAndUpdateState(VerifyUpgradeAndUpdateStateMsg {
+                upgrade_client_state: any_bytes(ClientStateType::new(
+                    IbcHeight::new(0, 3).unwrap(),
+                    false,
+                    ConsensusStateType::new(
+                        key_two.public_key().unwrap(),
+                        "two".into(),
+                        timestamp(30),
+                    ),
+                )),
+                upgrade_consensus_state: any_bytes(ConsensusStateType::new(
+                    key_two.public_key().unwrap(),
+                    "two".into(),
+                    timestamp(30),
+                )),
+                proof_upgrade_client: vec![1].into(),
+                proof_upgrade_consensus_state: vec![1].into(),
+            }),
+        )
+        .is_err());
+
+    // two different messages signed at the same sequence freeze the client
+    let signature = |data: &[u8]| SignatureAndData {
+        signature: sign(&key_two, 2, 30, "two", data),
+        path: sign_bytes_path(&RawMerklePath::from(merkle_path())),
+        data: data.to_vec(),
+        timestamp: timestamp(30),
+    };
+    let misbehaviour = Any::from(Misbehaviour {
+        sequence: IbcHeight::new(0, 2).unwrap(),
+        signature_one: signature(b"one"),
+        signature_two: signature(b"two"),
+    });
+    let _: EmptyResult = query_client(
+        &app,
+        &contract,
+        &QueryMsg::VerifyClientMessage(VerifyClientMessageMsg {
+            client_message: any_bytes(misbehaviour.clone()),
+        }),
+    )
+    .unwrap();
+    let check: CheckForMisbehaviourResult = query_client(
+        &app,
+        &contract,
+        &QueryMsg::CheckForMisbehaviour(CheckForMisbehaviourMsg {
+            client_message: any_bytes(misbehaviour.clone()),
+        }),
+    )
+    .unwrap();
+    assert!(check.found_misbehaviour);
+    app.wasm_sudo(
+        contract.clone(),
+        &SudoMsg::UpdateStateOnMisbehaviour(UpdateStateOnMisbehaviourMsg {
+            client_message: any_bytes(misbehaviour),
+        }),
+    )
+    .unwrap();
+
+    let status: StatusResult =
+        query_client(&app, &contract, &QueryMsg::Status(StatusMsg {})).unwrap();
+    assert_eq!(status.status, "Frozen");
+}
+
+#[test]
+fn unknown_client_messages_are_rejected() {
+    let key = SigningKey::from_ed25519_bytes(&[1u8; 32]).unwrap();
+
+    let mut app = App::default();
+    let code_id = app.store_code(Box::new(
+        ContractWrapper::new(execute, instantiate, query).with_sudo(sudo),
+    ));
+    let consensus_state =
+        ConsensusStateType::new(key.public_key().unwrap(), "one".into(), timestamp(10));
+    let contract = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked("08-wasm"),
+            &InstantiateMsg {
+                client_state: any_bytes(ClientStateType::new(
+                    IbcHeight::new(0, 1).unwrap(),
+                    false,
+                    consensus_state.clone(),
+                )),
+                consensus_state: any_bytes(consensus_state),
+                checksum: vec![0xab; 32].into(),
+            },
+            &[],
+            "solomachine",
+            None,
+        )
+        .unwrap();
+
+    let error = query_client::<EmptyResult>(
+        &app,
+        &contract,
+        &QueryMsg::VerifyClientMessage(VerifyClientMessageMsg {
+            client_message: any_bytes(Any {
+                type_url: "/ibc.lightclients.tendermint.v1.Header".into(),
+                value: vec![],
+            }),
+        }),
+    )
+    .unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("unknown client message type url"));
+}
@@ -0,0 +1,283 @@
+//! Host context running solo machine clients on the pallet storage.
+
+use crate::{ClientStates, Config, ConsensusStates, UpdateHeights, UpdateTimes};
+use core::marker::PhantomData;
+use frame_support::traits::{Get, UnixTime};
+use ibc_client_solomachine::client_state::ClientState;
+use ibc_client_solomachine::consensus_state::ConsensusState as SmConsensusState;
+use ibc_client_solomachine::context::{CommonContext, ValidationContext};
+use ibc_client_solomachine::types::{TimePolicy, VerificationLimits};
+use ibc_core::client::context::consensus_state::ConsensusState;
+use ibc_core::client::context::{ClientExecutionContext, ClientValidationContext};
+use ibc_core::client::types::error::ClientError;
+use ibc_core::client::types::Height;
+use ibc_core::commitment_types::commitment::CommitmentRoot;
+use ibc_core::handler::types::error::ContextError;
+use ibc_core::host::types::identifiers::ClientId;
+use ibc_core::host::types::path::{ClientConsensusStatePath, ClientStatePath};
+use ibc_core::primitives::prelude::*;
+use ibc_core::primitives::Timestamp;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::Protobuf;
+use sp_runtime::traits::UniqueSaturatedInto;
+
+/// The consensus states the pallet stores.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AnyConsensusState {
+    Solomachine(SmConsensusState),
+}
+
+impl From<SmConsensusState> for AnyConsensusState {
+    fn from(consensus_state: SmConsensusState) -> Self {
+        Self::Solomachine(consensus_state)
+    }
+}
+
+impl TryFrom<AnyConsensusState> for SmConsensusState {
+    type Error = ClientError;
+
+    fn try_from(consensus_state: AnyConsensusState) -> Result<Self, Self::Error> {
+        match consensus_state {
+            AnyConsensusState::Solomachine(consensus_state) => Ok(consensus_state),
+        }
+    }
+}
+
+impl From<AnyConsensusState> for Any {
+    fn from(consensus_state: AnyConsensusState) -> Self {
+        match consensus_state {
+            AnyConsensusState::Solomachine(consensus_state) => consensus_state.into(),
+        }
+    }
+}
+
+impl ConsensusState for AnyConsensusState {
+    fn root(&self) -> &CommitmentRoot {
+        match self {
+            Self::Solomachine(consensus_state) => consensus_state.root(),
+        }
+    }
+
+    fn timestamp(&self) -> Timestamp {
+        match self {
+            Self::Solomachine(consensus_state) => consensus_state.timestamp(),
+        }
+    }
+
+    fn encode_vec(self) -> Vec<u8> {
+        match self {
+            Self::Solomachine(consensus_state) => {
+                <SmConsensusState as Protobuf<Any>>::encode_vec(consensus_state)
+            }
+        }
+    }
+}
+
+/// Host context of the pallet. Clients are read from and written to the
+/// pallet storage maps, and the host time and height are those of the block
+/// being built.
+pub struct Context<T>(PhantomData<T>);
+
+impl<T> Default for Context<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: Config> Context<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the client state stored for `client_id`.
+    pub fn client_state(&self, client_id: &ClientId) -> Result<ClientState, ClientError> {
+        ClientStates::<T>::get(client_id).ok_or_else(|| ClientError::ClientStateNotFound {
+            client_id: client_id.clone(),
+        })
+    }
+}
+
+impl<T: Config> CommonContext for Context<T> {
+    type ConversionError = ClientError;
+    type AnyConsensusState = AnyConsensusState;
+
+    fn host_timestamp(&self) -> Result<Timestamp, ContextError> {
+        let nanoseconds: u64 = T::TimeProvider::now().as_nanos().unique_saturated_into();
+        Timestamp::from_nanoseconds(nanoseconds).map_err(|e| {
+            ClientError::Other {
+                description: e.to_string(),
+            }
+            .into()
+        })
+    }
+
+    /// Returns the height of the block being built.
+    fn host_height(&self) -> Result<Height, ContextError> {
+        let block_number: u64 = frame_system::Pallet::<T>::block_number().unique_saturated_into();
+        Ok(Height::new(0, block_number)?)
+    }
+
+    fn consensus_state(
+        &self,
+        client_cons_state_path: &ClientConsensusStatePath,
+    ) -> Result<Self::AnyConsensusState, ContextError> {
+        let height = Height::new(
+            client_cons_state_path.revision_number,
+            client_cons_state_path.revision_height,
+        )?;
+        ConsensusStates::<T>::get(&client_cons_state_path.client_id, height)
+            .map(Into::into)
+            .ok_or_else(|| {
+                ClientError::ConsensusStateNotFound {
+                    client_id: client_cons_state_path.client_id.clone(),
+                    height,
+                }
+                .into()
+            })
+    }
+}
+
+impl<T: Config> ValidationContext for Context<T> {
+    fn verification_limits(&self) -> VerificationLimits {
+        T::VerificationLimits::get()
+    }
+
+    fn time_policy(&self) -> TimePolicy {
+        T::TimePolicy::get()
+    }
+
+    // Consensus states are keyed by hashed heights, so the neighbours of a
+    // height are found by scanning the consensus states of the client.
+    fn next_consensus_state(
+        &self,
+        client_id: &ClientId,
+        height: &Height,
+    ) -> Result<Option<Self::AnyConsensusState>, ContextError> {
+        Ok(ConsensusStates::<T>::iter_prefix(client_id)
+            .filter(|(consensus_height, _)| consensus_height > height)
+            .min_by_key(|(consensus_height, _)| *consensus_height)
+            .map(|(_, consensus_state)| consensus_state.into()))
+    }
+
+    fn prev_consensus_state(
+        &self,
+        client_id: &ClientId,
+        height: &Height,
+    ) -> Result<Option<Self::AnyConsensusState>, ContextError> {
+        Ok(ConsensusStates::<T>::iter_prefix(client_id)
+            .filter(|(consensus_height, _)| consensus_height < height)
+            .max_by_key(|(consensus_height, _)| *consensus_height)
+            .map(|(_, consensus_state)| consensus_state.into()))
+    }
+}
+
+impl<T: Config> ClientValidationContext for Context<T> {
+    fn client_update_time(
+        &self,
+        client_id: &ClientId,
+        height: &Height,
+    ) -> Result<Timestamp, ContextError> {
+        let nanoseconds =
+            UpdateTimes::<T>::get(client_id, height).ok_or_else(|| ClientError::Other {
+                description: format!("no update time for client {} at {}", client_id, height),
+            })?;
+        Timestamp::from_nanoseconds(nanoseconds).map_err(|e| {
+            ClientError::Other {
+                description: e.to_string(),
+            }
+            .into()
+        })
+    }
+
+    fn client_update_height(
+        &self,
+        client_id: &ClientId,
+        height: &Height,
+    ) -> Result<Height, ContextError> {
+        UpdateHeights::<T>::get(client_id, height).ok_or_else(|| {
+            ClientError::Other {
+                description: format!("no update height for client {} at {}", client_id, height),
+            }
+            .into()
+        })
+    }
+}
+
+impl<T: Config> ClientExecutionContext for Context<T> {
+    type V = Self;
+    type AnyClientState = ClientState;
+    type AnyConsensusState = AnyConsensusState;
+
+    fn store_client_state(
+        &mut self,
+        client_state_path: ClientStatePath,
+        client_state: Self::AnyClientState,
+    ) -> Result<(), ContextError> {
+        ClientStates::<T>::insert(client_state_path.0, client_state);
+        Ok(())
+    }
+
+    fn store_consensus_state(
+        &mut self,
+        consensus_state_path: ClientConsensusStatePath,
+        consensus_state: Self::AnyConsensusState,
+    ) -> Result<(), ContextError> {
+        let height = Height::new(
+            consensus_state_path.revision_number,
+            consensus_state_path.revision_height,
+        )?;
+        let AnyConsensusState::Solomachine(consensus_state) = consensus_state;
+        ConsensusStates::<T>::insert(consensus_state_path.client_id, height, consensus_state);
+        Ok(())
+    }
+
+    fn delete_consensus_state(
+        &mut self,
+        consensus_state_path: ClientConsensusStatePath,
+    ) -> Result<(), ContextError> {
+        let height = Height::new(
+            consensus_state_path.revision_number,
+            consensus_state_path.revision_height,
+        )?;
+        ConsensusStates::<T>::remove(consensus_state_path.client_id, height);
+        Ok(())
+    }
+
+    fn store_update_time(
+        &mut self,
+        client_id: ClientId,
+        height: Height,
+        host_timestamp: Timestamp,
+    ) -> Result<(), ContextError> {
+        UpdateTimes::<T>::insert(client_id, height, host_timestamp.nanoseconds());
+        Ok(())
+    }
+
+    fn store_update_height(
+        &mut self,
+        client_id: ClientId,
+        height: Height,
+        host_height: Height,
+    ) -> Result<(), ContextError> {
+        UpdateHeights::<T>::insert(client_id, height, host_height);
+        Ok(())
+    }
+
+    fn delete_update_time(
+        &mut self,
+        client_id: ClientId,
+        height: Height,
+    ) -> Result<(), ContextError> {
+        UpdateTimes::<T>::remove(client_id, height);
+        Ok(())
+    }
+
+    fn delete_update_height(
+        &mut self,
+        client_id: ClientId,
+        height: Height,
+    ) -> Result<(), ContextError> {
+        UpdateHeights::<T>::remove(client_id, height);
+        Ok(())
+    }
+}
@@ -0,0 +1,474 @@
+//! FRAME pallet running ICS 06 solo machine clients on pallet storage, for
+//! appchains that want solo machine clients without writing the glue.
+//!
+//! [`Context`] implements the solo machine client context traits and ibc-core's
+//! client contexts on top of the pallet storage maps. The extrinsics take
+//! protobuf-encoded `Any`s, as carried by `MsgCreateClient`, `MsgUpdateClient`
+//! and `MsgSubmitMisbehaviour`, and drive the client handlers the way the
+//! ibc-core handlers do.
+#![cfg_attr(not(feature = "std"), no_std)]
+#![forbid(unsafe_code)]
+#![cfg_attr(not(test), deny(clippy::unwrap_used))]
+#![deny(
+    warnings,
+    trivial_casts,
+    trivial_numeric_casts,
+    unused_import_braces,
+    unused_qualifications,
+    rust_2018_idioms
+)]
+
+extern crate alloc;
+
+pub mod context;
+#[cfg(test)]
+mod mock;
+pub mod weights;
+
+pub use context::Context;
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+/// Target of the pallet's log messages.
+pub const LOG_TARGET: &str = "runtime::ics06-solomachine";
+
+// the `pallet::storage` expansion qualifies paths already in scope
+#[allow(unused_qualifications)]
+#[frame_support::pallet]
+pub mod pallet {
+    use crate::context::Context;
+    use crate::weights::WeightInfo;
+    use alloc::vec::Vec;
+    use frame_support::pallet_prelude::*;
+    use frame_support::traits::UnixTime;
+    use frame_system::pallet_prelude::*;
+    use ibc_client_solomachine::client_state::ClientState;
+    use ibc_client_solomachine::consensus_state::ConsensusState as SmConsensusState;
+    use ibc_client_solomachine::types::{client_type, TimePolicy, VerificationLimits};
+    use ibc_core::client::context::client_state::{
+        ClientStateCommon, ClientStateExecution, ClientStateValidation,
+    };
+    use ibc_core::client::types::{Height, Status, UpdateKind};
+    use ibc_core::host::types::identifiers::ClientId;
+    use ibc_proto::google::protobuf::Any;
+    use prost::Message;
+    use sp_runtime::SaturatedConversion;
+
+    #[pallet::pallet]
+    #[pallet::without_storage_info]
+    pub struct Pallet<T>(_);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// The overarching event type.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        /// Source of the host timestamp clients check expiry and proofs
+        /// against.
+        type TimeProvider: UnixTime;
+        /// Size limits headers and misbehaviours are checked against before
+        /// they are decoded and verified.
+        type VerificationLimits: Get<VerificationLimits>;
+        /// Bounds header timestamps must keep from the host timestamp.
+        type TimePolicy: Get<TimePolicy>;
+        /// Weight information for extrinsics in this pallet.
+        type WeightInfo: WeightInfo;
+    }
+
+    /// Number of clients created so far, the counter of the next client id.
+    #[pallet::storage]
+    pub type ClientCounter<T> = StorageValue<_, u64, ValueQuery>;
+
+    /// Client state of each client.
+    #[pallet::storage]
+    pub type ClientStates<T> = StorageMap<_, Blake2_128Concat, ClientId, ClientState>;
+
+    /// Consensus states of each client, by height.
+    #[pallet::storage]
+    pub type ConsensusStates<T> =
+        StorageDoubleMap<_, Blake2_128Concat, ClientId, Blake2_128Concat, Height, SmConsensusState>;
+
+    /// Host timestamp, in nanoseconds, each consensus state was stored at.
+    #[pallet::storage]
+    pub type UpdateTimes<T> =
+        StorageDoubleMap<_, Blake2_128Concat, ClientId, Blake2_128Concat, Height, u64>;
+
+    /// Host height each consensus state was stored at.
+    #[pallet::storage]
+    pub type UpdateHeights<T> =
+        StorageDoubleMap<_, Blake2_128Concat, ClientId, Blake2_128Concat, Height, Height>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// A client was created with its consensus state at `consensus_height`.
+        ClientCreated {
+            client_id: ClientId,
+            consensus_height: Height,
+        },
+        /// A client was updated with consensus states at `consensus_heights`.
+        ClientUpdated {
+            client_id: ClientId,
+            consensus_heights: Vec<Height>,
+        },
+        /// A client was frozen for misbehaviour.
+        ClientMisbehaviour { client_id: ClientId },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// The message is not a protobuf-encoded `Any`.
+        InvalidAny,
+        /// The client state or consensus state is invalid.
+        InvalidClientState,
+        /// No client exists with the given id.
+        ClientNotFound,
+        /// The client is frozen or expired.
+        ClientNotActive,
+        /// The header or misbehaviour failed verification.
+        InvalidClientMessage,
+        /// The client could not be stored or updated.
+        ClientExecutionFailed,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Creates a solo machine client from a protobuf-encoded client state
+        /// `Any` and its initial consensus state `Any`.
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::create_client(
+            client_state.len().saturating_add(consensus_state.len()).saturated_into()
+        ))]
+        pub fn create_client(
+            origin: OriginFor<T>,
+            client_state: Vec<u8>,
+            consensus_state: Vec<u8>,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+            let client_state = ClientState::try_from(decode_any::<T>(&client_state)?)
+                .map_err(log_error(Error::<T>::InvalidClientState))?;
+            let consensus_state = decode_any::<T>(&consensus_state)?;
+
+            let counter = ClientCounter::<T>::get();
+            let client_id = ClientId::new(client_type(), counter)
+                .map_err(log_error(Error::<T>::ClientExecutionFailed))?;
+            let mut ctx = Context::<T>::new();
+            client_state
+                .initialise(&mut ctx, &client_id, consensus_state)
+                .map_err(log_error(Error::<T>::InvalidClientState))?;
+            let consensus_height = client_state.latest_height();
+            ClientCounter::<T>::put(counter.saturating_add(1));
+
+            Self::deposit_event(Event::ClientCreated {
+                client_id,
+                consensus_height,
+            });
+            Ok(())
+        }
+
+        /// Updates a client with a protobuf-encoded header `Any`. Conflicting
+        /// signatures are not detected here, but submitted as misbehaviour.
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::update_client(header.len().saturated_into()))]
+        pub fn update_client(
+            origin: OriginFor<T>,
+            client_id: ClientId,
+            header: Vec<u8>,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+            let header = decode_any::<T>(&header)?;
+            let mut ctx = Context::<T>::new();
+            let client_state = active_client_state(&ctx, &client_id)?;
+
+            let update_kind = UpdateKind::UpdateClient;
+            client_state
+                .verify_client_message(&ctx, &client_id, header.clone(), &update_kind)
+                .map_err(log_error(Error::<T>::InvalidClientMessage))?;
+
+            let consensus_heights = client_state
+                .update_state(&mut ctx, &client_id, header)
+                .map_err(log_error(Error::<T>::ClientExecutionFailed))?;
+            Self::deposit_event(Event::ClientUpdated {
+                client_id,
+                consensus_heights,
+            });
+            Ok(())
+        }
+
+        /// Freezes a client with protobuf-encoded misbehaviour `Any`.
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::submit_misbehaviour(misbehaviour.len().saturated_into()))]
+        pub fn submit_misbehaviour(
+            origin: OriginFor<T>,
+            client_id: ClientId,
+            misbehaviour: Vec<u8>,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+            let misbehaviour = decode_any::<T>(&misbehaviour)?;
+            let mut ctx = Context::<T>::new();
+            let client_state = active_client_state(&ctx, &client_id)?;
+
+            let update_kind = UpdateKind::SubmitMisbehaviour;
+            client_state
+                .verify_client_message(&ctx, &client_id, misbehaviour.clone(), &update_kind)
+                .map_err(log_error(Error::<T>::InvalidClientMessage))?;
+            client_state
+                .update_state_on_misbehaviour(&mut ctx, &client_id, misbehaviour, &update_kind)
+                .map_err(log_error(Error::<T>::ClientExecutionFailed))?;
+
+            Self::deposit_event(Event::ClientMisbehaviour { client_id });
+            Ok(())
+        }
+    }
+
+    fn decode_any<T: Config>(bytes: &[u8]) -> Result<Any, Error<T>> {
+        <Any as Message>::decode(bytes).map_err(log_error(Error::<T>::InvalidAny))
+    }
+
+    /// Logs the client error a dispatch fails with, since the dispatch error
+    /// only carries the pallet error it is mapped to.
+    fn log_error<T: Config, E: core::fmt::Display>(
+        pallet_error: Error<T>,
+    ) -> impl FnOnce(E) -> Error<T> {
+        move |error| {
+            log::debug!(target: crate::LOG_TARGET, "{:?}: {}", pallet_error, error);
+            pallet_error
+        }
+    }
+
+    fn active_client_state<T: Config>(
+        ctx: &Context<T>,
+        client_id: &ClientId,
+    ) -> Result<ClientState, Error<T>> {
+        let client_state = ctx
+            .client_state(client_id)
+            .map_err(log_error(Error::<T>::ClientNotFound))?;
+        let status = client_state
+            .status(ctx, client_id)
+            .map_err(log_error(Error::<T>::ClientNotActive))?;
+        if status != Status::Active {
+            log::debug!(target: crate::LOG_TARGET, "client {} is {}", client_id, status);
+            return Err(Error::<T>::ClientNotActive);
+        }
+        Ok(client_state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mock::{new_test_ext, RuntimeOrigin, Solomachine, System, Test};
+    use crate::weights::WeightInfo;
+    use crate::{
+        ClientCounter, ClientStates, ConsensusStates, Error, Event, UpdateHeights, UpdateTimes,
+    };
+    use frame_support::{assert_noop, assert_ok};
+    use ibc_client_solomachine::types::cosmos::crypto::SigningKey;
+    use ibc_client_solomachine::types::header::SENTINEL_HEADER_PATH;
+    use ibc_client_solomachine::types::proof::types::header_data::HeaderData;
+    use ibc_client_solomachine::types::proof::types::sign_bytes::SignBytes;
+    use ibc_client_solomachine::types::proof::types::signature_and_data::SignatureAndData;
+    use ibc_client_solomachine::types::{
+        client_type, ClientState as ClientStateType, ConsensusState as ConsensusStateType, Header,
+        Misbehaviour,
+    };
+    use ibc_core::client::types::Height;
+    use ibc_core::host::types::identifiers::ClientId;
+    use ibc_core::primitives::Timestamp;
+    use ibc_proto::google::protobuf::Any;
+    use ibc_proto::Protobuf;
+    use prost::Message;
+
+    fn timestamp(nanoseconds: u64) -> Timestamp {
+        Timestamp::from_nanoseconds(nanoseconds).unwrap()
+    }
+
+    fn any_bytes(any: impl Into<Any>) -> Vec<u8> {
+        any.into().encode_to_vec()
+    }
+
+    fn create_client(key: &SigningKey) -> ClientId {
+        let consensus_state =
+            ConsensusStateType::new(key.public_key().unwrap(), "one".into(), timestamp(10));
+        let client_state =
+            ClientStateType::new(Height::new(0, 1).unwrap(), false, consensus_state.clone());
+        assert_ok!(Solomachine::create_client(
+            RuntimeOrigin::signed(1),
+            any_bytes(client_state),
+            any_bytes(consensus_state),
+        ));
+        ClientId::new(client_type(), ClientCounter::<Test>::get() - 1).unwrap()
+    }
+
+    fn header(key: &SigningKey, new_key: &SigningKey) -> Header {
+        let header_data = HeaderData {
+            new_pub_key: new_key.public_key().unwrap(),
+            new_diversifier: "two".into(),
+        };
+        Header {
+            timestamp: timestamp(20),
+            signature: key.sign_signature_data(SignBytes {
+                sequence: 1,
+                timestamp: 20,
+                diversifier: "one".into(),
+                path: SENTINEL_HEADER_PATH.as_bytes().to_vec(),
+                data: header_data.encode_vec(),
+            }),
+            new_public_key: new_key.public_key().unwrap(),
+            new_diversifier: "two".into(),
+        }
+    }
+
+    fn misbehaviour(key: &SigningKey) -> Misbehaviour {
+        let signature = |data: &[u8]| {
+            let path = b"/ibc/connections/connection-0".to_vec();
+            SignatureAndData {
+                signature: key.sign_signature_data(SignBytes {
+                    sequence: 2,
+                    timestamp: 30,
+                    diversifier: "two".into(),
+                    path: path.clone(),
+                    data: data.to_vec(),
+                }),
+                path,
+                data: data.to_vec(),
+                timestamp: timestamp(30),
+            }
+        };
+        Misbehaviour {
+            sequence: Height::new(0, 2).unwrap(),
+            signature_one: signature(b"one"),
+            signature_two: signature(b"two"),
+        }
+    }
+
+    #[test]
+    fn create_update_and_freeze_client() {
+        new_test_ext().execute_with(|| {
+            let key_one = SigningKey::from_ed25519_bytes(&[1u8; 32]).unwrap();
+            let key_two = SigningKey::from_secp256k1_bytes(&[2u8; 32]).unwrap();
+
+            let client_id = create_client(&key_one);
+            assert_eq!(client_id.as_str(), "06-solomachine-0");
+            System::assert_last_event(
+                Event::<Test>::ClientCreated {
+                    client_id: client_id.clone(),
+                    consensus_height: Height::new(0, 1).unwrap(),
+                }
+                .into(),
+            );
+            assert!(ConsensusStates::<Test>::contains_key(
+                &client_id,
+                Height::new(0, 1).unwrap()
+            ));
+
+            // rotate to the second key with a header signed by the first one
+            System::set_block_number(2);
+            assert_ok!(Solomachine::update_client(
+                RuntimeOrigin::signed(1),
+                client_id.clone(),
+                any_bytes(header(&key_one, &key_two)),
+            ));
+            System::assert_last_event(
+                Event::<Test>::ClientUpdated {
+                    client_id: client_id.clone(),
+                    consensus_heights: vec![Height::new(0, 2).unwrap()],
+                }
+                .into(),
+            );
+            let client_state = ClientStates::<Test>::get(&client_id).unwrap();
+            assert_eq!(
+                client_state.inner().consensus_state.public_key,
+                key_two.public_key().unwrap()
+            );
+            assert_eq!(
+                ConsensusStates::<Test>::get(&client_id, Height::new(0, 2).unwrap())
+                    .unwrap()
+                    .inner()
+                    .public_key,
+                key_two.public_key().unwrap()
+            );
+            assert_eq!(
+                UpdateTimes::<Test>::get(&client_id, Height::new(0, 2).unwrap()),
+                Some(100_000_000_000)
+            );
+            assert_eq!(
+                UpdateHeights::<Test>::get(&client_id, Height::new(0, 2).unwrap()),
+                Some(Height::new(0, 2).unwrap())
+            );
+
+            // two different messages signed at the same sequence freeze the client
+            assert_ok!(Solomachine::submit_misbehaviour(
+                RuntimeOrigin::signed(1),
+                client_id.clone(),
+                any_bytes(misbehaviour(&key_two)),
+            ));
+            System::assert_last_event(
+                Event::<Test>::ClientMisbehaviour {
+                    client_id: client_id.clone(),
+                }
+                .into(),
+            );
+            assert!(
+                ClientStates::<Test>::get(&client_id)
+                    .unwrap()
+                    .inner()
+                    .is_frozen
+            );
+
+            assert_noop!(
+                Solomachine::update_client(
+                    RuntimeOrigin::signed(1),
+                    client_id,
+                    any_bytes(header(&key_two, &key_one)),
+                ),
+                Error::<Test>::ClientNotActive
+            );
+        });
+    }
+
+    #[test]
+    fn invalid_messages_are_rejected() {
+        new_test_ext().execute_with(|| {
+            let key_one = SigningKey::from_ed25519_bytes(&[1u8; 32]).unwrap();
+            let key_two = SigningKey::from_secp256k1_bytes(&[2u8; 32]).unwrap();
+            let client_id = create_client(&key_one);
+
+            assert_noop!(
+                Solomachine::update_client(
+                    RuntimeOrigin::signed(1),
+                    client_id.clone(),
+                    vec![0xff],
+                ),
+                Error::<Test>::InvalidAny
+            );
+            // the header must be signed by the current key
+            assert_noop!(
+                Solomachine::update_client(
+                    RuntimeOrigin::signed(1),
+                    client_id.clone(),
+                    any_bytes(header(&key_two, &key_one)),
+                ),
+                Error::<Test>::InvalidClientMessage
+            );
+            assert_noop!(
+                Solomachine::submit_misbehaviour(
+                    RuntimeOrigin::signed(1),
+                    ClientId::new(client_type(), 7).unwrap(),
+                    any_bytes(misbehaviour(&key_one)),
+                ),
+                Error::<Test>::ClientNotFound
+            );
+            assert_noop!(
+                Solomachine::create_client(RuntimeOrigin::none(), vec![], vec![]),
+                sp_runtime::DispatchError::BadOrigin
+            );
+        });
+    }
+
+    #[test]
+    fn weights_grow_with_message_length() {
+        type Weights = ();
+        assert!(Weights::create_client(1_000).all_gt(Weights::create_client(0)));
+        assert!(Weights::update_client(1_000).all_gt(Weights::update_client(0)));
+        assert!(Weights::submit_misbehaviour(1_000).all_gt(Weights::submit_misbehaviour(0)));
+    }
+}
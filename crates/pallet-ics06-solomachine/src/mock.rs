@@ -0,0 +1,50 @@
+//! Mock runtime running the pallet next to `frame_system` and
+//! `pallet_timestamp`.
+
+use crate as pallet_ics06_solomachine;
+use frame_support::derive_impl;
+use frame_support::traits::ConstU64;
+use sp_runtime::BuildStorage;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+    pub enum Test {
+        System: frame_system,
+        Timestamp: pallet_timestamp,
+        Solomachine: pallet_ics06_solomachine,
+    }
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = Block;
+}
+
+impl pallet_timestamp::Config for Test {
+    type Moment = u64;
+    type OnTimestampSet = ();
+    type MinimumPeriod = ConstU64<1>;
+    type WeightInfo = ();
+}
+
+impl pallet_ics06_solomachine::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type TimeProvider = Timestamp;
+    type VerificationLimits = ();
+    type TimePolicy = ();
+    type WeightInfo = ();
+}
+
+/// Externalities at block 1, 100 seconds after the Unix epoch.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let storage = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+    let mut ext = sp_io::TestExternalities::new(storage);
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        Timestamp::set_timestamp(100_000);
+    });
+    ext
+}
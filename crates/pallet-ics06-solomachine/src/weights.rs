@@ -0,0 +1,38 @@
+//! Weights of the pallet's extrinsics.
+//!
+//! The default weights are estimates rather than benchmark results; runtimes
+//! should provide their own. Each weight grows with the length `n` of the
+//! encoded messages the extrinsic decodes and verifies.
+
+use frame_support::weights::constants::RocksDbWeight;
+use frame_support::weights::Weight;
+
+/// Weight functions needed by the pallet.
+pub trait WeightInfo {
+    fn create_client(n: u32) -> Weight;
+    fn update_client(n: u32) -> Weight;
+    fn submit_misbehaviour(n: u32) -> Weight;
+}
+
+/// Estimated weight of decoding and hashing one byte of a message.
+const PER_BYTE: Weight = Weight::from_parts(2_000, 1);
+
+impl WeightInfo for () {
+    fn create_client(n: u32) -> Weight {
+        Weight::from_parts(50_000_000, 0)
+            .saturating_add(PER_BYTE.saturating_mul(n.into()))
+            .saturating_add(RocksDbWeight::get().reads_writes(1, 5))
+    }
+
+    fn update_client(n: u32) -> Weight {
+        Weight::from_parts(100_000_000, 0)
+            .saturating_add(PER_BYTE.saturating_mul(n.into()))
+            .saturating_add(RocksDbWeight::get().reads_writes(3, 4))
+    }
+
+    fn submit_misbehaviour(n: u32) -> Weight {
+        Weight::from_parts(150_000_000, 0)
+            .saturating_add(PER_BYTE.saturating_mul(n.into()))
+            .saturating_add(RocksDbWeight::get().reads_writes(2, 1))
+    }
+}
@@ -0,0 +1,60 @@
+use crate::verify::VerifyError;
+use displaydoc::Display;
+use ibc_client_solomachine_types::cosmos::error::Error as CosmosError;
+use ibc_client_solomachine_types::error::Error as SmError;
+
+#[derive(Debug, Display)]
+pub enum Error {
+    /// invalid input `{input}`, expected `hex:<HEX>`, `base64:<BASE64>` or `file:<PATH>`
+    InvalidSource { input: String },
+    /// invalid encoding: `{description}`
+    Encoding { description: String },
+    /// io error on `{path}`: `{error}`
+    Io { path: String, error: std::io::Error },
+    /// decode error: `{description}`
+    Decode { description: String },
+    /// unknown solo machine type url: `{type_url}`
+    UnknownTypeUrl { type_url: String },
+    /// invalid path `{path}`: `{description}`
+    InvalidPath { path: String, description: String },
+    /// keyring error: `{0}`
+    Keyring(CosmosError),
+    /// solomachine error: `{0}`
+    Solomachine(SmError),
+    /// keyring passphrase is required, pass `--keyring-passphrase` or set `SOLOMACHINE_KEYRING_PASSPHRASE`
+    MissingPassphrase,
+    /// solo machine state not found in `{home}`, run `solomachine init` first
+    MissingState { home: String },
+    /// invalid argument: `{description}`
+    InvalidArgument { description: String },
+    /// json error: `{0}`
+    Json(serde_json_std::Error),
+    /// {0}
+    Verify(VerifyError),
+}
+
+impl std::error::Error for Error {}
+
+impl From<serde_json_std::Error> for Error {
+    fn from(e: serde_json_std::Error) -> Self {
+        Self::Json(e)
+    }
+}
+
+impl From<CosmosError> for Error {
+    fn from(e: CosmosError) -> Self {
+        Self::Keyring(e)
+    }
+}
+
+impl From<SmError> for Error {
+    fn from(e: SmError) -> Self {
+        Self::Solomachine(e)
+    }
+}
+
+impl From<VerifyError> for Error {
+    fn from(e: VerifyError) -> Self {
+        Self::Verify(e)
+    }
+}
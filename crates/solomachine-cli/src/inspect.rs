@@ -0,0 +1,158 @@
+//! `solomachine inspect`: decode solo machine messages into readable JSON.
+
+use crate::error::Error;
+use crate::source::Source;
+use clap::{Args, ValueEnum};
+use ibc_client_solomachine_types::client_state::{
+    decode_client_state, SOLOMACHINE_CLIENT_STATE_TYPE_URL,
+};
+use ibc_client_solomachine_types::consensus_state::SOLOMACHINE_CONSENSUS_STATE_TYPE_URL;
+use ibc_client_solomachine_types::header::SOLOMACHINE_HEADER_TYPE_URL;
+use ibc_client_solomachine_types::misbehaviour::SOLOMACHINE_MISBEHAVIOUR_TYPE_URL;
+use ibc_client_solomachine_types::proof::types::sign_bytes::SignBytes;
+use ibc_client_solomachine_types::proof::types::timestamped_signature_data::TimestampedSignatureData;
+use ibc_client_solomachine_types::{ConsensusState, Header, Misbehaviour};
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::lightclients::solomachine::v3::{
+    ConsensusState as RawSmConsensusState, Header as RawSmHeader, Misbehaviour as RawSmMisbehaviour,
+};
+use ibc_proto::Protobuf;
+use prost::Message;
+use serde_json_std::{json, Value};
+
+pub const SOLOMACHINE_TIMESTAMPED_SIGNATURE_DATA_TYPE_URL: &str =
+    "/ibc.lightclients.solomachine.v3.TimestampedSignatureData";
+
+pub const SOLOMACHINE_SIGN_BYTES_TYPE_URL: &str = "/ibc.lightclients.solomachine.v3.SignBytes";
+
+#[derive(Args, Debug)]
+pub struct InspectArgs {
+    /// Encoded message: `hex:<HEX>`, `base64:<BASE64>` or `file:<PATH>`
+    input: Source,
+    /// Decode the bytes as this message instead of a protobuf `Any`
+    #[arg(long = "as", value_enum)]
+    kind: Option<Kind>,
+}
+
+/// Solo machine messages the tool knows how to decode.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub enum Kind {
+    ClientState,
+    ConsensusState,
+    Header,
+    Misbehaviour,
+    TimestampedSignatureData,
+    SignBytes,
+}
+
+impl Kind {
+    const ALL: [Kind; 6] = [
+        Kind::ClientState,
+        Kind::ConsensusState,
+        Kind::Header,
+        Kind::Misbehaviour,
+        Kind::TimestampedSignatureData,
+        Kind::SignBytes,
+    ];
+
+    pub fn type_url(self) -> &'static str {
+        match self {
+            Kind::ClientState => SOLOMACHINE_CLIENT_STATE_TYPE_URL,
+            Kind::ConsensusState => SOLOMACHINE_CONSENSUS_STATE_TYPE_URL,
+            Kind::Header => SOLOMACHINE_HEADER_TYPE_URL,
+            Kind::Misbehaviour => SOLOMACHINE_MISBEHAVIOUR_TYPE_URL,
+            Kind::TimestampedSignatureData => SOLOMACHINE_TIMESTAMPED_SIGNATURE_DATA_TYPE_URL,
+            Kind::SignBytes => SOLOMACHINE_SIGN_BYTES_TYPE_URL,
+        }
+    }
+
+    pub fn from_type_url(type_url: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.type_url() == type_url)
+    }
+}
+
+fn decode_error<E: core::fmt::Display>(e: E) -> Error {
+    Error::Decode {
+        description: e.to_string(),
+    }
+}
+
+/// Decode the protobuf encoding of a `kind` message into JSON.
+pub fn decode(kind: Kind, bytes: &[u8]) -> Result<Value, Error> {
+    let value = match kind {
+        Kind::ClientState => {
+            serde_json_std::to_value(decode_client_state(bytes).map_err(decode_error)?)?
+        }
+        Kind::ConsensusState => serde_json_std::to_value(
+            <ConsensusState as Protobuf<RawSmConsensusState>>::decode_vec(bytes)
+                .map_err(decode_error)?,
+        )?,
+        Kind::Header => serde_json_std::to_value(
+            <Header as Protobuf<RawSmHeader>>::decode_vec(bytes).map_err(decode_error)?,
+        )?,
+        Kind::Misbehaviour => serde_json_std::to_value(
+            <Misbehaviour as Protobuf<RawSmMisbehaviour>>::decode_vec(bytes)
+                .map_err(decode_error)?,
+        )?,
+        Kind::TimestampedSignatureData => serde_json_std::to_value(
+            TimestampedSignatureData::decode_vec(bytes).map_err(decode_error)?,
+        )?,
+        Kind::SignBytes => {
+            serde_json_std::to_value(SignBytes::decode_vec(bytes).map_err(decode_error)?)?
+        }
+    };
+    Ok(value)
+}
+
+/// Decode a protobuf `Any` wrapping a solo machine message into JSON.
+pub fn decode_any(bytes: &[u8]) -> Result<Value, Error> {
+    let any = Any::decode(bytes).map_err(decode_error)?;
+    let kind = Kind::from_type_url(&any.type_url).ok_or_else(|| Error::UnknownTypeUrl {
+        type_url: any.type_url.clone(),
+    })?;
+
+    Ok(json!({
+        "@type": any.type_url,
+        "value": decode(kind, &any.value)?,
+    }))
+}
+
+pub fn run(args: InspectArgs) -> Result<(), Error> {
+    let bytes = args.input.read()?;
+    let value = match args.kind {
+        Some(kind) => decode(kind, &bytes)?,
+        None => decode_any(&bytes)?,
+    };
+    println!("{}", serde_json_std::to_string_pretty(&value)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode, decode_any, Kind};
+    use ibc_client_solomachine_types::cosmos::crypto::PublicKey;
+    use ibc_client_solomachine_types::ConsensusState;
+    use ibc_core::primitives::Timestamp;
+    use ibc_proto::google::protobuf::Any;
+    use prost::Message;
+
+    #[test]
+    fn decode_consensus_state() {
+        const PUBLIC_KEY_JSON: &str = "{\"@type\":\"/cosmos.crypto.ed25519.PubKey\",\"key\":\"sEEsVGkXvyewKLWMJbHVDRkBoerW0IIwmj1rHkabtHU=\"}";
+
+        let consensus_state = ConsensusState::new(
+            PUBLIC_KEY_JSON.parse::<PublicKey>().unwrap(),
+            "diversifier".into(),
+            Timestamp::from_nanoseconds(1).unwrap(),
+        );
+        let any = Any::from(consensus_state);
+
+        let value = decode_any(&any.encode_to_vec()).unwrap();
+        assert_eq!(value["@type"], Kind::ConsensusState.type_url());
+        assert_eq!(value["value"]["diversifier"], "diversifier");
+
+        assert!(decode(Kind::Header, &any.value).is_err());
+    }
+}
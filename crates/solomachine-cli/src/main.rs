@@ -0,0 +1,86 @@
+//! `solomachine`: command-line tool to run an ICS-06 solo machine offline, and
+//! to inspect solo machine messages and verify solo machine proofs.
+#![forbid(unsafe_code)]
+#![cfg_attr(not(test), deny(clippy::unwrap_used))]
+#![deny(
+    warnings,
+    trivial_casts,
+    trivial_numeric_casts,
+    unused_import_braces,
+    unused_qualifications,
+    rust_2018_idioms
+)]
+
+use clap::{Parser, Subcommand};
+use operator::Home;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+mod error;
+mod inspect;
+mod operator;
+mod source;
+mod verify;
+
+#[derive(Parser, Debug)]
+#[command(name = "solomachine", version, about)]
+struct Cli {
+    /// Directory holding the solo machine keyring and state
+    #[arg(
+        long,
+        global = true,
+        env = "SOLOMACHINE_HOME",
+        default_value = ".solomachine"
+    )]
+    home: PathBuf,
+    /// Passphrase of the `file` keyring in the home directory
+    #[arg(
+        long,
+        global = true,
+        env = "SOLOMACHINE_KEYRING_PASSPHRASE",
+        hide_env_values = true
+    )]
+    keyring_passphrase: Option<String>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Decode a solo machine message into JSON
+    Inspect(inspect::InspectArgs),
+    /// Verify a membership or non-membership proof against a client state
+    Verify(verify::VerifyArgs),
+    /// Manage the solo machine keys
+    Keys(operator::keys::KeysArgs),
+    /// Initialise the solo machine and print its client and consensus states
+    Init(operator::init::InitArgs),
+    /// Sign a header rotating the solo machine key or diversifier
+    SignHeader(operator::sign::SignHeaderArgs),
+    /// Sign a membership or non-membership proof
+    SignProof(operator::sign::SignProofArgs),
+    /// Build misbehaviour evidence from two conflicting proofs
+    Misbehaviour(operator::misbehaviour::MisbehaviourArgs),
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let home = Home::new(cli.home, cli.keyring_passphrase);
+    let result = match cli.command {
+        Command::Inspect(args) => inspect::run(args),
+        Command::Verify(args) => verify::run(args),
+        Command::Keys(args) => operator::keys::run(&home, args),
+        Command::Init(args) => operator::init::run(&home, args),
+        Command::SignHeader(args) => operator::sign::run_sign_header(&home, args),
+        Command::SignProof(args) => operator::sign::run_sign_proof(&home, args),
+        Command::Misbehaviour(args) => operator::misbehaviour::run(&home, args),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
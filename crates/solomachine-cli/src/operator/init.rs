@@ -0,0 +1,90 @@
+//! `solomachine init`: start a solo machine and print the client state and
+//! consensus state its counterparty client is created with.
+
+use crate::error::Error;
+use crate::operator::{any_to_base64, timestamp, to_base64, Home, OperatorState};
+use clap::Args;
+use ibc_client_solomachine_types::{ClientState, ConsensusState, MsgBuilder};
+use ibc_core::client::types::Height;
+use ibc_core::primitives::Signer;
+use ibc_proto::google::protobuf::Any;
+use serde_json_std::json;
+
+#[derive(Args, Debug)]
+pub struct InitArgs {
+    /// Name of the key the solo machine signs with
+    #[arg(long)]
+    key: String,
+    /// Diversifier of the solo machine
+    #[arg(long, default_value = "")]
+    diversifier: String,
+    /// Consensus state timestamp in nanoseconds, `0` for now
+    #[arg(long, default_value_t = 0)]
+    timestamp: u64,
+    /// Initial sequence of the client
+    #[arg(long, default_value_t = 1)]
+    sequence: u64,
+    /// Also print a `MsgCreateClient` signed by this relayer address
+    #[arg(long)]
+    signer: Option<String>,
+    /// Overwrite an existing solo machine state
+    #[arg(long)]
+    force: bool,
+}
+
+/// Write a fresh solo machine state to `home` and return the matching
+/// client and consensus states.
+pub fn init(
+    home: &Home,
+    key: &str,
+    diversifier: String,
+    timestamp_ns: u64,
+    sequence: u64,
+) -> Result<(ClientState, ConsensusState), Error> {
+    let state = OperatorState {
+        key: key.to_string(),
+        diversifier,
+        sequence,
+        timestamp: timestamp(timestamp_ns)?.nanoseconds(),
+    };
+    let consensus_state = home.consensus_state(&state)?;
+    let height = Height::new(0, sequence).map_err(|e| Error::InvalidArgument {
+        description: e.to_string(),
+    })?;
+    let client_state = ClientState::new(height, false, consensus_state.clone());
+    client_state.validate_basic()?;
+
+    home.save_state(&state)?;
+    Ok((client_state, consensus_state))
+}
+
+pub fn run(home: &Home, args: InitArgs) -> Result<(), Error> {
+    if !args.force && home.load_state().is_ok() {
+        return Err(Error::InvalidArgument {
+            description: "solo machine state already exists, pass `--force` to overwrite it"
+                .to_string(),
+        });
+    }
+
+    let (client_state, consensus_state) = init(
+        home,
+        &args.key,
+        args.diversifier,
+        args.timestamp,
+        args.sequence,
+    )?;
+
+    let mut output = json!({
+        "client_state": serde_json_std::to_value(&client_state)?,
+        "consensus_state": serde_json_std::to_value(&consensus_state)?,
+        "client_state_any": any_to_base64(Any::from(client_state.clone())),
+        "consensus_state_any": any_to_base64(Any::from(consensus_state.clone())),
+    });
+    if let Some(signer) = args.signer {
+        let msg =
+            MsgBuilder::new(Signer::from(signer)).create_client(client_state, consensus_state)?;
+        output["msg_create_client"] = to_base64(&msg).into();
+    }
+    println!("{}", serde_json_std::to_string_pretty(&output)?);
+    Ok(())
+}
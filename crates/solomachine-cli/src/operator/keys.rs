@@ -0,0 +1,117 @@
+//! `solomachine keys`: manage the solo machine keys in the home keyring.
+
+use crate::error::Error;
+use crate::operator::Home;
+use crate::source::Source;
+use clap::{Args, Subcommand};
+use ibc_client_solomachine_types::cosmos::crypto::{Algorithm, SigningKey};
+use ibc_client_solomachine_types::cosmos::keyring::KeyRecord;
+use rand_core::{OsRng, RngCore};
+use serde_json_std::{json, Value};
+
+#[derive(Args, Debug)]
+pub struct KeysArgs {
+    /// Bech32 prefix of the printed addresses
+    #[arg(long, global = true, default_value = "cosmos")]
+    prefix: String,
+    #[command(subcommand)]
+    command: KeysCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum KeysCommand {
+    /// Generate a new key
+    Add {
+        name: String,
+        /// Key algorithm: `ed25519` or `secp256k1`
+        #[arg(long, default_value = "secp256k1", value_parser = parse_algorithm)]
+        algo: Algorithm,
+    },
+    /// Import a private key exported with `keys export` or `<chain>d keys export`
+    Import {
+        name: String,
+        /// Encrypted armored private key
+        #[arg(long)]
+        armor: Source,
+        /// Passphrase the armor is encrypted with
+        #[arg(long, env = "SOLOMACHINE_ARMOR_PASSPHRASE", hide_env_values = true)]
+        armor_passphrase: String,
+    },
+    /// Export a private key as an encrypted armor
+    Export {
+        name: String,
+        /// Passphrase to encrypt the armor with
+        #[arg(long, env = "SOLOMACHINE_ARMOR_PASSPHRASE", hide_env_values = true)]
+        armor_passphrase: String,
+    },
+    /// Show a key
+    Show { name: String },
+    /// List all keys
+    List,
+}
+
+fn parse_algorithm(s: &str) -> Result<Algorithm, String> {
+    s.parse().map_err(|e| format!("{}", e))
+}
+
+/// Generate a key of the given algorithm and store it under `name`.
+pub fn add_key(home: &Home, name: &str, algorithm: Algorithm) -> Result<KeyRecord, Error> {
+    let mut seed = [0u8; 32];
+    OsRng.fill_bytes(&mut seed);
+    let signing_key = match algorithm {
+        Algorithm::Ed25519 => SigningKey::from_ed25519_bytes(&seed)?,
+        Algorithm::Secp256k1 => SigningKey::from_secp256k1_bytes(&seed)?,
+    };
+    Ok(home.keyring()?.add(name, &signing_key)?)
+}
+
+fn key_json(key_record: &KeyRecord, prefix: &str) -> Result<Value, Error> {
+    Ok(json!({
+        "name": key_record.name,
+        "type": key_record.public_key.type_url(),
+        "address": key_record.public_key.account_id(prefix)?.to_string(),
+        "pubkey": key_record.public_key.try_to_json()?,
+    }))
+}
+
+pub fn run(home: &Home, args: KeysArgs) -> Result<(), Error> {
+    let prefix = &args.prefix;
+    let output = match args.command {
+        KeysCommand::Add { name, algo } => key_json(&add_key(home, &name, algo)?, prefix)?,
+        KeysCommand::Import {
+            name,
+            armor,
+            armor_passphrase,
+        } => {
+            let armor = String::from_utf8(armor.read()?).map_err(|e| Error::Encoding {
+                description: e.to_string(),
+            })?;
+            let key_record = home
+                .keyring()?
+                .import_armor(&name, &armor, &armor_passphrase)?;
+            key_json(&key_record, prefix)?
+        }
+        KeysCommand::Export {
+            name,
+            armor_passphrase,
+        } => {
+            println!(
+                "{}",
+                home.keyring()?.export_armor(&name, &armor_passphrase)?
+            );
+            return Ok(());
+        }
+        KeysCommand::Show { name } => key_json(&home.keyring()?.get(&name)?, prefix)?,
+        KeysCommand::List => {
+            let keyring = home.keyring()?;
+            let keys = keyring
+                .list()?
+                .iter()
+                .map(|name| key_json(&keyring.get(name)?, prefix))
+                .collect::<Result<Vec<_>, _>>()?;
+            Value::Array(keys)
+        }
+    };
+    println!("{}", serde_json_std::to_string_pretty(&output)?);
+    Ok(())
+}
@@ -0,0 +1,111 @@
+//! `solomachine misbehaviour`: turn two conflicting signatures into
+//! misbehaviour evidence.
+
+use crate::error::Error;
+use crate::operator::{any_to_base64, parse_client_id, to_base64, Home};
+use crate::source::Source;
+use clap::Args;
+use ibc_client_solomachine_types::proof::sign_bytes_path;
+use ibc_client_solomachine_types::proof::types::signature_and_data::SignatureAndData;
+use ibc_client_solomachine_types::proof::types::timestamped_signature_data::TimestampedSignatureData;
+use ibc_client_solomachine_types::{Misbehaviour, MsgBuilder};
+use ibc_core::client::types::Height;
+use ibc_core::host::types::identifiers::ClientId;
+use ibc_core::host::types::path::Path;
+use ibc_core::primitives::Signer;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::core::commitment::v1::MerklePath;
+use ibc_proto::Protobuf;
+use serde_json_std::json;
+
+#[derive(Args, Debug)]
+pub struct MisbehaviourArgs {
+    /// First proof, as printed by `sign-proof`
+    #[arg(long)]
+    signature_one: Source,
+    /// Value the first proof was signed over
+    #[arg(long)]
+    value_one: Source,
+    /// Second proof over different data at the same sequence
+    #[arg(long)]
+    signature_two: Source,
+    /// Value the second proof was signed over
+    #[arg(long)]
+    value_two: Source,
+    /// ICS-24 path both proofs were signed at
+    #[arg(long)]
+    path: String,
+    /// Commitment prefix of the solo machine store
+    #[arg(long, default_value = "ibc")]
+    prefix: String,
+    /// Sequence both proofs were signed at, defaults to the current one
+    #[arg(long)]
+    sequence: Option<u64>,
+    /// Also print a `MsgSubmitMisbehaviour` for this client, signed by `--signer`
+    #[arg(long, requires = "signer", value_parser = parse_client_id)]
+    client_id: Option<ClientId>,
+    /// Relayer address signing the `MsgSubmitMisbehaviour`
+    #[arg(long, requires = "client_id")]
+    signer: Option<String>,
+}
+
+/// Build misbehaviour evidence from two proofs of `value_one` and `value_two`
+/// at `path`, signed at `sequence`.
+pub fn misbehaviour(
+    sequence: u64,
+    path: MerklePath,
+    (proof_one, value_one): (&[u8], Vec<u8>),
+    (proof_two, value_two): (&[u8], Vec<u8>),
+) -> Result<Misbehaviour, Error> {
+    let signature_and_data = |proof: &[u8], data: Vec<u8>| {
+        let proof = TimestampedSignatureData::decode_vec(proof).map_err(|e| Error::Decode {
+            description: e.to_string(),
+        })?;
+        Ok::<_, Error>(SignatureAndData {
+            signature: proof.signature_data,
+            path: sign_bytes_path(&path),
+            data,
+            timestamp: proof.timestamp,
+        })
+    };
+    let misbehaviour = Misbehaviour {
+        sequence: Height::new(0, sequence).map_err(|e| Error::InvalidArgument {
+            description: e.to_string(),
+        })?,
+        signature_one: signature_and_data(proof_one, value_one)?,
+        signature_two: signature_and_data(proof_two, value_two)?,
+    };
+    misbehaviour.validate_basic()?;
+    Ok(misbehaviour)
+}
+
+pub fn run(home: &Home, args: MisbehaviourArgs) -> Result<(), Error> {
+    let sequence = match args.sequence {
+        Some(sequence) => sequence,
+        None => home.load_state()?.sequence,
+    };
+    let path = args.path.parse::<Path>().map_err(|e| Error::InvalidPath {
+        path: args.path.clone(),
+        description: e.to_string(),
+    })?;
+    let misbehaviour = misbehaviour(
+        sequence,
+        MerklePath {
+            key_path: vec![args.prefix, path.to_string()],
+        },
+        (&args.signature_one.read()?, args.value_one.read()?),
+        (&args.signature_two.read()?, args.value_two.read()?),
+    )?;
+
+    let mut output = json!({
+        "misbehaviour": serde_json_std::to_value(&misbehaviour)?,
+        "misbehaviour_any": any_to_base64(Any::from(misbehaviour.clone())),
+    });
+    if let (Some(client_id), Some(signer)) = (args.client_id, args.signer) {
+        let msg =
+            MsgBuilder::new(Signer::from(signer)).submit_misbehaviour(&client_id, misbehaviour)?;
+        output["msg_submit_misbehaviour"] = to_base64(&msg).into();
+    }
+    println!("{}", serde_json_std::to_string_pretty(&output)?);
+    Ok(())
+}
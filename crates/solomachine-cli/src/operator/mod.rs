@@ -0,0 +1,194 @@
+//! Commands to run a solo machine: its keys and its state live in a local
+//! home directory and nothing ever leaves the machine.
+
+use crate::error::Error;
+use ibc_client_solomachine_types::cosmos::crypto::SigningKey;
+use ibc_client_solomachine_types::cosmos::keyring::{FileKeyring, KEYRING_FILE_DIR};
+use ibc_client_solomachine_types::ConsensusState;
+use ibc_core::host::types::identifiers::ClientId;
+use ibc_core::primitives::Timestamp;
+use ibc_proto::google::protobuf::Any;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+pub mod init;
+pub mod keys;
+pub mod misbehaviour;
+pub mod sign;
+
+const STATE_FILE: &str = "state.json";
+
+/// What the solo machine has to remember between signatures: the key and
+/// diversifier its counterparty client currently trusts, at which sequence.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct OperatorState {
+    /// name of the current key in the keyring
+    pub key: String,
+    /// current diversifier
+    pub diversifier: String,
+    /// sequence the next header or proof is signed at
+    pub sequence: u64,
+    /// timestamp of the latest consensus state, in nanoseconds
+    pub timestamp: u64,
+}
+
+/// The solo machine home directory.
+#[derive(Clone, Debug)]
+pub struct Home {
+    dir: PathBuf,
+    passphrase: Option<String>,
+}
+
+impl Home {
+    pub fn new(dir: PathBuf, passphrase: Option<String>) -> Self {
+        Self { dir, passphrase }
+    }
+
+    /// Open the `file` keyring of this home, creating it if needed.
+    pub fn keyring(&self) -> Result<FileKeyring, Error> {
+        let passphrase = self.passphrase.as_deref().ok_or(Error::MissingPassphrase)?;
+        Ok(FileKeyring::create(
+            self.dir.join(KEYRING_FILE_DIR),
+            passphrase,
+        )?)
+    }
+
+    pub fn load_state(&self) -> Result<OperatorState, Error> {
+        let path = self.dir.join(STATE_FILE);
+        match fs::read(&path) {
+            Ok(bytes) => Ok(serde_json_std::from_slice(&bytes)?),
+            Err(e) if e.kind() == ErrorKind::NotFound => Err(Error::MissingState {
+                home: self.dir.display().to_string(),
+            }),
+            Err(error) => Err(Error::Io {
+                path: path.display().to_string(),
+                error,
+            }),
+        }
+    }
+
+    pub fn save_state(&self, state: &OperatorState) -> Result<(), Error> {
+        let path = self.dir.join(STATE_FILE);
+        let bytes = serde_json_std::to_vec_pretty(state)?;
+        fs::create_dir_all(&self.dir)
+            .and_then(|_| fs::write(&path, bytes))
+            .map_err(|error| Error::Io {
+                path: path.display().to_string(),
+                error,
+            })
+    }
+
+    /// The key the solo machine currently signs with.
+    pub fn signing_key(&self, state: &OperatorState) -> Result<SigningKey, Error> {
+        Ok(self.keyring()?.signing_key(&state.key)?)
+    }
+
+    /// The consensus state the counterparty client holds for `state`.
+    pub fn consensus_state(&self, state: &OperatorState) -> Result<ConsensusState, Error> {
+        let public_key = self.keyring()?.get(&state.key)?.public_key;
+        let timestamp = timestamp(state.timestamp)?;
+        Ok(ConsensusState::new(
+            public_key,
+            state.diversifier.clone(),
+            timestamp,
+        ))
+    }
+}
+
+/// Parse a timestamp in nanoseconds, `0` meaning now.
+pub fn timestamp(nanoseconds: u64) -> Result<Timestamp, Error> {
+    if nanoseconds == 0 {
+        return Ok(Timestamp::now());
+    }
+    Timestamp::from_nanoseconds(nanoseconds).map_err(|e| Error::InvalidArgument {
+        description: e.to_string(),
+    })
+}
+
+/// Parse a client identifier given on the command line.
+pub fn parse_client_id(s: &str) -> Result<ClientId, String> {
+    s.parse().map_err(|e| format!("{}", e))
+}
+
+/// Base64 encoding of a protobuf message, as printed for piping into
+/// `inspect`, `verify` or a relayer.
+pub fn to_base64<M: Message>(message: &M) -> String {
+    String::from_utf8(subtle_encoding::base64::encode(message.encode_to_vec())).unwrap_or_default()
+}
+
+/// Base64 encoding of a message wrapped in an `Any`.
+pub fn any_to_base64(any: Any) -> String {
+    to_base64(&any)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::keys::add_key;
+    use super::sign::sign_proof;
+    use super::{init, Home};
+    use crate::verify::verify;
+    use ibc_client_solomachine_types::cosmos::crypto::Algorithm;
+    use ibc_client_solomachine_types::ClientState;
+    use ibc_core::client::types::Height;
+    use ibc_core::host::types::path::Path;
+    use ibc_proto::google::protobuf::Any;
+    use ibc_proto::Protobuf;
+    use prost::Message;
+
+    #[test]
+    fn init_and_sign_proof() {
+        let dir = std::env::temp_dir().join(format!("solomachine-cli-{}", std::process::id()));
+        let home = Home::new(dir.clone(), Some("passphrase".into()));
+
+        add_key(&home, "operator", Algorithm::Secp256k1).unwrap();
+        let (client_state, _) = init::init(&home, "operator", "diversifier".into(), 10, 1).unwrap();
+        assert_eq!(home.load_state().unwrap().sequence, 1);
+
+        let path = "connections/connection-0".parse::<Path>().unwrap();
+        let proof = sign_proof(&home, "ibc", &path, b"connection".to_vec(), 20, None).unwrap();
+
+        verify(
+            &Any::from(client_state.clone()).encode_to_vec(),
+            &proof.encode_vec(),
+            None,
+            "ibc",
+            &path,
+            b"connection".to_vec(),
+        )
+        .unwrap();
+
+        // the next proof is signed at the next sequence
+        let state = home.load_state().unwrap();
+        assert_eq!((state.sequence, state.timestamp), (2, 20));
+        let proof = sign_proof(&home, "ibc", &path, b"connection".to_vec(), 30, None).unwrap();
+        assert!(verify(
+            &Any::from(client_state).encode_to_vec(),
+            &proof.clone().encode_vec(),
+            None,
+            "ibc",
+            &path,
+            b"connection".to_vec(),
+        )
+        .is_err());
+        let client_state = ClientState::new(
+            Height::new(0, 2).unwrap(),
+            false,
+            home.consensus_state(&state).unwrap(),
+        );
+        verify(
+            &Any::from(client_state).encode_to_vec(),
+            &proof.encode_vec(),
+            None,
+            "ibc",
+            &path,
+            b"connection".to_vec(),
+        )
+        .unwrap();
+        assert_eq!(home.load_state().unwrap().sequence, 3);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
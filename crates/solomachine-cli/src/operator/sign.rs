@@ -0,0 +1,205 @@
+//! `solomachine sign-header` and `solomachine sign-proof`.
+
+use crate::error::Error;
+use crate::operator::{any_to_base64, parse_client_id, timestamp, to_base64, Home, OperatorState};
+use crate::source::Source;
+use clap::Args;
+use ibc_client_solomachine_types::header::SENTINEL_HEADER_PATH;
+use ibc_client_solomachine_types::proof::sign_bytes_path;
+use ibc_client_solomachine_types::proof::types::header_data::HeaderData;
+use ibc_client_solomachine_types::proof::types::sign_bytes::SignBytes;
+use ibc_client_solomachine_types::proof::types::timestamped_signature_data::TimestampedSignatureData;
+use ibc_client_solomachine_types::{Header, MsgBuilder};
+use ibc_core::host::types::identifiers::ClientId;
+use ibc_core::host::types::path::Path;
+use ibc_core::primitives::{Signer, Timestamp};
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::core::commitment::v1::MerklePath;
+use ibc_proto::ibc::lightclients::solomachine::v3::TimestampedSignatureData as RawTimestampedSignatureData;
+use ibc_proto::Protobuf;
+use serde_json_std::json;
+
+#[derive(Args, Debug)]
+pub struct SignHeaderArgs {
+    /// Name of the key to rotate to; the current key signs the header
+    #[arg(long)]
+    new_key: String,
+    /// Diversifier to rotate to, defaults to the current one
+    #[arg(long)]
+    new_diversifier: Option<String>,
+    /// Header timestamp in nanoseconds, `0` for now
+    #[arg(long, default_value_t = 0)]
+    timestamp: u64,
+    /// Also print a `MsgUpdateClient` for this client, signed by `--signer`
+    #[arg(long, requires = "signer", value_parser = parse_client_id)]
+    client_id: Option<ClientId>,
+    /// Relayer address signing the `MsgUpdateClient`
+    #[arg(long, requires = "client_id")]
+    signer: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct SignProofArgs {
+    /// ICS-24 path to prove, e.g. `connections/connection-0`
+    #[arg(long)]
+    path: String,
+    /// Value to prove at the path; omit it to prove non-membership
+    #[arg(long)]
+    value: Option<Source>,
+    /// Commitment prefix of the solo machine store
+    #[arg(long, default_value = "ibc")]
+    prefix: String,
+    /// Proof timestamp in nanoseconds, `0` for now
+    #[arg(long, default_value_t = 0)]
+    timestamp: u64,
+    /// Sign at this sequence instead of the current one
+    #[arg(long)]
+    sequence: Option<u64>,
+}
+
+/// Resolve a signature timestamp, which may not precede the consensus state.
+fn signature_timestamp(state: &OperatorState, nanoseconds: u64) -> Result<Timestamp, Error> {
+    let timestamp = timestamp(nanoseconds)?;
+    if timestamp.nanoseconds() < state.timestamp {
+        return Err(Error::InvalidArgument {
+            description: format!(
+                "timestamp {} is before the consensus state timestamp {}",
+                timestamp.nanoseconds(),
+                state.timestamp
+            ),
+        });
+    }
+    Ok(timestamp)
+}
+
+/// Sign a header rotating to `new_key`, then advance the solo machine state
+/// to the new key, diversifier and the next sequence.
+pub fn sign_header(
+    home: &Home,
+    new_key: &str,
+    new_diversifier: Option<String>,
+    timestamp_ns: u64,
+) -> Result<Header, Error> {
+    let state = home.load_state()?;
+    let timestamp = signature_timestamp(&state, timestamp_ns)?;
+    let signing_key = home.signing_key(&state)?;
+    let new_public_key = home.keyring()?.get(new_key)?.public_key;
+    let new_diversifier = new_diversifier.unwrap_or_else(|| state.diversifier.clone());
+
+    let header_data = HeaderData {
+        new_pub_key: new_public_key,
+        new_diversifier: new_diversifier.clone(),
+    };
+    let signature = signing_key.sign_signature_data(SignBytes {
+        sequence: state.sequence,
+        timestamp: timestamp.nanoseconds(),
+        diversifier: state.diversifier.clone(),
+        path: SENTINEL_HEADER_PATH.as_bytes().to_vec(),
+        data: header_data.encode_vec(),
+    });
+
+    let header = Header {
+        timestamp,
+        signature,
+        new_public_key,
+        new_diversifier: new_diversifier.clone(),
+    };
+    header.validate_basic()?;
+
+    home.save_state(&OperatorState {
+        key: new_key.to_string(),
+        diversifier: new_diversifier,
+        sequence: state.sequence + 1,
+        timestamp: timestamp.nanoseconds(),
+    })?;
+    Ok(header)
+}
+
+/// Sign a (non-)membership proof of `value` at `path`. An empty `value`
+/// proves non-membership.
+///
+/// Without an explicit `sequence` the proof is signed at the current one and
+/// the solo machine state advances to the next sequence and the proof
+/// timestamp, as the counterparty client does once it verifies the proof.
+pub fn sign_proof(
+    home: &Home,
+    prefix: &str,
+    path: &Path,
+    value: Vec<u8>,
+    timestamp_ns: u64,
+    sequence: Option<u64>,
+) -> Result<TimestampedSignatureData, Error> {
+    let state = home.load_state()?;
+    let timestamp = signature_timestamp(&state, timestamp_ns)?;
+    let signing_key = home.signing_key(&state)?;
+    let key_path = MerklePath {
+        key_path: vec![prefix.to_string(), path.to_string()],
+    };
+
+    let signature_data = signing_key.sign_signature_data(SignBytes {
+        sequence: sequence.unwrap_or(state.sequence),
+        timestamp: timestamp.nanoseconds(),
+        diversifier: state.diversifier.clone(),
+        path: sign_bytes_path(&key_path),
+        data: value,
+    });
+
+    if sequence.is_none() {
+        home.save_state(&OperatorState {
+            sequence: state.sequence + 1,
+            timestamp: timestamp.nanoseconds(),
+            ..state
+        })?;
+    }
+    Ok(TimestampedSignatureData {
+        signature_data,
+        timestamp,
+    })
+}
+
+pub fn run_sign_header(home: &Home, args: SignHeaderArgs) -> Result<(), Error> {
+    let header = sign_header(home, &args.new_key, args.new_diversifier, args.timestamp)?;
+
+    let mut output = json!({
+        "header": serde_json_std::to_value(&header)?,
+        "header_any": any_to_base64(Any::from(header.clone())),
+    });
+    if let (Some(client_id), Some(signer)) = (args.client_id, args.signer) {
+        let msg = MsgBuilder::new(Signer::from(signer)).update_client(&client_id, header)?;
+        output["msg_update_client"] = to_base64(&msg).into();
+    }
+    println!("{}", serde_json_std::to_string_pretty(&output)?);
+    Ok(())
+}
+
+pub fn run_sign_proof(home: &Home, args: SignProofArgs) -> Result<(), Error> {
+    let path = args.path.parse::<Path>().map_err(|e| Error::InvalidPath {
+        path: args.path.clone(),
+        description: e.to_string(),
+    })?;
+    let value = match &args.value {
+        Some(value) => value.read()?,
+        None => vec![],
+    };
+
+    let sequence = match args.sequence {
+        Some(sequence) => sequence,
+        None => home.load_state()?.sequence,
+    };
+    let proof = sign_proof(
+        home,
+        &args.prefix,
+        &path,
+        value,
+        args.timestamp,
+        args.sequence,
+    )?;
+
+    let output = json!({
+        "sequence": sequence,
+        "timestamp": proof.timestamp.nanoseconds(),
+        "proof": to_base64(&RawTimestampedSignatureData::from(proof)),
+    });
+    println!("{}", serde_json_std::to_string_pretty(&output)?);
+    Ok(())
+}
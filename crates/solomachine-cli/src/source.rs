@@ -0,0 +1,69 @@
+//! Byte inputs given on the command line.
+
+use crate::error::Error;
+use std::path::PathBuf;
+use std::str::FromStr;
+use subtle_encoding::{base64, hex};
+
+/// Where to read an encoded message from: `hex:<HEX>`, `base64:<BASE64>` or
+/// `file:<PATH>` for a file holding the raw bytes.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Source {
+    Hex(String),
+    Base64(String),
+    File(PathBuf),
+}
+
+impl Source {
+    /// Read the bytes this source points at.
+    pub fn read(&self) -> Result<Vec<u8>, Error> {
+        match self {
+            Source::Hex(s) => {
+                let s = s.trim();
+                let s = s.strip_prefix("0x").unwrap_or(s);
+                hex::decode(s.to_ascii_lowercase()).map_err(|e| Error::Encoding {
+                    description: e.to_string(),
+                })
+            }
+            Source::Base64(s) => base64::decode(s.trim()).map_err(|e| Error::Encoding {
+                description: e.to_string(),
+            }),
+            Source::File(path) => std::fs::read(path).map_err(|e| Error::Io {
+                path: path.display().to_string(),
+                error: e,
+            }),
+        }
+    }
+}
+
+impl FromStr for Source {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some(("hex", value)) => Ok(Source::Hex(value.to_string())),
+            Some(("base64", value)) => Ok(Source::Base64(value.to_string())),
+            Some(("file", path)) => Ok(Source::File(PathBuf::from(path))),
+            _ => Err(Error::InvalidSource {
+                input: s.to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Source;
+
+    #[test]
+    fn parse_and_read() {
+        let hex = "hex:0x0A0b".parse::<Source>().unwrap();
+        assert_eq!(hex.read().unwrap(), vec![0x0a, 0x0b]);
+
+        let base64 = "base64:Cgs=".parse::<Source>().unwrap();
+        assert_eq!(base64.read().unwrap(), vec![0x0a, 0x0b]);
+
+        assert!("0a0b".parse::<Source>().is_err());
+        assert!("hex:zz".parse::<Source>().unwrap().read().is_err());
+    }
+}
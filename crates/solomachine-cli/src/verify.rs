@@ -0,0 +1,222 @@
+//! `solomachine verify`: check a proof against a client state the way the
+//! solo machine client does, reporting why it is rejected.
+
+use crate::error::Error;
+use crate::source::Source;
+use clap::Args;
+use displaydoc::Display;
+use ibc_client_solomachine::client_state::ClientState;
+use ibc_client_solomachine_types::{TimePolicy, VerificationLimits};
+use ibc_core::client::context::client_state::ClientStateCommon;
+use ibc_core::commitment_types::commitment::CommitmentProofBytes;
+use ibc_core::host::types::path::Path;
+use ibc_core::primitives::Timestamp;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::core::commitment::v1::MerklePath;
+use prost::Message;
+use std::time::Duration;
+
+#[derive(Args, Debug)]
+pub struct VerifyArgs {
+    /// Client state as an encoded protobuf `Any`
+    #[arg(long)]
+    client_state: Source,
+    /// Proof: an encoded `TimestampedSignatureData`
+    #[arg(long)]
+    proof: Source,
+    /// ICS-24 path the proof is for, e.g. `connections/connection-0`
+    #[arg(long)]
+    path: String,
+    /// Value committed at the path; omit it to verify non-membership
+    #[arg(long)]
+    value: Option<Source>,
+    /// Commitment prefix of the counterparty store
+    #[arg(long, default_value = "ibc")]
+    prefix: String,
+    /// Reject proofs timestamped more than this many seconds ahead of the local clock
+    #[arg(long)]
+    max_clock_drift: Option<u64>,
+    /// Reject proofs timestamped more than this many seconds behind the local clock
+    #[arg(long)]
+    max_proof_age: Option<u64>,
+}
+
+/// The reason a proof is rejected.
+#[derive(Debug, Display, PartialEq)]
+pub enum VerifyError {
+    /// decode check failed: `{0}`
+    Decode(String),
+    /// proof check failed: `{0}`
+    Proof(String),
+}
+
+/// Verify a (non-)membership proof with the solo machine client, under the
+/// default verification limits and, if given, a time policy checked against
+/// a host timestamp. An empty `value` means non-membership.
+pub fn verify(
+    client_state: &[u8],
+    proof: &[u8],
+    time_bounds: Option<(TimePolicy, Timestamp)>,
+    prefix: &str,
+    path: &Path,
+    value: Vec<u8>,
+) -> Result<(), VerifyError> {
+    let client_state = Any::decode(client_state)
+        .map_err(|e| VerifyError::Decode(format!("client state: {}", e)))
+        .and_then(|any| {
+            ClientState::try_from(any)
+                .map_err(|e| VerifyError::Decode(format!("client state: {}", e)))
+        })?;
+    let proof = CommitmentProofBytes::try_from(proof.to_vec())
+        .map_err(|e| VerifyError::Decode(format!("proof: {}", e)))?;
+
+    client_state
+        .verify_signed_path_with(
+            &VerificationLimits::default(),
+            time_bounds,
+            client_state.latest_height(),
+            &proof,
+            &MerklePath {
+                key_path: vec![prefix.to_string(), path.to_string()],
+            },
+            value,
+        )
+        .map_err(|e| VerifyError::Proof(e.to_string()))
+}
+
+pub fn run(args: VerifyArgs) -> Result<(), Error> {
+    let path = args.path.parse::<Path>().map_err(|e| Error::InvalidPath {
+        path: args.path.clone(),
+        description: e.to_string(),
+    })?;
+    let value = match &args.value {
+        Some(value) => value.read()?,
+        None => vec![],
+    };
+    let time_policy = TimePolicy {
+        max_clock_drift: args.max_clock_drift.map(Duration::from_secs),
+        max_proof_age: args.max_proof_age.map(Duration::from_secs),
+    };
+    let time_bounds = time_policy
+        .is_enabled()
+        .then(|| (time_policy, Timestamp::now()));
+
+    verify(
+        &args.client_state.read()?,
+        &args.proof.read()?,
+        time_bounds,
+        &args.prefix,
+        &path,
+        value,
+    )?;
+
+    println!("ok");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{verify, VerifyError};
+    use ibc_client_solomachine_types::cosmos::crypto::SigningKey;
+    use ibc_client_solomachine_types::proof::types::sign_bytes::SignBytes;
+    use ibc_client_solomachine_types::proof::types::timestamped_signature_data::TimestampedSignatureData;
+    use ibc_client_solomachine_types::{ClientState, ConsensusState, TimePolicy};
+    use ibc_core::client::types::Height;
+    use ibc_core::host::types::path::Path;
+    use ibc_core::primitives::Timestamp;
+    use ibc_proto::google::protobuf::Any;
+    use ibc_proto::Protobuf;
+    use prost::Message;
+    use std::time::Duration;
+
+    #[test]
+    fn verify_membership_proof() {
+        let signing_key = SigningKey::from_ed25519_bytes(&[1u8; 32]).unwrap();
+        let consensus_state = ConsensusState::new(
+            signing_key.public_key().unwrap(),
+            "diversifier".into(),
+            Timestamp::from_nanoseconds(10).unwrap(),
+        );
+        let client_state = ClientState::new(Height::new(0, 1).unwrap(), false, consensus_state);
+        let frozen_client_state = Any::from(client_state.clone().with_frozen()).encode_to_vec();
+        let client_state = Any::from(client_state).encode_to_vec();
+
+        let path = "connections/connection-0".parse::<Path>().unwrap();
+        let key_path = b"/ibc/connections/connection-0".to_vec();
+        let proof = |timestamp: u64| {
+            let signature_data = signing_key.sign_signature_data(SignBytes {
+                sequence: 1,
+                timestamp,
+                diversifier: "diversifier".into(),
+                path: key_path.clone(),
+                data: b"connection".to_vec(),
+            });
+            TimestampedSignatureData {
+                signature_data,
+                timestamp: Timestamp::from_nanoseconds(timestamp).unwrap(),
+            }
+            .encode_vec()
+        };
+        let verify_connection = |client_state: &[u8], proof: &[u8], time_bounds| {
+            verify(
+                client_state,
+                proof,
+                time_bounds,
+                "ibc",
+                &path,
+                b"connection".to_vec(),
+            )
+        };
+
+        verify_connection(&client_state, &proof(20), None).unwrap();
+
+        assert!(matches!(
+            verify(
+                &client_state,
+                &proof(20),
+                None,
+                "ibc",
+                &path,
+                b"channel".to_vec()
+            ),
+            Err(VerifyError::Proof(_))
+        ));
+        // a proof timestamped before the consensus state
+        assert!(matches!(
+            verify_connection(&client_state, &proof(5), None),
+            Err(VerifyError::Proof(_))
+        ));
+        assert!(matches!(
+            verify_connection(&frozen_client_state, &proof(20), None),
+            Err(VerifyError::Proof(_))
+        ));
+        // a proof older than the time policy allows
+        let time_policy = TimePolicy {
+            max_clock_drift: None,
+            max_proof_age: Some(Duration::from_nanos(5)),
+        };
+        let host_timestamp = Timestamp::from_nanoseconds(30).unwrap();
+        assert!(matches!(
+            verify_connection(
+                &client_state,
+                &proof(20),
+                Some((time_policy, host_timestamp))
+            ),
+            Err(VerifyError::Proof(_))
+        ));
+        verify_connection(
+            &client_state,
+            &proof(25),
+            Some((time_policy, host_timestamp)),
+        )
+        .unwrap();
+        assert!(matches!(
+            verify(&client_state, &[0xff], None, "ibc", &path, vec![]),
+            Err(VerifyError::Proof(_))
+        ));
+        assert!(matches!(
+            verify_connection(&[0xff], &proof(20), None),
+            Err(VerifyError::Decode(_))
+        ));
+    }
+}